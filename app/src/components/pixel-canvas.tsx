@@ -25,7 +25,7 @@ import { Button } from './ui/button';
 import { Brush, Eraser, Grid2X2, Grid3X3, ImagePlus, LayoutGrid, ScanEye, Search, Settings, Unlock, Upload, Volume2, VolumeX, X } from 'lucide-react';
 import { cn } from '@/lib/utils';
 import { useGameSounds } from '../hooks/use-game-sounds';
-import { useMagicplaceProgram, COOLDOWN_LIMIT, COOLDOWN_PERIOD } from '../hooks/use-magicplace-program';
+import { useMagicplaceProgram, cooldownForTier } from '../hooks/use-magicplace-program';
 
 import { useMagicplaceEvents } from '../hooks/use-magicplace-events';
 // import { useReadonlyMode } from './start-using';
@@ -303,6 +303,8 @@ export function PixelCanvas() {
     const [unlockingShard, setUnlockingShard] = useState<{ x: number; y: number; status: string } | null>(null);
     const [shardMetadata, setShardMetadata] = useState<Map<string, { creator: string, pixelCount: number }>>(new Map());
     const [cooldownState, setCooldownState] = useState<{ placed: number, lastTimestamp: number }>({ placed: 0, lastTimestamp: 0 });
+    // Burst limit and period for the wallet's XP tier
+    const [cooldownLimits, setCooldownLimits] = useState(() => cooldownForTier(0));
     const [isSettingsOpen, setIsSettingsOpen] = useState(false);
     const [isSearchOpen, setIsSearchOpen] = useState(false);
     const [isImageUploadOpen, setIsImageUploadOpen] = useState(false);
@@ -325,7 +327,8 @@ export function PixelCanvas() {
         placePixelsBulkOnER,
         erasePixelOnER,
         getPixelFromShard,
-        fetchSessionAccount
+        fetchSessionAccount,
+        fetchCooldownTier
     } = useMagicplaceProgram();
 
     // Poll session account for cooldowns
@@ -350,19 +353,33 @@ export function PixelCanvas() {
         return () => clearInterval(interval);
     }, [sessionKey, fetchSessionAccount]);
 
+    // Track the wallet's XP tier, which raises the burst limit and shortens the period
+    useEffect(() => {
+        if (!wallet.publicKey) return;
+
+        const mainWallet = wallet.publicKey;
+        const updateTier = async () => {
+            setCooldownLimits(cooldownForTier(await fetchCooldownTier(mainWallet)));
+        };
+
+        updateTier();
+        const interval = setInterval(updateTier, 60000); // XP changes slowly
+        return () => clearInterval(interval);
+    }, [wallet.publicKey, fetchCooldownTier]);
+
     // Cooldown Limit Toast
     useEffect(() => {
-        if (cooldownState.placed >= COOLDOWN_LIMIT) {
+        if (cooldownState.placed >= cooldownLimits.limit) {
             const now = Math.floor(Date.now() / 1000);
             // Only show toast if the limit was reached extremely recently (<2s)
             // This prevents spam on page refresh if we are mid-cooldown
             if (now - cooldownState.lastTimestamp < 2) {
-                toast.error("Limit reached! Wait 30 seconds.");
+                toast.error(`Limit reached! Wait ${cooldownLimits.period} seconds.`);
                 // Show first-time cooldown explanation
                 actions.start(TourItems.CooldownLimitReached);
             }
         }
-    }, [cooldownState, actions]);
+    }, [cooldownState, cooldownLimits, actions]);
 
     // Readonly mode - hide interactions
     // Readonly mode - hide interactions
@@ -744,12 +761,12 @@ export function PixelCanvas() {
     // Place pixel at coordinates
     const handlePlacePixelAt = useCallback(async (px: number, py: number) => {
         // Check Cooldown
-        if (cooldownState.placed >= COOLDOWN_LIMIT) {
+        if (cooldownState.placed >= cooldownLimits.limit) {
             const now = Math.floor(Date.now() / 1000);
             const elapsed = now - cooldownState.lastTimestamp;
-            if (elapsed < COOLDOWN_PERIOD) {
+            if (elapsed < cooldownLimits.period) {
                 playFail();
-                toast.error(`Burst limit reached! Wait ${COOLDOWN_PERIOD - elapsed}s`);
+                toast.error(`Burst limit reached! Wait ${cooldownLimits.period - elapsed}s`);
                 // Show cooldown explanation dialog
                 actions.forceStart(TourItems.CooldownLimitReached);
                 return;
@@ -855,15 +872,15 @@ export function PixelCanvas() {
                     const now = Math.floor(Date.now() / 1000);
                     let { placed, lastTimestamp } = prev;
 
-                    if (placed >= COOLDOWN_LIMIT) {
-                        if (now - lastTimestamp >= COOLDOWN_PERIOD) {
+                    if (placed >= cooldownLimits.limit) {
+                        if (now - lastTimestamp >= cooldownLimits.period) {
                             placed = 0;
                         }
                     }
 
                     placed += 1;
 
-                    if (placed >= COOLDOWN_LIMIT) {
+                    if (placed >= cooldownLimits.limit) {
                         lastTimestamp = now;
                     }
 
@@ -928,7 +945,7 @@ export function PixelCanvas() {
             console.error("Failed to place pixel:", e);
             toast.error("Failed to place pixel: " + (e instanceof Error ? e.message : String(e)));
         }
-    }, [selectedColor, updateMarker, removeMarker, playPop, playFail, isShardLocked, placePixelOnER, erasePixelOnER, unlockingShard, zoomToLockedShard, cooldownState, initializeShard, posthog, wallet.publicKey, sessionKey, currentZoom]);
+    }, [selectedColor, updateMarker, removeMarker, playPop, playFail, isShardLocked, placePixelOnER, erasePixelOnER, unlockingShard, zoomToLockedShard, cooldownState, initializeShard, posthog, wallet.publicKey, sessionKey, currentZoom, cooldownLimits]);
 
 
 
@@ -1219,11 +1236,11 @@ export function PixelCanvas() {
 
         // Check if we have an active cooldown before starting
         // If we're at the limit, we must wait for cooldown to expire first
-        if (cooldownState.placed >= COOLDOWN_LIMIT) {
+        if (cooldownState.placed >= cooldownLimits.limit) {
             const now = Math.floor(Date.now() / 1000);
             const elapsed = now - cooldownState.lastTimestamp;
-            if (elapsed < COOLDOWN_PERIOD) {
-                const remaining = COOLDOWN_PERIOD - elapsed;
+            if (elapsed < cooldownLimits.period) {
+                const remaining = cooldownLimits.period - elapsed;
                 await waitWithCountdown(remaining);
             }
         }
@@ -1234,7 +1251,7 @@ export function PixelCanvas() {
         const userPubkey = wallet.publicKey?.toBase58();
         
         // Track cooldown state during placement
-        let currentCooldownCount = cooldownState.placed >= COOLDOWN_LIMIT ? 0 : cooldownState.placed;
+        let currentCooldownCount = cooldownState.placed >= cooldownLimits.limit ? 0 : cooldownState.placed;
 
         // Helper to check if user owns the shard (no cooldown for owners)
         const userOwnsShard = (shardX: number, shardY: number): boolean => {
@@ -1281,13 +1298,13 @@ export function PixelCanvas() {
                     let batchSize: number;
                     if (isOwner) {
                         // Owners have no cooldown - use max batch size
-                        batchSize = Math.min(COOLDOWN_LIMIT, shardPixels.length - shardIndex);
+                        batchSize = Math.min(cooldownLimits.limit, shardPixels.length - shardIndex);
                     } else {
                         // Non-owners: respect remaining cooldown capacity
-                        const remaining = COOLDOWN_LIMIT - currentCooldownCount;
+                        const remaining = cooldownLimits.limit - currentCooldownCount;
                         if (remaining <= 0) {
                             // Need to wait for cooldown to reset
-                            await waitWithCountdown(COOLDOWN_PERIOD);
+                            await waitWithCountdown(cooldownLimits.period);
                             currentCooldownCount = 0;
                             continue; // Re-check with fresh counter
                         }
@@ -1360,7 +1377,7 @@ export function PixelCanvas() {
             setIsStamping(false);
             setStampProgress(null);
         }
-    }, [stampPixelArt, isStamping, isShardLocked, isReadonly, sessionKey, wallet.publicKey, actions, placePixelsBulkOnER, updateMarker, playPop, playFail, zoomToLockedShard, shardMetadata, cooldownState]);
+    }, [stampPixelArt, isStamping, isShardLocked, isReadonly, sessionKey, wallet.publicKey, actions, placePixelsBulkOnER, updateMarker, playPop, playFail, zoomToLockedShard, shardMetadata, cooldownState, cooldownLimits]);
 
     // Combined click handler that handles both normal and stamp modes
     const handleMapClickCombined = useCallback((lat: number, lng: number) => {
@@ -1817,9 +1834,9 @@ export function PixelCanvas() {
                 {!isReadonly && (
                     <CooldownTimer
                         pixelsPlaced={cooldownState.placed}
-                        maxPixels={COOLDOWN_LIMIT}
+                        maxPixels={cooldownLimits.limit}
                         lastPlaceTimestamp={cooldownState.lastTimestamp}
-                        cooldownPeriod={COOLDOWN_PERIOD}
+                        cooldownPeriod={cooldownLimits.period}
                     />
                 )}
             </div>
@@ -29,10 +29,25 @@ export interface SessionAccount {
     bump: number;
 }
 
-// Cooldown Constants
+// Cooldown Constants (tier 0; see cooldownForTier)
 export const COOLDOWN_LIMIT = 60;
 export const COOLDOWN_PERIOD = 15; // seconds
 
+// XP tiers (must match contract): each threshold reached adds burst pixels and shortens the period
+const XP_TIER_THRESHOLDS = [1_000, 10_000, 100_000, 1_000_000];
+const XP_TIER_BURST_BONUS = 10;
+const XP_TIER_PERIOD_CUT = 2; // seconds
+
+/**
+ * Burst limit and cooldown period for an XP tier, mirroring the contract's cooldown_for_tier
+ */
+export function cooldownForTier(tier: number): { limit: number, period: number } {
+    return {
+        limit: COOLDOWN_LIMIT + tier * XP_TIER_BURST_BONUS,
+        period: COOLDOWN_PERIOD - tier * XP_TIER_PERIOD_CUT,
+    };
+}
+
 // Priority fee for base layer transactions (MicroLamports)
 const PRIORITY_FEE_MICRO_LAMPORTS = 200_000;
 
@@ -45,6 +60,21 @@ const SHARD_SEED = Buffer.from("shard");
 // Seed prefix for session account PDAs (must match contract: b"session")
 const SESSION_SEED = Buffer.from("session");
 
+// Seed prefix for per-wallet stats PDAs (must match contract: b"wallet_stats")
+const WALLET_STATS_SEED = Buffer.from("wallet_stats");
+const PROFILE_SEED = Buffer.from("profile");
+const FACTION_TERRITORY_SEED = Buffer.from("faction_territory");
+const REFERRAL_SEED = Buffer.from("referral");
+const REWARDS_SEED = Buffer.from("rewards");
+const FACTION_SEED = Buffer.from("faction");
+const SEASON_SEED = Buffer.from("season");
+const PAINTERS_SEED = Buffer.from("painters");
+const HISTORY_SEED = Buffer.from("history");
+
+// Optional shard layers (must match contract: PixelShard.layers bits)
+const LAYER_PAINTERS = 1 << 0;
+const LAYER_HISTORY = 1 << 1;
+
 // Delegation Program ID
 const DELEGATION_PROGRAM_ID = new PublicKey("DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh");
 
@@ -92,6 +122,110 @@ export function deriveSessionPDA(sessionKey: PublicKey): PublicKey {
     return pda;
 }
 
+/**
+ * Derive the PDA accruing a shard owner's visitor pixels (required when others paint its shards)
+ */
+export function deriveRewardsPDA(owner: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+        [REWARDS_SEED, owner.toBuffer()],
+        new PublicKey(IDL.address)
+    );
+    return pda;
+}
+
+/**
+ * Derive the PDA recording who referred a main wallet and how many it referred
+ */
+export function deriveReferralPDA(mainWallet: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+        [REFERRAL_SEED, mainWallet.toBuffer()],
+        new PublicKey(IDL.address)
+    );
+    return pda;
+}
+
+/**
+ * Derive the PDA holding a main wallet's XP and placement counters
+ */
+export function deriveProfilePDA(mainWallet: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+        [PROFILE_SEED, mainWallet.toBuffer()],
+        new PublicKey(IDL.address)
+    );
+    return pda;
+}
+
+/**
+ * Derive the PDA holding a main wallet's owned-shard counter and badges
+ */
+export function deriveWalletStatsPDA(mainWallet: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+        [WALLET_STATS_SEED, mainWallet.toBuffer()],
+        new PublicKey(IDL.address)
+    );
+    return pda;
+}
+
+/**
+ * Derive the PDA of a faction's pixel counters
+ */
+export function deriveFactionPDA(factionId: number): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+        [FACTION_SEED, Buffer.from([factionId])],
+        new PublicKey(IDL.address)
+    );
+    return pda;
+}
+
+/**
+ * Derive the PDA counting a faction's shards
+ */
+export function deriveFactionTerritoryPDA(factionId: number): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+        [FACTION_TERRITORY_SEED, Buffer.from([factionId])],
+        new PublicKey(IDL.address)
+    );
+    return pda;
+}
+
+/**
+ * Derive the PDA of the season singleton
+ */
+export function deriveSeasonPDA(): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+        [SEASON_SEED],
+        new PublicKey(IDL.address)
+    );
+    return pda;
+}
+
+/**
+ * Derive the PDA of one of a shard's optional layers (painters or history)
+ */
+function deriveShardLayerPDA(seed: Buffer, shardX: number, shardY: number): PublicKey {
+    const shardXBytes = Buffer.alloc(2);
+    shardXBytes.writeUInt16LE(shardX);
+    const shardYBytes = Buffer.alloc(2);
+    shardYBytes.writeUInt16LE(shardY);
+
+    const [pda] = PublicKey.findProgramAddressSync(
+        [seed, shardXBytes, shardYBytes],
+        new PublicKey(IDL.address)
+    );
+    return pda;
+}
+
+/**
+ * A shard's painters and history accounts for instructions that take them,
+ * null for the layers the shard doesn't have enabled
+ */
+function shardLayerAccounts(layers: number, shardX: number, shardY: number) {
+    return {
+        painters: layers & LAYER_PAINTERS ? deriveShardLayerPDA(PAINTERS_SEED, shardX, shardY) : null,
+        history: layers & LAYER_HISTORY ? deriveShardLayerPDA(HISTORY_SEED, shardX, shardY) : null,
+    };
+}
+
 /**
  * Hook to interact with the Magicplace program on Solana.
  * Provides functions to manage shards and pixels.
@@ -303,6 +437,60 @@ export function useMagicplaceProgram() {
        return null;
     }, [program, erProgram, readOnlyErProgram]);
 
+    /**
+     * Fetch a main wallet's profile (ER first, then base layer), null if it has none
+     */
+    const fetchProfile = useCallback(async (mainWallet: PublicKey) => {
+        const profilePDA = deriveProfilePDA(mainWallet);
+        for (const target of [erProgram || readOnlyErProgram, readOnlyProgram]) {
+            try {
+                const profile = await target.account.playerProfile.fetchNullable(profilePDA);
+                if (profile) return profile;
+            } catch (e) {
+                // Try the next layer
+            }
+        }
+        return null;
+    }, [erProgram, readOnlyErProgram, readOnlyProgram]);
+
+    /**
+     * Fetch a main wallet's XP tier from its profile. Returns 0 when there is no profile.
+     */
+    const fetchCooldownTier = useCallback(async (mainWallet: PublicKey): Promise<number> => {
+        const profile = await fetchProfile(mainWallet);
+        if (!profile) return 0;
+        const xp = profile.xp.toNumber();
+        return XP_TIER_THRESHOLDS.filter(t => xp >= t).length;
+    }, [fetchProfile]);
+
+    /**
+     * Accounts a paint instruction (placePixel, erasePixel, placePixelsBulk) needs besides
+     * the shard, session and signer: the shard's enabled layers, the painter's profile and
+     * faction, the owner's reward accrual when painting someone else's shard, and the season.
+     * Accounts that don't exist are passed as null.
+     */
+    const fetchPaintAccounts = useCallback(async (
+        target: Program<Magicplace>,
+        shardX: number,
+        shardY: number,
+        mainWallet: PublicKey
+    ) => {
+        const shard = await target.account.pixelShard.fetch(deriveShardPDA(shardX, shardY));
+        const profile = await fetchProfile(mainWallet);
+        const ownerRewardsPDA = deriveRewardsPDA(shard.creator);
+        const ownerRewards = shard.creator.equals(mainWallet)
+            ? null
+            : await target.account.rewardAccrual.fetchNullable(ownerRewardsPDA);
+
+        return {
+            ...shardLayerAccounts(shard.layers, shardX, shardY),
+            profile: profile ? deriveProfilePDA(mainWallet) : null,
+            ownerRewards: ownerRewards ? ownerRewardsPDA : null,
+            faction: profile && profile.faction !== 0 ? deriveFactionPDA(profile.faction) : null,
+            season: deriveSeasonPDA(),
+        };
+    }, [fetchProfile]);
+
     /**
      * Check if user can place a pixel on a specific shard based on cooldown rules.
      * Returns { allowed: boolean, reason?: string, remaining?: number, refreshIn?: number }
@@ -313,6 +501,8 @@ export function useMagicplaceProgram() {
             return { allowed: true };
         }
 
+        const { limit, period } = cooldownForTier(await fetchCooldownTier(mainWallet));
+
         // 2. Fetch session account to check limits
         // We need the active session key
         if (!sessionKey.keypair) {
@@ -322,13 +512,13 @@ export function useMagicplaceProgram() {
              // But here we don't have the signer passed as arg.
              // Assume we use the active session key from valid hook if exists, else main wallet?
              // But wait, the hook uses `sessionKey.keypair` if active.
-             return { allowed: true, remaining: COOLDOWN_LIMIT }; // Fallback
+             return { allowed: true, remaining: limit }; // Fallback
         }
 
         const session = await fetchSessionAccount(sessionKey.keypair.publicKey);
         if (!session) {
             // No session account means fresh state (all zeros), allowed to start
-            return { allowed: true, remaining: COOLDOWN_LIMIT };
+            return { allowed: true, remaining: limit };
         }
 
         const now = Math.floor(Date.now() / 1000);
@@ -340,29 +530,29 @@ export function useMagicplaceProgram() {
         //      session.cooldown_counter = 0;
         // }
         let currentCounter = session.cooldownCounter;
-        if (timeDiff >= COOLDOWN_PERIOD) {
+        if (timeDiff >= period) {
             currentCounter = 0;
         }
 
         // if session.cooldown_counter >= COOLDOWN_LIMIT { return err!(PixelError::Cooldown); }
-        if (currentCounter >= COOLDOWN_LIMIT) {
-             const waitTime = COOLDOWN_PERIOD - timeDiff; // This might be weird if timeDiff > COOLDOWN_PERIOD, but in that case counter is 0.
-             // Wait, if counter >= LIMIT, it means timeDiff < COOLDOWN_PERIOD necessarily (unless logic error).
-             // Actually, if timeDiff >= COOLDOWN_PERIOD, counter resets to 0, so it won't be >= LIMIT.
-             // So here timeDiff < COOLDOWN_PERIOD.
+        if (currentCounter >= limit) {
+             const waitTime = period - timeDiff; // This might be weird if timeDiff > period, but in that case counter is 0.
+             // Wait, if counter >= LIMIT, it means timeDiff < period necessarily (unless logic error).
+             // Actually, if timeDiff >= period, counter resets to 0, so it won't be >= LIMIT.
+             // So here timeDiff < period.
              return { 
                  allowed: false, 
                  reason: `Cooldown active. limit reached.`, 
-                 refreshIn: Math.max(0, COOLDOWN_PERIOD - timeDiff) 
+                 refreshIn: Math.max(0, period - timeDiff) 
              };
         }
 
         return { 
             allowed: true, 
-            remaining: COOLDOWN_LIMIT - currentCounter 
+            remaining: limit - currentCounter 
         };
 
-    }, [fetchSessionAccount]);
+    }, [fetchSessionAccount, fetchCooldownTier]);
 
     // ========================================
     // Shard Query Functions
@@ -527,12 +717,14 @@ export function useMagicplaceProgram() {
      * @param mainWallet - The main wallet public key
      * @param authSignature - The authorization signature from main wallet (second signature)
      * @param authMessage - The message that was signed (for Ed25519 verification)
+     * @param referrer - Main wallet that referred this one; must be signed into authMessage
      */
     const initializeUser = useCallback(async (
         sessionKeypair: Keypair,
         mainWallet: PublicKey,
         authSignature: Uint8Array,
-        authMessage?: string
+        authMessage?: string,
+        referrer?: PublicKey
     ): Promise<string> => {
         if (!program) {
             throw new Error("Program not initialized");
@@ -546,7 +738,8 @@ export function useMagicplaceProgram() {
             const { Ed25519Program, SYSVAR_INSTRUCTIONS_PUBKEY } = await import("@solana/web3.js");
             
             // Generate the authorization message if not provided
-            const message = authMessage || `Authorize session key: ${sessionKeypair.publicKey.toBase58()} for wallet: ${mainWallet.toBase58()} on Magicplace`;
+            const message = authMessage || `Authorize session key: ${sessionKeypair.publicKey.toBase58()} for wallet: ${mainWallet.toBase58()} on Magicplace`
+                + (referrer ? ` referred by: ${referrer.toBase58()}` : "");
             const messageBytes = new TextEncoder().encode(message);
             
             // Create Ed25519 signature verification instruction
@@ -559,11 +752,14 @@ export function useMagicplaceProgram() {
             
             // Build the program instruction
             const programIx = await program.methods
-                .initializeUser(mainWallet, Array.from(authSignature) as number[])
+                .initializeUser(mainWallet, Array.from(authSignature) as number[], referrer ?? null)
                 .accounts({
                     authority: sessionKeypair.publicKey,
                     // @ts-ignore
                     instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                    // Referral records are only touched when a referrer is given
+                    referral: referrer ? deriveReferralPDA(mainWallet) : null,
+                    referrerReferral: referrer ? deriveReferralPDA(referrer) : null,
                 })
                 .instruction();
                 
@@ -705,11 +901,13 @@ export function useMagicplaceProgram() {
         try {
             // For "send from wallet", the wallet.publicKey IS the signer (authority)
             // So the session account is derived from wallet.publicKey
+            const paintAccounts = await fetchPaintAccounts(program, shardX, shardY, wallet.publicKey);
             const tx = await program.methods
                 .placePixel(shardX, shardY, px, py, color)
-                .accounts({
+                .accountsPartial({
                     signer: wallet.publicKey,
                     // session PDA auto-derived from signer
+                    ...paintAccounts,
                 })
                 .rpc();
 
@@ -721,7 +919,7 @@ export function useMagicplaceProgram() {
         } finally {
             setIsLoading(false);
         }
-    }, [program, wallet.publicKey]);
+    }, [program, wallet.publicKey, fetchPaintAccounts]);
 
     /**
      * Erase a pixel at global coordinates (px, py) - sets it to 0/transparent
@@ -737,11 +935,13 @@ export function useMagicplaceProgram() {
         setError(null);
 
         try {
+            const paintAccounts = await fetchPaintAccounts(program, shardX, shardY, wallet.publicKey);
             const tx = await program.methods
                 .erasePixel(shardX, shardY, px, py)
-                .accounts({
+                .accountsPartial({
                     signer: wallet.publicKey,
                     // session PDA auto-derived from signer
+                    ...paintAccounts,
                 })
                 .rpc();
 
@@ -753,7 +953,7 @@ export function useMagicplaceProgram() {
         } finally {
             setIsLoading(false);
         }
-    }, [program, wallet.publicKey]);
+    }, [program, wallet.publicKey, fetchPaintAccounts]);
 
     // ========================================
     // Ephemeral Rollups Pixel Functions
@@ -766,7 +966,7 @@ export function useMagicplaceProgram() {
     const placePixelOnER = useCallback(async (px: number, py: number, color: number): Promise<string> => {
         console.log(`[placePixelOnER] Starting: px=${px}, py=${py}, color=${color}`);
         
-        if (!sessionProgram || !sessionKey.keypair) {
+        if (!sessionProgram || !sessionKey.keypair || !wallet.publicKey) {
             console.error("[placePixelOnER] Session program or key not available");
             throw new Error("Session program or key not available");
         }
@@ -784,10 +984,12 @@ export function useMagicplaceProgram() {
 
         try {
             console.log("[placePixelOnER] Building instruction...");
+            const paintAccounts = await fetchPaintAccounts(erProgram || readOnlyErProgram, shardX, shardY, wallet.publicKey);
             const placeIx = await sessionProgram.methods
                 .placePixel(shardX, shardY, px, py, color)
-                .accounts({
+                .accountsPartial({
                     signer: sessionKey.keypair.publicKey,
+                    ...paintAccounts,
                 })
                 .instruction();
             console.log("[placePixelOnER] Instruction built successfully");
@@ -830,14 +1032,14 @@ export function useMagicplaceProgram() {
         } finally {
             setIsLoading(false);
         }
-    }, [sessionProgram, sessionKey.keypair, erConnection]);
+    }, [sessionProgram, sessionKey.keypair, erConnection, erProgram, readOnlyErProgram, wallet.publicKey, fetchPaintAccounts]);
 
     /**
      * Erase a pixel on Ephemeral Rollups (when shard is delegated)
      * Uses session key for signing - no wallet popup needed
      */
     const erasePixelOnER = useCallback(async (px: number, py: number): Promise<string> => {
-        if (!sessionProgram || !sessionKey.keypair) {
+        if (!sessionProgram || !sessionKey.keypair || !wallet.publicKey) {
             throw new Error("Session program or key not available");
         }
 
@@ -848,11 +1050,13 @@ export function useMagicplaceProgram() {
 
         try {
             // Build instruction using session program for IDL
+            const paintAccounts = await fetchPaintAccounts(erProgram || readOnlyErProgram, shardX, shardY, wallet.publicKey);
             const eraseIx = await sessionProgram.methods
                 .erasePixel(shardX, shardY, px, py)
-                .accounts({
+                .accountsPartial({
                     signer: sessionKey.keypair.publicKey,
                     // session -> auto-derived from signer
+                    ...paintAccounts,
                 })
                 .instruction();
 
@@ -877,7 +1081,7 @@ export function useMagicplaceProgram() {
         } finally {
             setIsLoading(false);
         }
-    }, [sessionProgram, sessionKey.keypair, erConnection]);
+    }, [sessionProgram, sessionKey.keypair, erConnection, erProgram, readOnlyErProgram, wallet.publicKey, fetchPaintAccounts]);
 
     /**
      * Place multiple pixels in bulk on Ephemeral Rollups (when shard is delegated)
@@ -896,7 +1100,7 @@ export function useMagicplaceProgram() {
     ): Promise<string> => {
         console.log(`[placePixelsBulkOnER] Starting: shard=(${shardX}, ${shardY}), pixels=${pixels.length}`);
         
-        if (!sessionProgram || !sessionKey.keypair) {
+        if (!sessionProgram || !sessionKey.keypair || !wallet.publicKey) {
             console.error("[placePixelsBulkOnER] Session program or key not available");
             throw new Error("Session program or key not available");
         }
@@ -932,10 +1136,12 @@ export function useMagicplaceProgram() {
                 color: p.color,
             }));
 
+            const paintAccounts = await fetchPaintAccounts(erProgram || readOnlyErProgram, shardX, shardY, wallet.publicKey);
             const bulkIx = await sessionProgram.methods
                 .placePixelsBulk(shardX, shardY, bulkPixels)
-                .accounts({
+                .accountsPartial({
                     signer: sessionKey.keypair.publicKey,
+                    ...paintAccounts,
                 })
                 .instruction();
             console.log("[placePixelsBulkOnER] Instruction built successfully");
@@ -975,7 +1181,7 @@ export function useMagicplaceProgram() {
             setError(message);
             throw err;
         }
-    }, [sessionProgram, sessionKey.keypair, erConnection]);
+    }, [sessionProgram, sessionKey.keypair, erConnection, erProgram, readOnlyErProgram, wallet.publicKey, fetchPaintAccounts]);
 
     // ========================================
     // Ephemeral Rollups Shard Functions
@@ -1147,7 +1353,7 @@ export function useMagicplaceProgram() {
         shardY: number,
         onStatusUpdate?: (status: string) => void
     ): Promise<string> => {
-        if (!program || !sessionKey.keypair) {
+        if (!program || !sessionKey.keypair || !wallet.publicKey) {
             throw new Error("Program or session key not available");
         }
 
@@ -1215,13 +1421,22 @@ export function useMagicplaceProgram() {
                     microLamports: PRIORITY_FEE_MICRO_LAMPORTS,
                 });
 
+                // Once the wallet's shards count for a faction, the new one joins them
+                const walletStatsPDA = deriveWalletStatsPDA(wallet.publicKey);
+                const stats = await readOnlyProgram.account.walletStats.fetchNullable(walletStatsPDA);
+                const factionTerritory = stats && stats.territoryFaction !== 0
+                    ? deriveFactionTerritoryPDA(stats.territoryFaction)
+                    : null;
+
                 // Build transaction manually using session program (for IDL) 
                 // and sign with session key
                 const initIx = await sessionProgram.methods
-                    .initializeShard(shardX, shardY)
-                    .accounts({
+                    .initializeShard(shardX, shardY, wallet.publicKey)
+                    .accountsPartial({
                         authority: sessionKey.keypair.publicKey,
                         // session -> auto-derived from authority (session key)
+                        walletStats: walletStatsPDA,
+                        factionTerritory,
                     })
                     .instruction();
 
@@ -1251,6 +1466,37 @@ export function useMagicplaceProgram() {
                 }
                 
                 console.log("Initialized shard:", initTxSig);
+
+                // Visitor pixels only earn the owner rewards once its accrual exists on the ER
+                const rewardsPDA = deriveRewardsPDA(wallet.publicKey);
+                if (!(await connection.getAccountInfo(rewardsPDA))) {
+                    const rewardsInitIx = await sessionProgram.methods
+                        .initializeRewards(wallet.publicKey)
+                        .accounts({
+                            payer: sessionKey.keypair.publicKey,
+                        })
+                        .instruction();
+                    const rewardsDelegateIx = await sessionProgram.methods
+                        .delegateRewards(wallet.publicKey)
+                        .accounts({
+                            authority: sessionKey.keypair.publicKey,
+                        })
+                        .remainingAccounts([
+                            { pubkey: new PublicKey("MAS1Dt9qreoRMQ14YQuhg8UTZMMzDdKhmkZMECCzk57"), isSigner: false, isWritable: false }
+                        ])
+                        .instruction();
+
+                    const rewardsTx = new Transaction().add(rewardsInitIx, rewardsDelegateIx);
+                    rewardsTx.feePayer = sessionKey.keypair.publicKey;
+                    const rewardsBlockhash = await connection.getLatestBlockhash();
+                    rewardsTx.recentBlockhash = rewardsBlockhash.blockhash;
+                    rewardsTx.sign(sessionKey.keypair);
+                    const rewardsSig = await connection.sendRawTransaction(rewardsTx.serialize(), {
+                        skipPreflight: true,
+                    });
+                    await connection.confirmTransaction({ signature: rewardsSig, ...rewardsBlockhash }, "confirmed");
+                    console.log("Initialized and delegated reward accrual:", rewardsSig);
+                }
                 
                 // After initialization, check again
                 const newAccountInfo = await connection.getAccountInfo(shardPDA);
@@ -1318,7 +1564,7 @@ export function useMagicplaceProgram() {
         } finally {
             setIsLoading(false);
         }
-    }, [connection, sessionProgram, sessionKey.keypair, delegateShardWithSession]);
+    }, [connection, readOnlyProgram, sessionProgram, sessionKey.keypair, wallet.publicKey, delegateShardWithSession]);
 
     /**
      * Commit shard state from ER to base layer
@@ -1337,11 +1583,15 @@ export function useMagicplaceProgram() {
         setError(null);
 
         try {
+            // Layers enabled on the shard are committed along with it
+            const shard = await (erProgram || readOnlyErProgram).account.pixelShard.fetch(deriveShardPDA(shardX, shardY));
+
             // Build transaction using base program
             let tx = await program.methods
                 .commitShard(shardX, shardY)
-                .accounts({
+                .accountsPartial({
                     payer: wallet.publicKey,
+                    ...shardLayerAccounts(shard.layers, shardX, shardY),
                 })
                 .transaction();
 
@@ -1373,7 +1623,7 @@ export function useMagicplaceProgram() {
         } finally {
             setIsLoading(false);
         }
-    }, [program, erProvider, erConnection, wallet.publicKey]);
+    }, [program, erProvider, erProgram, readOnlyErProgram, erConnection, wallet.publicKey]);
 
     // ========================================
     // Pixel Utility Functions
@@ -1466,6 +1716,7 @@ export function useMagicplaceProgram() {
         checkUserDelegation, // Exporting this function
         deriveSessionPDA,
         fetchSessionAccount,
+        fetchCooldownTier,
         checkCanPlacePixel,
 
         // Pixel operations (base layer)
@@ -31,16 +31,19 @@ export interface CreateSessionKeyOptions {
     duration?: number;
     /** Custom salt to make the session key unique per use case */
     salt?: string;
+    /** Main wallet that referred this one, signed into the authorization message */
+    referrer?: import("@solana/web3.js").PublicKey;
     /** 
      * Callback to create the on-chain session account after getting signatures.
-     * Called with (sessionKeypair, mainWallet, authSignature, authMessage).
+     * Called with (sessionKeypair, mainWallet, authSignature, authMessage, referrer).
      * Should call initializeUser from useMagicplaceProgram.
      */
     onCreateAccount?: (
         sessionKeypair: import("@solana/web3.js").Keypair,
         mainWallet: import("@solana/web3.js").PublicKey,
         authSignature: Uint8Array,
-        authMessage: string
+        authMessage: string,
+        referrer?: import("@solana/web3.js").PublicKey
     ) => Promise<string>;
     /**
      * Optional callback after key is derived but before authorization.
@@ -72,8 +75,9 @@ function generateDerivationMessage(walletPubkey: PublicKey): string {
  * This is the second signature - proves the main wallet authorized this specific session key.
  * This message format MUST match what the Solana program expects.
  */
-function generateAuthorizationMessage(sessionKeyPubkey: PublicKey, mainWalletPubkey: PublicKey): string {
-    return `Authorize session key: ${sessionKeyPubkey.toBase58()} for wallet: ${mainWalletPubkey.toBase58()} on Magicplace`;
+function generateAuthorizationMessage(sessionKeyPubkey: PublicKey, mainWalletPubkey: PublicKey, referrer?: PublicKey): string {
+    const message = `Authorize session key: ${sessionKeyPubkey.toBase58()} for wallet: ${mainWalletPubkey.toBase58()} on Magicplace`;
+    return referrer ? `${message} referred by: ${referrer.toBase58()}` : message;
 }
 
 /**
@@ -210,6 +214,7 @@ export function SessionKeyProvider({ children }: { children: ReactNode }) {
             message,
             duration = DEFAULT_SESSION_DURATION,
             salt = "default",
+            referrer,
             onCreateAccount,
             onKeyDerived,
         } = options;
@@ -240,7 +245,7 @@ export function SessionKeyProvider({ children }: { children: ReactNode }) {
             if (proceed) {
                 // SIGNATURE 2: Authorize this specific session key
                 // This proves the main wallet authorized THIS session key (not just any key)
-                const authMessage = generateAuthorizationMessage(keypair.publicKey, wallet.publicKey);
+                const authMessage = generateAuthorizationMessage(keypair.publicKey, wallet.publicKey, referrer);
                 const authMessageBytes = new TextEncoder().encode(authMessage);
                 
                 // Request second signature from wallet (popup 2)
@@ -248,7 +253,7 @@ export function SessionKeyProvider({ children }: { children: ReactNode }) {
                 
                 // STEP 3: Create on-chain session account (if callback provided)
                 if (onCreateAccount) {
-                    await onCreateAccount(keypair, wallet.publicKey, authSignature, authMessage);
+                    await onCreateAccount(keypair, wallet.publicKey, authSignature, authMessage, referrer);
                 }
             }
             
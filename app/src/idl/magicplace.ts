@@ -14,28 +14,42 @@ export type Magicplace = {
   },
   "instructions": [
     {
-      "name": "commitShard",
+      "name": "archiveSeason",
       "docs": [
-        "Commit shard state from ER to base layer"
+        "Snapshot a finalized season's scores into its own account (base layer, permissionless)",
+        "Reads the season singleton as committed by finalize_season."
       ],
       "discriminator": [
-        85,
-        249,
-        246,
-        67,
-        192,
-        89,
         165,
-        50
+        31,
+        153,
+        184,
+        100,
+        101,
+        32,
+        15
       ],
       "accounts": [
         {
-          "name": "payer",
-          "writable": true,
-          "signer": true
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
         },
         {
-          "name": "shard",
+          "name": "result",
           "writable": true,
           "pda": {
             "seeds": [
@@ -43,71 +57,67 @@ export type Magicplace = {
                 "kind": "const",
                 "value": [
                   115,
-                  104,
+                  101,
                   97,
-                  114,
-                  100
+                  115,
+                  111,
+                  110
                 ]
               },
               {
                 "kind": "arg",
-                "path": "shardX"
-              },
-              {
-                "kind": "arg",
-                "path": "shardY"
+                "path": "seasonId"
               }
             ]
           }
         },
         {
-          "name": "magicProgram",
-          "address": "Magic11111111111111111111111111111111111111"
+          "name": "payer",
+          "writable": true,
+          "signer": true
         },
         {
-          "name": "magicContext",
-          "writable": true,
-          "address": "MagicContext1111111111111111111111111111111"
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "shardX",
-          "type": "u16"
-        },
-        {
-          "name": "shardY",
-          "type": "u16"
+          "name": "seasonId",
+          "type": "u32"
         }
       ]
     },
     {
-      "name": "delegateShard",
+      "name": "checkTemplateProgress",
       "docs": [
-        "Delegate an existing shard to Ephemeral Rollups",
-        "This should be called after initialize_shard in a separate transaction"
+        "Compare a locked template against the canvas and record its completion (base layer)",
+        "Permissionless. Every shard the template covers must be passed as a remaining",
+        "account, followed by its ShardPainters account; the painter layer must still be enabled.",
+        "Delegated shards and painter layers are read at their last committed state.",
+        "",
+        "The first check at or above the threshold before the bounty's deadline completes it:",
+        "matching pixels are credited to their last painter and the top contributors are",
+        "snapshotted for claim_bounty. Partner programs are not credited. Without any",
+        "attributed pixel the bounty stays open."
       ],
       "discriminator": [
-        193,
-        43,
-        22,
-        82,
-        105,
-        20,
-        98,
-        22
+        251,
+        164,
+        99,
+        239,
+        181,
+        39,
+        49,
+        12
       ],
       "accounts": [
         {
-          "name": "authority",
-          "docs": [
-            "The authority requesting delegation"
-          ],
-          "writable": true,
-          "signer": true
+          "name": "template",
+          "writable": true
         },
         {
-          "name": "bufferPda",
+          "name": "bounty",
           "writable": true,
           "pda": {
             "seeds": [
@@ -115,161 +125,194 @@ export type Magicplace = {
                 "kind": "const",
                 "value": [
                   98,
+                  111,
                   117,
-                  102,
-                  102,
-                  101,
-                  114
+                  110,
+                  116,
+                  121
                 ]
               },
               {
                 "kind": "account",
-                "path": "pda"
+                "path": "template"
               }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                55,
-                86,
-                190,
-                231,
-                187,
-                241,
-                178,
-                93,
-                156,
-                164,
-                97,
-                165,
-                93,
-                69,
-                109,
-                254,
-                60,
-                140,
-                218,
-                139,
-                104,
-                66,
-                16,
-                9,
-                139,
-                59,
-                136,
-                184,
-                153,
-                69,
-                121,
-                115
-              ]
-            }
+            ]
           }
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimBadge",
+      "docs": [
+        "Mint badge `badge` to the caller once they own enough shards (base layer)",
+        "Idempotent: claiming a badge the wallet already holds succeeds without minting again."
+      ],
+      "discriminator": [
+        111,
+        30,
+        18,
+        17,
+        228,
+        252,
+        239,
+        102
+      ],
+      "accounts": [
         {
-          "name": "delegationRecordPda",
+          "name": "walletStats",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  100,
-                  101,
+                  119,
+                  97,
+                  108,
                   108,
                   101,
-                  103,
+                  116,
+                  95,
+                  115,
+                  116,
                   97,
                   116,
-                  105,
-                  111,
-                  110
+                  115
                 ]
               },
               {
                 "kind": "account",
-                "path": "pda"
+                "path": "owner"
               }
-            ],
-            "program": {
-              "kind": "account",
-              "path": "delegationProgram"
-            }
+            ]
           }
         },
         {
-          "name": "delegationMetadataPda",
+          "name": "badgeMint",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
+                  98,
+                  97,
                   100,
-                  101,
-                  108,
-                  101,
                   103,
-                  97,
-                  116,
+                  101,
+                  95,
+                  109,
                   105,
-                  111,
                   110,
-                  45,
-                  109,
-                  101,
-                  116,
-                  97,
-                  100,
-                  97,
-                  116,
-                  97
+                  116
                 ]
               },
               {
-                "kind": "account",
-                "path": "pda"
+                "kind": "arg",
+                "path": "badge"
               }
-            ],
-            "program": {
-              "kind": "account",
-              "path": "delegationProgram"
-            }
+            ]
           }
         },
         {
-          "name": "pda",
-          "writable": true,
+          "name": "mintAuthority",
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  115,
-                  104,
+                  109,
+                  105,
+                  110,
+                  116,
+                  95,
                   97,
+                  117,
+                  116,
+                  104,
+                  111,
                   114,
-                  100
+                  105,
+                  116,
+                  121
                 ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerBadgeAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
               },
               {
-                "kind": "arg",
-                "path": "shardX"
+                "kind": "account",
+                "path": "tokenProgram"
               },
               {
-                "kind": "arg",
-                "path": "shardY"
+                "kind": "account",
+                "path": "badgeMint"
               }
-            ]
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
           }
         },
         {
-          "name": "ownerProgram",
-          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+          "name": "owner",
+          "docs": [
+            "The shard owner's main wallet"
+          ],
+          "writable": true,
+          "signer": true
         },
         {
-          "name": "delegationProgram",
-          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+          "name": "tokenProgram",
+          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
         },
         {
           "name": "systemProgram",
@@ -278,357 +321,419 @@ export type Magicplace = {
       ],
       "args": [
         {
-          "name": "shardX",
-          "type": "u16"
-        },
-        {
-          "name": "shardY",
-          "type": "u16"
+          "name": "badge",
+          "type": "u8"
         }
       ]
     },
     {
-      "name": "delegateUser",
+      "name": "claimBounty",
       "docs": [
-        "Delegate a user session account to Ephemeral Rollups",
-        "This should be called after initialize_user in a separate transaction"
+        "Pay a contributor's share of a completed bounty (base layer, contributor's main wallet)",
+        "The share is proportional to the contributor's matching pixels at completion."
       ],
       "discriminator": [
-        237,
-        220,
-        199,
-        24,
-        59,
-        41,
-        247,
-        24
+        225,
+        157,
+        163,
+        238,
+        239,
+        169,
+        75,
+        226
       ],
       "accounts": [
         {
-          "name": "user",
-          "docs": [
-            "The session account to delegate"
-          ],
+          "name": "bounty",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  115,
-                  101,
-                  115,
-                  115,
-                  105,
+                  98,
                   111,
-                  110
+                  117,
+                  110,
+                  116,
+                  121
                 ]
               },
               {
                 "kind": "account",
-                "path": "authority"
+                "path": "bounty.template",
+                "account": "bounty"
               }
             ]
           }
         },
         {
-          "name": "authority",
+          "name": "contributor",
           "docs": [
-            "The session key authority"
+            "Contributor's main wallet, as recorded by the painter layer"
           ],
           "writable": true,
           "signer": true
-        },
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRewards",
+      "docs": [
+        "Mint every accrued but unclaimed reward to the owner's token account (base layer)",
+        "Reads the last committed accrual (see commit_rewards) and only moves the base-layer",
+        "claim counter, so the accrual can stay delegated. An owner with a referrer must",
+        "pass the referrer's accounts; the referrer's share is taken out of the owner's amount."
+      ],
+      "discriminator": [
+        4,
+        144,
+        132,
+        71,
+        116,
+        23,
+        151,
+        80
+      ],
+      "accounts": [
         {
-          "name": "bufferPda",
-          "writable": true,
+          "name": "rewards",
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  98,
-                  117,
-                  102,
-                  102,
+                  114,
                   101,
-                  114
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
                 ]
               },
               {
                 "kind": "account",
-                "path": "pda"
+                "path": "owner"
               }
-            ],
-            "program": {
-              "kind": "const",
-              "value": [
-                55,
-                86,
-                190,
-                231,
-                187,
-                241,
-                178,
-                93,
-                156,
-                164,
-                97,
-                165,
-                93,
-                69,
-                109,
-                254,
-                60,
-                140,
-                218,
-                139,
-                104,
-                66,
-                16,
-                9,
-                139,
-                59,
-                136,
-                184,
-                153,
-                69,
-                121,
-                115
-              ]
-            }
+            ]
           }
         },
         {
-          "name": "delegationRecordPda",
+          "name": "claim",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  100,
+                  114,
                   101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  95,
+                  99,
                   108,
-                  101,
-                  103,
                   97,
-                  116,
                   105,
-                  111,
-                  110
+                  109
                 ]
               },
               {
                 "kind": "account",
-                "path": "pda"
+                "path": "owner"
               }
-            ],
-            "program": {
-              "kind": "account",
-              "path": "delegationProgram"
-            }
+            ]
           }
         },
         {
-          "name": "delegationMetadataPda",
+          "name": "rewardMint",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  100,
-                  101,
-                  108,
+                  114,
                   101,
-                  103,
+                  119,
                   97,
-                  116,
+                  114,
+                  100,
+                  95,
+                  109,
                   105,
-                  111,
                   110,
-                  45,
+                  116
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
                   109,
-                  101,
+                  105,
+                  110,
                   116,
+                  95,
                   97,
-                  100,
-                  97,
+                  117,
                   116,
-                  97
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerTokenAccount",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "owner"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
                 ]
               },
               {
                 "kind": "account",
-                "path": "pda"
+                "path": "rewardMint"
               }
             ],
             "program": {
-              "kind": "account",
-              "path": "delegationProgram"
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
             }
           }
         },
         {
-          "name": "pda",
+          "name": "owner",
+          "docs": [
+            "The shard owner's main wallet"
+          ],
           "writable": true,
+          "signer": true
+        },
+        {
+          "name": "ownerReferral",
+          "docs": [
+            "referrer's record and reward token account are required and the referral share is paid out of the claim."
+          ],
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  115,
+                  114,
                   101,
-                  115,
-                  115,
-                  105,
-                  111,
-                  110
+                  102,
+                  101,
+                  114,
+                  114,
+                  97,
+                  108
                 ]
               },
               {
                 "kind": "account",
-                "path": "authority"
+                "path": "owner"
               }
             ]
           }
         },
         {
-          "name": "ownerProgram",
-          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+          "name": "referrerReferral",
+          "writable": true,
+          "optional": true
         },
         {
-          "name": "delegationProgram",
-          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+          "name": "referrerTokenAccount",
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
         },
         {
           "name": "systemProgram",
           "address": "11111111111111111111111111111111"
         }
       ],
-      "args": [
-        {
-          "name": "mainWallet",
-          "type": "pubkey"
-        }
-      ]
+      "args": []
     },
     {
-      "name": "erasePixel",
+      "name": "commitRewards",
       "docs": [
-        "Erase a pixel (set to 0/transparent)"
+        "Commit a shard owner's reward accrual to the base layer so it can be claimed"
       ],
       "discriminator": [
-        6,
+        230,
+        240,
+        251,
+        224,
+        134,
         38,
-        248,
-        220,
-        47,
-        82,
-        57,
-        222
+        190,
+        64
       ],
       "accounts": [
         {
-          "name": "shard",
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "rewards",
           "writable": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  115,
-                  104,
+                  114,
+                  101,
+                  119,
                   97,
                   114,
-                  100
+                  100,
+                  115
                 ]
               },
               {
                 "kind": "arg",
-                "path": "shardX"
-              },
-              {
-                "kind": "arg",
-                "path": "shardY"
+                "path": "owner"
               }
             ]
           }
         },
         {
-          "name": "session",
-          "writable": true,
-          "pda": {
-            "seeds": [
-              {
-                "kind": "const",
-                "value": [
-                  115,
-                  101,
-                  115,
-                  115,
-                  105,
-                  111,
-                  110
-                ]
-              },
-              {
-                "kind": "account",
-                "path": "signer"
-              }
-            ]
-          }
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
         },
         {
-          "name": "signer",
+          "name": "magicContext",
           "writable": true,
-          "signer": true
+          "address": "MagicContext1111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "shardX",
-          "type": "u16"
-        },
-        {
-          "name": "shardY",
-          "type": "u16"
-        },
-        {
-          "name": "px",
-          "type": "u32"
-        },
-        {
-          "name": "py",
-          "type": "u32"
+          "name": "owner",
+          "type": "pubkey"
         }
       ]
     },
     {
-      "name": "initializeShard",
+      "name": "commitShard",
       "docs": [
-        "Initialize a shard at (shard_x, shard_y) coordinates (without delegation)",
-        "Shards are created on-demand when a user wants to paint in that region",
-        "shard_x, shard_y: 0-4095 (4096 shards per dimension)",
-        "Call delegate_shard separately after this to delegate to ER"
+        "Commit shard state from ER to base layer"
       ],
       "discriminator": [
-        100,
-        96,
-        88,
-        58,
-        225,
-        178,
-        9,
-        147
+        85,
+        249,
+        246,
+        67,
+        192,
+        89,
+        165,
+        50
       ],
       "accounts": [
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
         {
           "name": "shard",
-          "docs": [
-            "The shard account to initialize"
-          ],
           "writable": true,
           "pda": {
             "seeds": [
@@ -654,144 +759,112 @@ export type Magicplace = {
           }
         },
         {
-          "name": "session",
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, committed alongside the shard when passed"
+          ],
+          "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  115,
-                  101,
-                  115,
-                  115,
+                  112,
+                  97,
                   105,
-                  111,
-                  110
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
                 ]
               },
               {
-                "kind": "account",
-                "path": "authority"
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
               }
             ]
           }
         },
         {
-          "name": "authority",
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
-        }
-      ],
-      "args": [
-        {
-          "name": "shardX",
-          "type": "u16"
-        },
-        {
-          "name": "shardY",
-          "type": "u16"
-        }
-      ]
-    },
-    {
-      "name": "initializeUser",
-      "discriminator": [
-        111,
-        17,
-        185,
-        250,
-        60,
-        122,
-        38,
-        254
-      ],
-      "accounts": [
-        {
-          "name": "user",
+          "name": "history",
           "docs": [
-            "Session account PDA derived from the MAIN wallet (not session key)",
-            "This ensures each main wallet has exactly one session account"
+            "Change history, committed alongside the shard when passed"
           ],
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  115,
-                  101,
-                  115,
-                  115,
+                  104,
                   105,
+                  115,
+                  116,
                   111,
-                  110
+                  114,
+                  121
                 ]
               },
               {
-                "kind": "account",
-                "path": "authority"
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
               }
             ]
           }
         },
         {
-          "name": "authority",
-          "docs": [
-            "The session key that is authorized to act on behalf of main_wallet"
-          ],
-          "writable": true,
-          "signer": true
-        },
-        {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
         },
         {
-          "name": "instructionsSysvar",
-          "address": "Sysvar1nstructions1111111111111111111111111"
+          "name": "magicContext",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "mainWallet",
-          "type": "pubkey"
+          "name": "shardX",
+          "type": "u16"
         },
         {
-          "name": "signature",
-          "type": {
-            "array": [
-              "u8",
-              64
-            ]
-          }
+          "name": "shardY",
+          "type": "u16"
         }
       ]
     },
     {
-      "name": "placePixel",
+      "name": "copyRegion",
       "docs": [
-        "Place a pixel using global coordinates",
-        "px, py: 0 to 524,287 (global pixel coordinates)",
-        "color: 1-15 (0 is reserved for unset/transparent, 4-bit packing)"
+        "Copy a rectangle from one shard into another (or the same) shard",
+        "The source rectangle is flipped first, then rotated clockwise by `quarter_turns`;",
+        "odd turns swap width and height at the destination. Ownership and cooldown rules",
+        "apply to the destination shard only, counting pixels that actually change."
       ],
       "discriminator": [
-        178,
-        40,
-        167,
-        97,
-        31,
-        149,
-        219,
-        143
+        233,
+        101,
+        93,
+        251,
+        213,
+        67,
+        91,
+        92
       ],
       "accounts": [
         {
-          "name": "shard",
-          "writable": true,
+          "name": "source",
           "pda": {
             "seeds": [
               {
@@ -806,17 +879,20 @@ export type Magicplace = {
               },
               {
                 "kind": "arg",
-                "path": "shardX"
+                "path": "srcShardX"
               },
               {
                 "kind": "arg",
-                "path": "shardY"
+                "path": "srcShardY"
               }
             ]
           }
         },
         {
-          "name": "session",
+          "name": "shard",
+          "docs": [
+            "Destination shard"
+          ],
           "writable": true,
           "pda": {
             "seeds": [
@@ -824,99 +900,90 @@ export type Magicplace = {
                 "kind": "const",
                 "value": [
                   115,
-                  101,
-                  115,
-                  115,
-                  105,
-                  111,
-                  110
+                  104,
+                  97,
+                  114,
+                  100
                 ]
               },
               {
-                "kind": "account",
-                "path": "signer"
+                "kind": "arg",
+                "path": "dstShardX"
+              },
+              {
+                "kind": "arg",
+                "path": "dstShardY"
               }
             ]
           }
         },
         {
-          "name": "signer",
-          "writable": true,
-          "signer": true
-        }
-      ],
-      "args": [
-        {
-          "name": "shardX",
-          "type": "u16"
-        },
-        {
-          "name": "shardY",
-          "type": "u16"
-        },
-        {
-          "name": "px",
-          "type": "u32"
-        },
-        {
-          "name": "py",
-          "type": "u32"
-        },
-        {
-          "name": "color",
-          "type": "u8"
-        }
-      ]
-    },
-    {
-      "name": "placePixelsBulk",
-      "docs": [
-        "Place multiple pixels in bulk (max 50 pixels per call)",
-        "All pixels must be within the same shard",
-        "Each pixel is specified as (local_x, local_y, color) where:",
-        "- local_x: 0-89 (position within shard)",
-        "- local_y: 0-89 (position within shard)",
-        "- color: 1-255 (0 is reserved for transparent)"
-      ],
-      "discriminator": [
-        52,
-        220,
-        66,
-        126,
-        140,
-        36,
-        121,
-        150
-      ],
-      "accounts": [
-        {
-          "name": "shard",
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer of the destination, required when it has one enabled"
+          ],
           "writable": true,
+          "optional": true,
           "pda": {
             "seeds": [
               {
                 "kind": "const",
                 "value": [
-                  115,
-                  104,
+                  112,
                   97,
+                  105,
+                  110,
+                  116,
+                  101,
                   114,
-                  100
+                  115
                 ]
               },
               {
                 "kind": "arg",
-                "path": "shardX"
+                "path": "dstShardX"
               },
               {
                 "kind": "arg",
-                "path": "shardY"
+                "path": "dstShardY"
               }
             ]
           }
         },
         {
-          "name": "session",
+          "name": "history",
+          "docs": [
+            "Change history of the destination, required when it has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "dstShardX"
+              },
+              {
+                "kind": "arg",
+                "path": "dstShardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
           "writable": true,
           "pda": {
             "seeds": [
@@ -939,6 +1006,112 @@ export type Magicplace = {
             ]
           }
         },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerRewards",
+          "docs": [
+            "Reward accrual of the shard's owner, credited for pixels placed by visitors",
+            "Visitor pixels on a shard whose owner has none accrue nothing"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shard.creator",
+                "account": "pixelShard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
         {
           "name": "signer",
           "writable": true,
@@ -947,220 +1120,12169 @@ export type Magicplace = {
       ],
       "args": [
         {
-          "name": "shardX",
+          "name": "srcShardX",
           "type": "u16"
         },
         {
-          "name": "shardY",
+          "name": "srcShardY",
           "type": "u16"
         },
         {
-          "name": "pixels",
+          "name": "dstShardX",
+          "type": "u16"
+        },
+        {
+          "name": "dstShardY",
+          "type": "u16"
+        },
+        {
+          "name": "region",
           "type": {
-            "vec": {
-              "defined": {
-                "name": "bulkPixel"
-              }
+            "defined": {
+              "name": "regionCopy"
             }
           }
         }
       ]
     },
     {
-      "name": "processUndelegation",
+      "name": "crankCheckpoint",
+      "docs": [
+        "Add the next batch of shards to a checkpoint (base layer, permissionless)",
+        "Shards are passed as remaining accounts in creation order, continuing from",
+        "`next_index`. Delegated shards contribute their last committed state, as of the",
+        "crank that adds them; the seq range seen is recorded. The call that adds the last",
+        "shard finalizes the root."
+      ],
       "discriminator": [
-        196,
-        28,
-        41,
-        206,
-        48,
+        138,
+        8,
+        179,
+        13,
+        100,
+        7,
         37,
-        51,
-        167
+        150
       ],
       "accounts": [
         {
-          "name": "baseAccount",
-          "writable": true
-        },
-        {
-          "name": "buffer"
-        },
-        {
-          "name": "payer",
-          "writable": true
-        },
-        {
-          "name": "systemProgram"
+          "name": "checkpoint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  104,
+                  101,
+                  99,
+                  107,
+                  112,
+                  111,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "checkpointId"
+              }
+            ]
+          }
         }
       ],
       "args": [
         {
-          "name": "accountSeeds",
-          "type": {
-            "vec": "bytes"
-          }
+          "name": "checkpointId",
+          "type": "u32"
         }
       ]
-    }
-  ],
-  "accounts": [
-    {
-      "name": "pixelShard",
-      "discriminator": [
-        71,
-        74,
-        139,
-        167,
-        18,
-        144,
-        148,
-        25
-      ]
-    },
-    {
-      "name": "sessionAccount",
-      "discriminator": [
-        74,
-        34,
-        65,
-        133,
-        96,
-        163,
-        80,
-        69
-      ]
-    }
-  ],
-  "events": [
-    {
-      "name": "pixelChanged",
-      "discriminator": [
-        140,
-        76,
-        21,
-        27,
-        179,
-        226,
-        1,
-        84
-      ]
     },
     {
-      "name": "shardInitialized",
+      "name": "createSnapshot",
+      "docs": [
+        "Snapshot an owned shard's pixels into a new numbered snapshot PDA (base layer)",
+        "Reads the last committed state, so call commit_shard first to capture recent",
+        "ER changes. Snapshot ids start at 0 and increase by one per shard."
+      ],
       "discriminator": [
-        183,
-        80,
-        70,
-        77,
-        210,
-        226,
-        161,
-        150
-      ]
-    }
-  ],
-  "errors": [
-    {
-      "code": 6000,
-      "name": "invalidShardCoord",
-      "msg": "Invalid shard coordinates: must be 0-5825"
-    },
-    {
-      "code": 6001,
-      "name": "invalidPixelCoord",
-      "msg": "Invalid pixel coordinates: must be 0-524287"
-    },
-    {
-      "code": 6002,
-      "name": "shardMismatch",
-      "msg": "Shard coordinates don't match pixel location"
-    },
-    {
-      "code": 6003,
-      "name": "invalidColor",
-      "msg": "Invalid color: must be 1-255"
-    },
-    {
-      "code": 6004,
-      "name": "invalidAuth",
-      "msg": "Invalid authentication"
-    },
-    {
-      "code": 6005,
-      "name": "cooldown",
-      "msg": "Cooldown active: limit reached"
-    },
+        63,
+        212,
+        3,
+        100,
+        190,
+        182,
+        203,
+        130
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "snapshotIndex",
+          "docs": [
+            "Per-shard snapshot counter, created with the first snapshot"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  110,
+                  97,
+                  112,
+                  115,
+                  104,
+                  111,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "snapshot",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  110,
+                  97,
+                  112,
+                  115,
+                  104,
+                  111,
+                  116
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              },
+              {
+                "kind": "account",
+                "path": "snapshot_index.count",
+                "account": "shardSnapshotIndex"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "createTemplate",
+      "docs": [
+        "Create an empty w×h painting template at global (x, y) (base layer)",
+        "The target is uploaded with write_template_chunk and frozen with lock_template.",
+        "`threshold_bps` is the completion (basis points of target pixels) that releases",
+        "the template's bounty to its contributors. Every shard the template covers must be",
+        "passed as a remaining account and have its painter layer enabled."
+      ],
+      "discriminator": [
+        245,
+        51,
+        247,
+        234,
+        31,
+        9,
+        40,
+        227
+      ],
+      "accounts": [
+        {
+          "name": "template",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  116,
+                  101,
+                  109,
+                  112,
+                  108,
+                  97,
+                  116,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "creator"
+              },
+              {
+                "kind": "arg",
+                "path": "templateId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "creator",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "templateId",
+          "type": "u32"
+        },
+        {
+          "name": "x",
+          "type": "u32"
+        },
+        {
+          "name": "y",
+          "type": "u32"
+        },
+        {
+          "name": "w",
+          "type": "u16"
+        },
+        {
+          "name": "h",
+          "type": "u16"
+        },
+        {
+          "name": "thresholdBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "delegateFaction",
+      "docs": [
+        "Delegate a faction to Ephemeral Rollups"
+      ],
+      "discriminator": [
+        65,
+        232,
+        136,
+        229,
+        136,
+        57,
+        186,
+        30
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "factionId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "factionId",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "delegateHistory",
+      "docs": [
+        "Delegate a shard's change history to Ephemeral Rollups"
+      ],
+      "discriminator": [
+        120,
+        78,
+        120,
+        228,
+        99,
+        224,
+        212,
+        152
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "delegateLeaderboard",
+      "docs": [
+        "Delegate a leaderboard to Ephemeral Rollups"
+      ],
+      "discriminator": [
+        80,
+        13,
+        133,
+        118,
+        196,
+        123,
+        45,
+        102
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "metric",
+          "type": {
+            "defined": {
+              "name": "leaderboardMetric"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "delegatePainterLayer",
+      "docs": [
+        "Delegate a shard's painter layer to Ephemeral Rollups"
+      ],
+      "discriminator": [
+        226,
+        92,
+        150,
+        120,
+        138,
+        52,
+        219,
+        189
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "delegatePartner",
+      "docs": [
+        "Delegate a partner registry entry to Ephemeral Rollups"
+      ],
+      "discriminator": [
+        50,
+        161,
+        12,
+        50,
+        109,
+        94,
+        252,
+        142
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  114,
+                  116,
+                  110,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "programId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "programId",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "delegateProfile",
+      "docs": [
+        "Delegate a profile to Ephemeral Rollups so placements can update it"
+      ],
+      "discriminator": [
+        197,
+        115,
+        194,
+        166,
+        110,
+        39,
+        73,
+        134
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "mainWallet"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "mainWallet",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "delegateRewards",
+      "docs": [
+        "Delegate a shard owner's reward accrual to Ephemeral Rollups"
+      ],
+      "discriminator": [
+        1,
+        110,
+        234,
+        90,
+        166,
+        70,
+        112,
+        77
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "owner",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "delegateSeason",
+      "docs": [
+        "Delegate the season singleton to Ephemeral Rollups"
+      ],
+      "discriminator": [
+        69,
+        49,
+        255,
+        212,
+        191,
+        231,
+        123,
+        41
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "delegateShard",
+      "docs": [
+        "Delegate an existing shard to Ephemeral Rollups",
+        "This should be called after initialize_shard in a separate transaction"
+      ],
+      "discriminator": [
+        193,
+        43,
+        22,
+        82,
+        105,
+        20,
+        98,
+        22
+      ],
+      "accounts": [
+        {
+          "name": "authority",
+          "docs": [
+            "The authority requesting delegation"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "delegateUser",
+      "docs": [
+        "Delegate a user session account to Ephemeral Rollups",
+        "This should be called after initialize_user in a separate transaction"
+      ],
+      "discriminator": [
+        237,
+        220,
+        199,
+        24,
+        59,
+        41,
+        247,
+        24
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "docs": [
+            "The session account to delegate"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "docs": [
+            "The session key authority"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "bufferPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  117,
+                  102,
+                  102,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                55,
+                86,
+                190,
+                231,
+                187,
+                241,
+                178,
+                93,
+                156,
+                164,
+                97,
+                165,
+                93,
+                69,
+                109,
+                254,
+                60,
+                140,
+                218,
+                139,
+                104,
+                66,
+                16,
+                9,
+                139,
+                59,
+                136,
+                184,
+                153,
+                69,
+                121,
+                115
+              ]
+            }
+          }
+        },
+        {
+          "name": "delegationRecordPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "delegationMetadataPda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  100,
+                  101,
+                  108,
+                  101,
+                  103,
+                  97,
+                  116,
+                  105,
+                  111,
+                  110,
+                  45,
+                  109,
+                  101,
+                  116,
+                  97,
+                  100,
+                  97,
+                  116,
+                  97
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pda"
+              }
+            ],
+            "program": {
+              "kind": "account",
+              "path": "delegationProgram"
+            }
+          }
+        },
+        {
+          "name": "pda",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerProgram",
+          "address": "4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe"
+        },
+        {
+          "name": "delegationProgram",
+          "address": "DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "mainWallet",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "drawLine",
+      "docs": [
+        "Draw a straight line between two local points (0-89) within one shard",
+        "Uses Bresenham's algorithm; both endpoints are painted."
+      ],
+      "discriminator": [
+        156,
+        228,
+        34,
+        36,
+        71,
+        1,
+        18,
+        246
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerRewards",
+          "docs": [
+            "Reward accrual of the shard's owner, credited for pixels placed by visitors",
+            "Visitor pixels on a shard whose owner has none accrue nothing"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shard.creator",
+                "account": "pixelShard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "x0",
+          "type": "u8"
+        },
+        {
+          "name": "y0",
+          "type": "u8"
+        },
+        {
+          "name": "x1",
+          "type": "u8"
+        },
+        {
+          "name": "y1",
+          "type": "u8"
+        },
+        {
+          "name": "color",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "erasePixel",
+      "docs": [
+        "Erase a pixel (set to 0/transparent)"
+      ],
+      "discriminator": [
+        6,
+        38,
+        248,
+        220,
+        47,
+        82,
+        57,
+        222
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerRewards",
+          "docs": [
+            "Reward accrual of the shard's owner, credited for pixels placed by visitors",
+            "Visitor pixels on a shard whose owner has none accrue nothing"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shard.creator",
+                "account": "pixelShard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "px",
+          "type": "u32"
+        },
+        {
+          "name": "py",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "fillRect",
+      "docs": [
+        "Fill a rectangle within one shard with a single color",
+        "(x0, y0) and (x1, y1) are inclusive local corners (0-89), in any order.",
+        "Only pixels that actually change color are charged against the non-owner cooldown."
+      ],
+      "discriminator": [
+        142,
+        153,
+        137,
+        106,
+        18,
+        3,
+        7,
+        195
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerRewards",
+          "docs": [
+            "Reward accrual of the shard's owner, credited for pixels placed by visitors",
+            "Visitor pixels on a shard whose owner has none accrue nothing"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shard.creator",
+                "account": "pixelShard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "x0",
+          "type": "u8"
+        },
+        {
+          "name": "y0",
+          "type": "u8"
+        },
+        {
+          "name": "x1",
+          "type": "u8"
+        },
+        {
+          "name": "y1",
+          "type": "u8"
+        },
+        {
+          "name": "color",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "finalizeSeason",
+      "docs": [
+        "Close a season once it has ended and commit the result to the base layer (ER, permissionless)",
+        "Every Faction account is passed as a remaining account; their season counters are",
+        "rolled up into the season. The winner is the faction with the most points (lowest",
+        "id on ties, 0 if nobody scored). Painting stays frozen until the next season opens."
+      ],
+      "discriminator": [
+        183,
+        221,
+        183,
+        7,
+        73,
+        215,
+        158,
+        50
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "season",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
+        },
+        {
+          "name": "magicContext",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "floodFill",
+      "docs": [
+        "Flood fill the 4-connected area of the seed pixel's color within one shard",
+        "Bounded to MAX_FLOOD_FILL_PIXELS; larger areas fail instead of filling partially."
+      ],
+      "discriminator": [
+        75,
+        40,
+        110,
+        95,
+        136,
+        247,
+        101,
+        23
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerRewards",
+          "docs": [
+            "Reward accrual of the shard's owner, credited for pixels placed by visitors",
+            "Visitor pixels on a shard whose owner has none accrue nothing"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shard.creator",
+                "account": "pixelShard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "x",
+          "type": "u8"
+        },
+        {
+          "name": "y",
+          "type": "u8"
+        },
+        {
+          "name": "color",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "fundBounty",
+      "docs": [
+        "Add lamports to a template's bounty (base layer, anyone, until it completes or",
+        "its deadline passes)",
+        "The lamports are held by the Bounty PDA itself; each funder's total is recorded in",
+        "a BountyFunding account for refunds."
+      ],
+      "discriminator": [
+        36,
+        148,
+        139,
+        239,
+        172,
+        37,
+        58,
+        255
+      ],
+      "accounts": [
+        {
+          "name": "bounty",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  117,
+                  110,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "bounty.template",
+                "account": "bounty"
+              }
+            ]
+          }
+        },
+        {
+          "name": "funding",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  117,
+                  110,
+                  116,
+                  121,
+                  95,
+                  102,
+                  117,
+                  110,
+                  100,
+                  105,
+                  110,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "bounty"
+              },
+              {
+                "kind": "account",
+                "path": "funder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "funder",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "getArtworkPixels",
+      "docs": [
+        "Current colors of rows [row, row + rows) of an artwork, row by row",
+        "Pass the shards covering those rows as remaining accounts (any order, either layer);",
+        "a shard that is not passed, e.g. because it was never created, reads as transparent.",
+        "At most MAX_VIEW_REGION_PIXELS pixels per call, so page through tall artworks."
+      ],
+      "discriminator": [
+        7,
+        98,
+        191,
+        177,
+        82,
+        105,
+        174,
+        3
+      ],
+      "accounts": [
+        {
+          "name": "artwork"
+        }
+      ],
+      "args": [
+        {
+          "name": "row",
+          "type": "u16"
+        },
+        {
+          "name": "rows",
+          "type": "u16"
+        }
+      ],
+      "returns": "bytes"
+    },
+    {
+      "name": "getCooldownStatus",
+      "docs": [
+        "Burst and cooldown state of a session (delegated or not)"
+      ],
+      "discriminator": [
+        219,
+        177,
+        46,
+        64,
+        218,
+        242,
+        55,
+        125
+      ],
+      "accounts": [
+        {
+          "name": "session"
+        },
+        {
+          "name": "profile",
+          "optional": true
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": {
+          "name": "cooldownStatus"
+        }
+      }
+    },
+    {
+      "name": "getPixel",
+      "docs": [
+        "Color of the pixel at global (px, py); 0 = transparent",
+        "Works on both layers: delegated shards return their last committed state on base."
+      ],
+      "discriminator": [
+        105,
+        199,
+        169,
+        92,
+        25,
+        234,
+        213,
+        141
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "px",
+          "type": "u32"
+        },
+        {
+          "name": "py",
+          "type": "u32"
+        }
+      ],
+      "returns": "u8"
+    },
+    {
+      "name": "getRegion",
+      "docs": [
+        "Colors of a w×h local rectangle starting at (x0, y0), row by row",
+        "The rectangle must lie within the shard and hold at most MAX_VIEW_REGION_PIXELS."
+      ],
+      "discriminator": [
+        244,
+        145,
+        179,
+        157,
+        203,
+        183,
+        211,
+        25
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "x0",
+          "type": "u8"
+        },
+        {
+          "name": "y0",
+          "type": "u8"
+        },
+        {
+          "name": "w",
+          "type": "u8"
+        },
+        {
+          "name": "h",
+          "type": "u8"
+        }
+      ],
+      "returns": "bytes"
+    },
+    {
+      "name": "initializeBadgeMint",
+      "docs": [
+        "Create the non-transferable Token-2022 mint of a badge (base layer, once, permissionless)",
+        "Minted by the same authority PDA as the reward token, 0 decimals."
+      ],
+      "discriminator": [
+        225,
+        103,
+        39,
+        0,
+        34,
+        226,
+        82,
+        217
+      ],
+      "accounts": [
+        {
+          "name": "badgeMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  97,
+                  100,
+                  103,
+                  101,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "badge"
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "badge",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "initializeFaction",
+      "docs": [
+        "Create a faction's accounts (base layer, once per faction, permissionless)",
+        "Delegate the Faction with delegate_faction; its territory counter stays on base."
+      ],
+      "discriminator": [
+        88,
+        1,
+        196,
+        215,
+        69,
+        190,
+        157,
+        130
+      ],
+      "accounts": [
+        {
+          "name": "faction",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "factionId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "factionTerritory",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110,
+                  95,
+                  116,
+                  101,
+                  114,
+                  114,
+                  105,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "factionId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "factionId",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "initializeHistory",
+      "docs": [
+        "Create the change history ring buffer of an owned shard (base layer)",
+        "Delegate it with delegate_history, then turn it on with set_history_layer."
+      ],
+      "discriminator": [
+        16,
+        143,
+        228,
+        87,
+        255,
+        10,
+        177,
+        154
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "initializeLeaderboard",
+      "docs": [
+        "Create the leaderboard of a metric (base layer, once per metric, permissionless)",
+        "Delegate it with delegate_leaderboard; scores are submitted on the ER."
+      ],
+      "discriminator": [
+        47,
+        23,
+        34,
+        39,
+        46,
+        108,
+        91,
+        176
+      ],
+      "accounts": [
+        {
+          "name": "leaderboard",
+          "writable": true
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "metric",
+          "type": {
+            "defined": {
+              "name": "leaderboardMetric"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "initializePainterLayer",
+      "docs": [
+        "Create the painter attribution layer of an owned shard (base layer)",
+        "Delegate it with delegate_painter_layer, then turn it on with set_painter_layer."
+      ],
+      "discriminator": [
+        238,
+        34,
+        7,
+        98,
+        86,
+        194,
+        81,
+        234
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "initializeProfile",
+      "docs": [
+        "Create the XP profile of a main wallet (base layer)",
+        "Any session of the wallet can create it; all of its sessions share it."
+      ],
+      "discriminator": [
+        32,
+        145,
+        77,
+        213,
+        58,
+        39,
+        251,
+        234
+      ],
+      "accounts": [
+        {
+          "name": "profile",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "mainWallet"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "mainWallet",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "initializeRewardMint",
+      "docs": [
+        "Create the reward token mint (base layer, once, permissionless)",
+        "Both the mint and its authority are PDAs, so only this program can mint."
+      ],
+      "discriminator": [
+        136,
+        219,
+        113,
+        48,
+        109,
+        59,
+        18,
+        208
+      ],
+      "accounts": [
+        {
+          "name": "rewardMint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  95,
+                  109,
+                  105,
+                  110,
+                  116
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "mintAuthority",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  109,
+                  105,
+                  110,
+                  116,
+                  95,
+                  97,
+                  117,
+                  116,
+                  104,
+                  111,
+                  114,
+                  105,
+                  116,
+                  121
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeRewards",
+      "docs": [
+        "Create the reward accrual account of a shard owner (base layer, permissionless)",
+        "Delegate it with delegate_rewards so visitor placements on the ER can credit it."
+      ],
+      "discriminator": [
+        91,
+        174,
+        112,
+        191,
+        233,
+        236,
+        147,
+        12
+      ],
+      "accounts": [
+        {
+          "name": "rewards",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "owner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "owner",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "initializeSeason",
+      "docs": [
+        "Create the season singleton (base layer, once, admin only)",
+        "Painting stays open until the first season is opened. Delegate it with delegate_season.",
+        "The admin is recorded on the singleton, since the ER cannot check the upgrade authority."
+      ],
+      "discriminator": [
+        48,
+        218,
+        111,
+        51,
+        235,
+        207,
+        4,
+        119
+      ],
+      "accounts": [
+        {
+          "name": "season",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "docs": [
+            "Program upgrade authority, recorded as the season admin"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "programData",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  55,
+                  86,
+                  190,
+                  231,
+                  187,
+                  241,
+                  178,
+                  93,
+                  156,
+                  164,
+                  97,
+                  165,
+                  93,
+                  69,
+                  109,
+                  254,
+                  60,
+                  140,
+                  218,
+                  139,
+                  104,
+                  66,
+                  16,
+                  9,
+                  139,
+                  59,
+                  136,
+                  184,
+                  153,
+                  69,
+                  121,
+                  115
+                ]
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                2,
+                168,
+                246,
+                145,
+                78,
+                136,
+                161,
+                176,
+                226,
+                16,
+                21,
+                62,
+                247,
+                99,
+                174,
+                43,
+                0,
+                194,
+                185,
+                61,
+                22,
+                193,
+                36,
+                210,
+                192,
+                83,
+                122,
+                16,
+                4,
+                128,
+                0,
+                0
+              ]
+            }
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeShard",
+      "docs": [
+        "Initialize a shard at (shard_x, shard_y) coordinates (without delegation)",
+        "Shards are created on-demand when a user wants to paint in that region",
+        "shard_x, shard_y: 0-4095 (4096 shards per dimension)",
+        "Call delegate_shard separately after this to delegate to ER"
+      ],
+      "discriminator": [
+        100,
+        96,
+        88,
+        58,
+        225,
+        178,
+        9,
+        147
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "docs": [
+            "The shard account to initialize"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "canvas",
+          "docs": [
+            "Global canvas counters, created along with the first shard"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  97,
+                  110,
+                  118,
+                  97,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "walletStats",
+          "docs": [
+            "Shard counter of the session's main wallet, created along with its first shard"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  97,
+                  108,
+                  108,
+                  101,
+                  116,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "mainWallet"
+              }
+            ]
+          }
+        },
+        {
+          "name": "factionTerritory",
+          "docs": [
+            "Territory counter the creator's shards are counted in, required once it has one"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110,
+                  95,
+                  116,
+                  101,
+                  114,
+                  114,
+                  105,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction_territory.id",
+                "account": "factionTerritory"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "mainWallet",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "initializeUser",
+      "docs": [
+        "Create a session account for a session key authorized by `main_wallet`",
+        "With a `referrer`, the signed authorization message must end with",
+        "\" referred by: <referrer>\" and both wallets' Referral accounts must be passed.",
+        "A main wallet is attributed to its first referrer only."
+      ],
+      "discriminator": [
+        111,
+        17,
+        185,
+        250,
+        60,
+        122,
+        38,
+        254
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "docs": [
+            "Session account PDA derived from the MAIN wallet (not session key)",
+            "This ensures each main wallet has exactly one session account"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "authority"
+              }
+            ]
+          }
+        },
+        {
+          "name": "authority",
+          "docs": [
+            "The session key that is authorized to act on behalf of main_wallet"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "instructionsSysvar",
+          "address": "Sysvar1nstructions1111111111111111111111111"
+        },
+        {
+          "name": "referral",
+          "docs": [
+            "Referral record of main_wallet, only passed with a referrer",
+            "A wallet referring itself would alias both records, so it is rejected here"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "mainWallet"
+              }
+            ]
+          }
+        },
+        {
+          "name": "referrerReferral",
+          "docs": [
+            "Referral record of the referrer, only passed with a referrer"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  102,
+                  101,
+                  114,
+                  114,
+                  97,
+                  108
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "referrer"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "mainWallet",
+          "type": "pubkey"
+        },
+        {
+          "name": "signature",
+          "type": {
+            "array": [
+              "u8",
+              64
+            ]
+          }
+        },
+        {
+          "name": "referrer",
+          "type": {
+            "option": "pubkey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "joinFaction",
+      "docs": [
+        "Join a faction, or switch to another one (ER)",
+        "Switching is allowed FACTION_SWITCH_COOLDOWN after joining the current faction and",
+        "needs the faction being left as `previous_faction`. The profile is committed so",
+        "sync_territory can move the wallet's shards on the base layer."
+      ],
+      "discriminator": [
+        214,
+        51,
+        85,
+        242,
+        189,
+        94,
+        67,
+        149
+      ],
+      "accounts": [
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "factionId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "previousFaction",
+          "docs": [
+            "Faction being left, required when switching"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "profile.faction",
+                "account": "playerProfile"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
+        },
+        {
+          "name": "magicContext",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "factionId",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "lockTemplate",
+      "docs": [
+        "Freeze a template's target and open its bounty escrow (base layer, creator only)",
+        "The bounty can complete until `deadline` (unix time); after that, funders of a",
+        "bounty that never completed can take their lamports back with refund_bounty."
+      ],
+      "discriminator": [
+        136,
+        15,
+        76,
+        48,
+        60,
+        70,
+        110,
+        246
+      ],
+      "accounts": [
+        {
+          "name": "template",
+          "writable": true
+        },
+        {
+          "name": "bounty",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  117,
+                  110,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "template"
+              }
+            ]
+          }
+        },
+        {
+          "name": "creator",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "template"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "deadline",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "migrateShard",
+      "docs": [
+        "Upgrade a shard created before the layers/seq/index/hook fields (base layer)",
+        "Permissionless; the payer covers the extra rent. Grows the account to the current",
+        "PixelShard size and backfills the new fields: content_hash recomputed from the",
+        "pixels, the next canvas index, no layers and no hook. Legacy shards predate",
+        "WalletStats too, so the shard is also counted for its creator (badges, continents).",
+        "A delegated legacy shard must first be brought back with undelegate_legacy_shard."
+      ],
+      "discriminator": [
+        104,
+        161,
+        75,
+        116,
+        173,
+        53,
+        64,
+        154
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "canvas",
+          "docs": [
+            "Global canvas counters, the source of the shard's index"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  97,
+                  110,
+                  118,
+                  97,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "walletStats",
+          "docs": [
+            "Shard counter of the shard's creator, credited with the migrated shard"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  97,
+                  108,
+                  108,
+                  101,
+                  116,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "creator"
+              }
+            ]
+          }
+        },
+        {
+          "name": "factionTerritory",
+          "docs": [
+            "Territory counter the creator's shards are counted in, required once it has one"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110,
+                  95,
+                  116,
+                  101,
+                  114,
+                  114,
+                  105,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction_territory.id",
+                "account": "factionTerritory"
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "creator",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "mintArtworkCard",
+      "docs": [
+        "Issue the ownership card of an artwork to its creator (base layer, creator only)",
+        "A lightweight, program-tracked alternative to an NFT; move it with transfer_artwork_card."
+      ],
+      "discriminator": [
+        18,
+        136,
+        196,
+        171,
+        255,
+        245,
+        70,
+        141
+      ],
+      "accounts": [
+        {
+          "name": "artwork"
+        },
+        {
+          "name": "card",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  114,
+                  116,
+                  119,
+                  111,
+                  114,
+                  107,
+                  95,
+                  99,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "artwork"
+              }
+            ]
+          }
+        },
+        {
+          "name": "creator",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "artwork"
+          ]
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "openSeason",
+      "docs": [
+        "Open the next season for [start, end) with the given scoring (ER, admin only)",
+        "The previous season must be finalized. Painting is rejected until `start`."
+      ],
+      "discriminator": [
+        100,
+        152,
+        95,
+        245,
+        247,
+        126,
+        124,
+        215
+      ],
+      "accounts": [
+        {
+          "name": "season",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "docs": [
+            "Season admin recorded by initialize_season"
+          ],
+          "signer": true,
+          "relations": [
+            "season"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "start",
+          "type": "u64"
+        },
+        {
+          "name": "end",
+          "type": "u64"
+        },
+        {
+          "name": "rules",
+          "type": {
+            "defined": {
+              "name": "seasonRules"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "partnerPlacePixels",
+      "docs": [
+        "Place pixels on behalf of a registered partner program (via ER, called through CPI)",
+        "The partner signs with its [PARTNER_AUTHORITY_SEED] PDA; see `partner::place_pixels`",
+        "under the `cpi` feature. Pixels use local coordinates like place_pixels_bulk, count",
+        "against the partner's quota instead of a session cooldown, and are attributed to",
+        "the partner program id. The painter layer records them under the partner's registry",
+        "entry (partner::registry_address), which is never credited for template bounties."
+      ],
+      "discriminator": [
+        238,
+        143,
+        45,
+        204,
+        151,
+        47,
+        165,
+        159
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "partner",
+          "docs": [
+            "The calling program's registry entry"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  114,
+                  116,
+                  110,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "partner.program_id",
+                "account": "partner"
+              }
+            ]
+          }
+        },
+        {
+          "name": "partnerAuthority",
+          "docs": [
+            "The calling program's [PARTNER_AUTHORITY_SEED] PDA, signed with invoke_signed"
+          ],
+          "signer": true
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "pixels",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "bulkPixel"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "placePixel",
+      "docs": [
+        "Place a pixel using global coordinates",
+        "px, py: 0 to 524,287 (global pixel coordinates)",
+        "color: 1-15 (0 is reserved for unset/transparent, 4-bit packing)",
+        "On a shard with a paint hook, pass the hook accounts as remaining accounts",
+        "(see invoke_paint_hook); the hook can reject the pixel."
+      ],
+      "discriminator": [
+        178,
+        40,
+        167,
+        97,
+        31,
+        149,
+        219,
+        143
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerRewards",
+          "docs": [
+            "Reward accrual of the shard's owner, credited for pixels placed by visitors",
+            "Visitor pixels on a shard whose owner has none accrue nothing"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shard.creator",
+                "account": "pixelShard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "px",
+          "type": "u32"
+        },
+        {
+          "name": "py",
+          "type": "u32"
+        },
+        {
+          "name": "color",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "placePixelsBulk",
+      "docs": [
+        "Place multiple pixels in bulk (up to the caller's tier burst, 60-100 pixels per call)",
+        "All pixels must be within the same shard",
+        "Each pixel is specified as (local_x, local_y, color) where:",
+        "- local_x: 0-89 (position within shard)",
+        "- local_y: 0-89 (position within shard)",
+        "- color: 1-255 (0 is reserved for transparent)",
+        "On a shard with a paint hook, the hook vets the whole batch (see place_pixel)."
+      ],
+      "discriminator": [
+        52,
+        220,
+        66,
+        126,
+        140,
+        36,
+        121,
+        150
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerRewards",
+          "docs": [
+            "Reward accrual of the shard's owner, credited for pixels placed by visitors",
+            "Visitor pixels on a shard whose owner has none accrue nothing"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shard.creator",
+                "account": "pixelShard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "pixels",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "bulkPixel"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "placePixelsMultiShard",
+      "docs": [
+        "Place multiple pixels across shard boundaries (up to the caller's tier burst, max 4 shards per call)",
+        "Pixels use global coordinates (px, py: 0 to 524,287) and are routed to their shard",
+        "by coordinates. Every target shard must be passed as a writable remaining account,",
+        "followed by its ShardPainters / ShardHistory accounts if those layers are enabled.",
+        "Pixels on the caller's own shards are free, the rest are charged against the",
+        "cooldown as a single burst. Any pixel without a matching shard fails the whole call.",
+        "A shard owned by someone else may be followed by its owner's writable RewardAccrual",
+        "(only after the first such shard of each owner), which is credited for the pixels;",
+        "owners without one accrue nothing."
+      ],
+      "discriminator": [
+        125,
+        124,
+        219,
+        243,
+        208,
+        139,
+        250,
+        4
+      ],
+      "accounts": [
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "pixels",
+          "type": {
+            "vec": {
+              "defined": {
+                "name": "globalPixel"
+              }
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "placePixelsPacked",
+      "docs": [
+        "Place pixels in bulk using the bit-packed encoding (max MAX_PACKED_PIXELS, 296, per call)",
+        "All pixels must be within the same shard. `data` holds `count` 21-bit records packed",
+        "LSB-first: low 13 bits = local pixel index (local_y * 90 + local_x), high 8 bits = color.",
+        "The PixelsBulkChanged event echoes `data` as is."
+      ],
+      "discriminator": [
+        9,
+        184,
+        51,
+        1,
+        27,
+        219,
+        217,
+        65
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerRewards",
+          "docs": [
+            "Reward accrual of the shard's owner, credited for pixels placed by visitors",
+            "Visitor pixels on a shard whose owner has none accrue nothing"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shard.creator",
+                "account": "pixelShard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "count",
+          "type": "u16"
+        },
+        {
+          "name": "data",
+          "type": "bytes"
+        }
+      ]
+    },
+    {
+      "name": "processUndelegation",
+      "discriminator": [
+        196,
+        28,
+        41,
+        206,
+        48,
+        37,
+        51,
+        167
+      ],
+      "accounts": [
+        {
+          "name": "baseAccount",
+          "writable": true
+        },
+        {
+          "name": "buffer"
+        },
+        {
+          "name": "payer",
+          "writable": true
+        },
+        {
+          "name": "systemProgram"
+        }
+      ],
+      "args": [
+        {
+          "name": "accountSeeds",
+          "type": {
+            "vec": "bytes"
+          }
+        }
+      ]
+    },
+    {
+      "name": "refundBounty",
+      "docs": [
+        "Return a funder's lamports from a bounty that missed its deadline (base layer)",
+        "Closes the funder's BountyFunding record."
+      ],
+      "discriminator": [
+        167,
+        234,
+        121,
+        108,
+        247,
+        216,
+        216,
+        124
+      ],
+      "accounts": [
+        {
+          "name": "bounty",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  117,
+                  110,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "bounty.template",
+                "account": "bounty"
+              }
+            ]
+          }
+        },
+        {
+          "name": "funding",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  117,
+                  110,
+                  116,
+                  121,
+                  95,
+                  102,
+                  117,
+                  110,
+                  100,
+                  105,
+                  110,
+                  103
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "bounty"
+              },
+              {
+                "kind": "account",
+                "path": "funder"
+              }
+            ]
+          }
+        },
+        {
+          "name": "funder",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "registerArtwork",
+      "docs": [
+        "Register a w×h rectangle of the canvas at global (x, y) as an artwork (base layer)",
+        "Signed by the creator's main wallet; the rectangle may span shards. The artwork only",
+        "references the pixels, which keep changing; read them with get_artwork_pixels."
+      ],
+      "discriminator": [
+        211,
+        48,
+        220,
+        65,
+        174,
+        16,
+        158,
+        168
+      ],
+      "accounts": [
+        {
+          "name": "artwork",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  114,
+                  116,
+                  119,
+                  111,
+                  114,
+                  107
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "creator"
+              },
+              {
+                "kind": "arg",
+                "path": "x"
+              },
+              {
+                "kind": "arg",
+                "path": "y"
+              },
+              {
+                "kind": "arg",
+                "path": "w"
+              },
+              {
+                "kind": "arg",
+                "path": "h"
+              }
+            ]
+          }
+        },
+        {
+          "name": "creator",
+          "docs": [
+            "The artist's main wallet, attesting the artwork by signing"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "x",
+          "type": "u32"
+        },
+        {
+          "name": "y",
+          "type": "u32"
+        },
+        {
+          "name": "w",
+          "type": "u16"
+        },
+        {
+          "name": "h",
+          "type": "u16"
+        },
+        {
+          "name": "title",
+          "type": "string"
+        }
+      ]
+    },
+    {
+      "name": "registerPartner",
+      "docs": [
+        "Register a partner program allowed to paint via CPI (base layer, admin only)",
+        "The partner gets `quota` pixels per `quota_period` seconds. Delegate the entry",
+        "with delegate_partner before the partner paints on the ER."
+      ],
+      "discriminator": [
+        126,
+        105,
+        229,
+        224,
+        136,
+        11,
+        184,
+        137
+      ],
+      "accounts": [
+        {
+          "name": "partner",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  114,
+                  116,
+                  110,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "programId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "docs": [
+            "Program upgrade authority, acting as canvas admin"
+          ],
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "programData",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  55,
+                  86,
+                  190,
+                  231,
+                  187,
+                  241,
+                  178,
+                  93,
+                  156,
+                  164,
+                  97,
+                  165,
+                  93,
+                  69,
+                  109,
+                  254,
+                  60,
+                  140,
+                  218,
+                  139,
+                  104,
+                  66,
+                  16,
+                  9,
+                  139,
+                  59,
+                  136,
+                  184,
+                  153,
+                  69,
+                  121,
+                  115
+                ]
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                2,
+                168,
+                246,
+                145,
+                78,
+                136,
+                161,
+                176,
+                226,
+                16,
+                21,
+                62,
+                247,
+                99,
+                174,
+                43,
+                0,
+                194,
+                185,
+                61,
+                22,
+                193,
+                36,
+                210,
+                192,
+                83,
+                122,
+                16,
+                4,
+                128,
+                0,
+                0
+              ]
+            }
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "programId",
+          "type": "pubkey"
+        },
+        {
+          "name": "quota",
+          "type": "u32"
+        },
+        {
+          "name": "quotaPeriod",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "restoreShard",
+      "docs": [
+        "Write a snapshot back into a delegated shard in one call (owner only, via ER)",
+        "Rejected while the season is frozen, like any other pixel write."
+      ],
+      "discriminator": [
+        4,
+        206,
+        14,
+        161,
+        203,
+        232,
+        148,
+        35
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "snapshot",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  110,
+                  97,
+                  112,
+                  115,
+                  104,
+                  111,
+                  116
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              },
+              {
+                "kind": "arg",
+                "path": "snapshotId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "snapshotId",
+          "type": "u32"
+        }
+      ]
+    },
+    {
+      "name": "revertPainter",
+      "docs": [
+        "Revert every pixel last painted by `painter` (a main wallet, or a partner's registry",
+        "entry) on an owned shard (via ER)",
+        "Pixels go back to the passed snapshot, or are cleared to 0 when no snapshot is given.",
+        "Rejected while the season is frozen."
+      ],
+      "discriminator": [
+        205,
+        196,
+        87,
+        40,
+        210,
+        109,
+        78,
+        130
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "snapshot",
+          "docs": [
+            "Snapshot to revert to; pixels are cleared when omitted"
+          ],
+          "optional": true
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "painter",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "setHistoryLayer",
+      "docs": [
+        "Turn change recording on or off for an owned shard (via ER)",
+        "Enabling starts from an empty buffer; from then on every write must pass it."
+      ],
+      "discriminator": [
+        198,
+        144,
+        22,
+        166,
+        98,
+        249,
+        193,
+        235
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setPaintHook",
+      "docs": [
+        "Set or clear the paint hook of an owned shard (via ER)",
+        "place_pixel and place_pixels_bulk then CPI into the hook's `on_paint` before",
+        "writing; other write paths are limited to the owner while a hook is set."
+      ],
+      "discriminator": [
+        184,
+        70,
+        61,
+        193,
+        214,
+        180,
+        140,
+        174
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "hookProgram",
+          "type": {
+            "option": "pubkey"
+          }
+        }
+      ]
+    },
+    {
+      "name": "setPainterLayer",
+      "docs": [
+        "Turn painter attribution on or off for an owned shard (via ER)",
+        "Enabling starts from a clean layer; from then on every write must pass it."
+      ],
+      "discriminator": [
+        56,
+        145,
+        181,
+        238,
+        244,
+        152,
+        46,
+        137
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "startCheckpoint",
+      "docs": [
+        "Start a new canvas checkpoint covering every shard created so far (base layer)",
+        "Permissionless; the caller pays rent. Fill it in with crank_checkpoint. The",
+        "checkpoint is tagged with the current season (0 before the first one)."
+      ],
+      "discriminator": [
+        45,
+        116,
+        100,
+        19,
+        41,
+        180,
+        21,
+        100
+      ],
+      "accounts": [
+        {
+          "name": "canvas",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  97,
+                  110,
+                  118,
+                  97,
+                  115
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "checkpoint",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  104,
+                  101,
+                  99,
+                  107,
+                  112,
+                  111,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "canvas.checkpoint_count",
+                "account": "canvasState"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "submitScore",
+      "docs": [
+        "Submit a wallet's current score to a leaderboard (ER, permissionless)",
+        "The score is read from the wallet's counter account passed as `source`:",
+        "WalletStats for ShardsOwned, PlayerProfile for EnemyPixels and Xp, RewardAccrual",
+        "for RewardsEarned. Entries are ordered by score, then wallet; a full board only",
+        "evicts its last entry for a strictly higher score."
+      ],
+      "discriminator": [
+        212,
+        128,
+        45,
+        22,
+        112,
+        82,
+        85,
+        235
+      ],
+      "accounts": [
+        {
+          "name": "leaderboard",
+          "writable": true
+        },
+        {
+          "name": "source",
+          "docs": [
+            "Verified by owner check, discriminator and PDA address in leaderboard_score."
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "metric",
+          "type": {
+            "defined": {
+              "name": "leaderboardMetric"
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "sweepBounty",
+      "docs": [
+        "Close a settled bounty, sending what is left to the template creator (base layer)",
+        "A completed bounty settles once every contributor has claimed or BOUNTY_CLAIM_WINDOW",
+        "has passed, so shares nobody can claim (e.g. partner programs) and rounding dust",
+        "are recovered. A bounty that never completed settles once every funder has refunded."
+      ],
+      "discriminator": [
+        52,
+        174,
+        70,
+        154,
+        156,
+        216,
+        115,
+        55
+      ],
+      "accounts": [
+        {
+          "name": "template"
+        },
+        {
+          "name": "bounty",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  98,
+                  111,
+                  117,
+                  110,
+                  116,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "template"
+              }
+            ]
+          }
+        },
+        {
+          "name": "creator",
+          "writable": true,
+          "signer": true,
+          "relations": [
+            "template"
+          ]
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "syncTerritory",
+      "docs": [
+        "Move a wallet's shards to the territory of its profile's faction (base layer)",
+        "Permissionless. Reads the committed profile (join_faction commits it), so run it",
+        "after a join or switch; until then the shards count for the previous faction."
+      ],
+      "discriminator": [
+        98,
+        8,
+        239,
+        37,
+        182,
+        31,
+        185,
+        5
+      ],
+      "accounts": [
+        {
+          "name": "walletStats",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  97,
+                  108,
+                  108,
+                  101,
+                  116,
+                  95,
+                  115,
+                  116,
+                  97,
+                  116,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "wallet_stats.owner",
+                "account": "walletStats"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "wallet_stats.owner",
+                "account": "walletStats"
+              }
+            ]
+          }
+        },
+        {
+          "name": "previousTerritory",
+          "docs": [
+            "Territory the shards are counted in now, required unless they are in none"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110,
+                  95,
+                  116,
+                  101,
+                  114,
+                  114,
+                  105,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "wallet_stats.territory_faction",
+                "account": "walletStats"
+              }
+            ]
+          }
+        },
+        {
+          "name": "territory",
+          "docs": [
+            "Territory of the profile's faction, required unless it has left every faction"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110,
+                  95,
+                  116,
+                  101,
+                  114,
+                  114,
+                  105,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "territory.id",
+                "account": "factionTerritory"
+              }
+            ]
+          }
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "transferArtworkCard",
+      "docs": [
+        "Give an artwork card to another wallet (base layer, current owner only)"
+      ],
+      "discriminator": [
+        249,
+        129,
+        140,
+        222,
+        227,
+        105,
+        25,
+        183
+      ],
+      "accounts": [
+        {
+          "name": "card",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  97,
+                  114,
+                  116,
+                  119,
+                  111,
+                  114,
+                  107,
+                  95,
+                  99,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "card.artwork",
+                "account": "artworkCard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "owner",
+          "signer": true,
+          "relations": [
+            "card"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "newOwner",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "undelegateLegacyShard",
+      "docs": [
+        "Commit and undelegate a shard that still has the old layout (ER, permissionless)",
+        "Legacy shards can't be loaded by the other instructions; back on the base layer",
+        "they can be upgraded with migrate_shard and delegated again."
+      ],
+      "discriminator": [
+        140,
+        123,
+        243,
+        128,
+        66,
+        233,
+        148,
+        64
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "magicProgram",
+          "address": "Magic11111111111111111111111111111111111111"
+        },
+        {
+          "name": "magicContext",
+          "writable": true,
+          "address": "MagicContext1111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "undoLast",
+      "docs": [
+        "Roll back the last `count` recorded changes on an owned shard (via ER)",
+        "Stops early when the history runs out. Undone pixels lose their attribution,",
+        "and the undo itself is not recorded. Rejected while the season is frozen."
+      ],
+      "discriminator": [
+        128,
+        30,
+        119,
+        166,
+        183,
+        250,
+        100,
+        221
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "count",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "updatePartner",
+      "docs": [
+        "Change a partner's quota or turn it off (admin only, on whichever layer holds the entry)"
+      ],
+      "discriminator": [
+        19,
+        112,
+        236,
+        81,
+        127,
+        55,
+        21,
+        196
+      ],
+      "accounts": [
+        {
+          "name": "partner",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  114,
+                  116,
+                  110,
+                  101,
+                  114
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "programId"
+              }
+            ]
+          }
+        },
+        {
+          "name": "admin",
+          "docs": [
+            "Program upgrade authority, acting as canvas admin"
+          ],
+          "signer": true
+        },
+        {
+          "name": "programData",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  55,
+                  86,
+                  190,
+                  231,
+                  187,
+                  241,
+                  178,
+                  93,
+                  156,
+                  164,
+                  97,
+                  165,
+                  93,
+                  69,
+                  109,
+                  254,
+                  60,
+                  140,
+                  218,
+                  139,
+                  104,
+                  66,
+                  16,
+                  9,
+                  139,
+                  59,
+                  136,
+                  184,
+                  153,
+                  69,
+                  121,
+                  115
+                ]
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                2,
+                168,
+                246,
+                145,
+                78,
+                136,
+                161,
+                176,
+                226,
+                16,
+                21,
+                62,
+                247,
+                99,
+                174,
+                43,
+                0,
+                194,
+                185,
+                61,
+                22,
+                193,
+                36,
+                210,
+                192,
+                83,
+                122,
+                16,
+                4,
+                128,
+                0,
+                0
+              ]
+            }
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "programId",
+          "type": "pubkey"
+        },
+        {
+          "name": "quota",
+          "type": "u32"
+        },
+        {
+          "name": "quotaPeriod",
+          "type": "u64"
+        },
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "verifyCheckpoint",
+      "docs": [
+        "Prove a shard's state at a finalized checkpoint; fails unless the proof holds",
+        "`pixels_hash` is shard_pixels_hash of the claimed pixels and `proof` holds the",
+        "CHECKPOINT_DEPTH sibling hashes from the shard's leaf (at position `index`) upwards."
+      ],
+      "discriminator": [
+        119,
+        187,
+        26,
+        193,
+        230,
+        216,
+        64,
+        10
+      ],
+      "accounts": [
+        {
+          "name": "checkpoint",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  104,
+                  101,
+                  99,
+                  107,
+                  112,
+                  111,
+                  105,
+                  110,
+                  116
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "checkpointId"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "checkpointId",
+          "type": "u32"
+        },
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "index",
+          "type": "u32"
+        },
+        {
+          "name": "seq",
+          "type": "u64"
+        },
+        {
+          "name": "pixelsHash",
+          "type": {
+            "array": [
+              "u8",
+              32
+            ]
+          }
+        },
+        {
+          "name": "proof",
+          "type": {
+            "vec": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          }
+        }
+      ]
+    },
+    {
+      "name": "writeShardChunk",
+      "docs": [
+        "Copy a contiguous run of palette indices into an owned shard (owner only)",
+        "`offset` is the local pixel index of the first byte (local_y * 90 + local_x) and",
+        "the run continues row by row. With `skip_transparent`, 0 bytes leave the existing",
+        "pixel unchanged instead of erasing it, so non-rectangular art can be stamped."
+      ],
+      "discriminator": [
+        138,
+        96,
+        2,
+        128,
+        236,
+        192,
+        239,
+        107
+      ],
+      "accounts": [
+        {
+          "name": "shard",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  104,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "painters",
+          "docs": [
+            "Painter attribution layer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  97,
+                  105,
+                  110,
+                  116,
+                  101,
+                  114,
+                  115
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "history",
+          "docs": [
+            "Change history ring buffer, required when the shard has one enabled"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  104,
+                  105,
+                  115,
+                  116,
+                  111,
+                  114,
+                  121
+                ]
+              },
+              {
+                "kind": "arg",
+                "path": "shardX"
+              },
+              {
+                "kind": "arg",
+                "path": "shardY"
+              }
+            ]
+          }
+        },
+        {
+          "name": "session",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  115,
+                  115,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "signer"
+              }
+            ]
+          }
+        },
+        {
+          "name": "profile",
+          "docs": [
+            "XP profile of the session's main wallet; without it placements use tier 0 limits"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  114,
+                  111,
+                  102,
+                  105,
+                  108,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "session.main_address",
+                "account": "sessionAccount"
+              }
+            ]
+          }
+        },
+        {
+          "name": "ownerRewards",
+          "docs": [
+            "Reward accrual of the shard's owner, credited for pixels placed by visitors",
+            "Visitor pixels on a shard whose owner has none accrue nothing"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100,
+                  115
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "shard.creator",
+                "account": "pixelShard"
+              }
+            ]
+          }
+        },
+        {
+          "name": "faction",
+          "docs": [
+            "Faction of the session's main wallet, credited with every pixel placed"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  99,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "faction.id",
+                "account": "faction"
+              }
+            ]
+          }
+        },
+        {
+          "name": "season",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  101,
+                  97,
+                  115,
+                  111,
+                  110
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "signer",
+          "writable": true,
+          "signer": true
+        }
+      ],
+      "args": [
+        {
+          "name": "shardX",
+          "type": "u16"
+        },
+        {
+          "name": "shardY",
+          "type": "u16"
+        },
+        {
+          "name": "offset",
+          "type": "u16"
+        },
+        {
+          "name": "bytes",
+          "type": "bytes"
+        },
+        {
+          "name": "skipTransparent",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "writeTemplateChunk",
+      "docs": [
+        "Write part of a template's target (base layer, creator only, before locking)",
+        "`data` holds palette indices in row-major order starting at `offset`; 0 = don't care."
+      ],
+      "discriminator": [
+        121,
+        145,
+        253,
+        25,
+        176,
+        204,
+        239,
+        211
+      ],
+      "accounts": [
+        {
+          "name": "template",
+          "writable": true
+        },
+        {
+          "name": "creator",
+          "signer": true,
+          "relations": [
+            "template"
+          ]
+        }
+      ],
+      "args": [
+        {
+          "name": "offset",
+          "type": "u16"
+        },
+        {
+          "name": "data",
+          "type": "bytes"
+        }
+      ]
+    }
+  ],
+  "accounts": [
+    {
+      "name": "artwork",
+      "discriminator": [
+        18,
+        146,
+        190,
+        9,
+        4,
+        164,
+        2,
+        47
+      ]
+    },
+    {
+      "name": "artworkCard",
+      "discriminator": [
+        156,
+        183,
+        162,
+        142,
+        215,
+        198,
+        67,
+        122
+      ]
+    },
+    {
+      "name": "bounty",
+      "discriminator": [
+        237,
+        16,
+        105,
+        198,
+        19,
+        69,
+        242,
+        234
+      ]
+    },
+    {
+      "name": "bountyFunding",
+      "discriminator": [
+        54,
+        7,
+        142,
+        24,
+        146,
+        105,
+        170,
+        221
+      ]
+    },
+    {
+      "name": "canvasCheckpoint",
+      "discriminator": [
+        235,
+        28,
+        34,
+        11,
+        165,
+        98,
+        16,
+        28
+      ]
+    },
+    {
+      "name": "canvasState",
+      "discriminator": [
+        64,
+        117,
+        74,
+        242,
+        207,
+        52,
+        148,
+        249
+      ]
+    },
+    {
+      "name": "faction",
+      "discriminator": [
+        131,
+        20,
+        223,
+        22,
+        227,
+        204,
+        231,
+        35
+      ]
+    },
+    {
+      "name": "factionTerritory",
+      "discriminator": [
+        240,
+        144,
+        216,
+        126,
+        45,
+        119,
+        89,
+        55
+      ]
+    },
+    {
+      "name": "leaderboard",
+      "discriminator": [
+        247,
+        186,
+        238,
+        243,
+        194,
+        30,
+        9,
+        36
+      ]
+    },
+    {
+      "name": "partner",
+      "discriminator": [
+        122,
+        43,
+        246,
+        239,
+        141,
+        56,
+        243,
+        182
+      ]
+    },
+    {
+      "name": "pixelShard",
+      "discriminator": [
+        71,
+        74,
+        139,
+        167,
+        18,
+        144,
+        148,
+        25
+      ]
+    },
+    {
+      "name": "playerProfile",
+      "discriminator": [
+        82,
+        226,
+        99,
+        87,
+        164,
+        130,
+        181,
+        80
+      ]
+    },
+    {
+      "name": "referral",
+      "discriminator": [
+        30,
+        235,
+        136,
+        224,
+        106,
+        107,
+        49,
+        64
+      ]
+    },
+    {
+      "name": "rewardAccrual",
+      "discriminator": [
+        158,
+        244,
+        116,
+        30,
+        227,
+        132,
+        105,
+        182
+      ]
+    },
+    {
+      "name": "rewardClaim",
+      "discriminator": [
+        194,
+        80,
+        130,
+        80,
+        113,
+        62,
+        2,
+        91
+      ]
+    },
+    {
+      "name": "season",
+      "discriminator": [
+        76,
+        67,
+        93,
+        156,
+        180,
+        157,
+        248,
+        47
+      ]
+    },
+    {
+      "name": "seasonResult",
+      "discriminator": [
+        249,
+        186,
+        189,
+        55,
+        110,
+        158,
+        11,
+        168
+      ]
+    },
+    {
+      "name": "sessionAccount",
+      "discriminator": [
+        74,
+        34,
+        65,
+        133,
+        96,
+        163,
+        80,
+        69
+      ]
+    },
+    {
+      "name": "shardHistory",
+      "discriminator": [
+        149,
+        226,
+        8,
+        173,
+        162,
+        72,
+        205,
+        31
+      ]
+    },
+    {
+      "name": "shardPainters",
+      "discriminator": [
+        130,
+        177,
+        180,
+        112,
+        63,
+        122,
+        206,
+        148
+      ]
+    },
+    {
+      "name": "shardSnapshot",
+      "discriminator": [
+        176,
+        199,
+        16,
+        178,
+        148,
+        222,
+        143,
+        217
+      ]
+    },
+    {
+      "name": "shardSnapshotIndex",
+      "discriminator": [
+        153,
+        194,
+        1,
+        60,
+        138,
+        173,
+        119,
+        109
+      ]
+    },
+    {
+      "name": "template",
+      "discriminator": [
+        43,
+        26,
+        88,
+        69,
+        69,
+        96,
+        9,
+        79
+      ]
+    },
+    {
+      "name": "walletStats",
+      "discriminator": [
+        96,
+        53,
+        96,
+        132,
+        136,
+        197,
+        236,
+        144
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "achievementUnlocked",
+      "discriminator": [
+        125,
+        160,
+        118,
+        30,
+        180,
+        209,
+        171,
+        62
+      ]
+    },
+    {
+      "name": "artworkCardTransferred",
+      "discriminator": [
+        203,
+        236,
+        0,
+        168,
+        70,
+        186,
+        211,
+        120
+      ]
+    },
+    {
+      "name": "artworkRegistered",
+      "discriminator": [
+        208,
+        137,
+        180,
+        69,
+        29,
+        66,
+        44,
+        228
+      ]
+    },
+    {
+      "name": "badgeClaimed",
+      "discriminator": [
+        11,
+        176,
+        119,
+        121,
+        7,
+        255,
+        229,
+        74
+      ]
+    },
+    {
+      "name": "bountyCompleted",
+      "discriminator": [
+        206,
+        22,
+        65,
+        135,
+        31,
+        56,
+        249,
+        158
+      ]
+    },
+    {
+      "name": "bountyFunded",
+      "discriminator": [
+        142,
+        150,
+        1,
+        35,
+        45,
+        75,
+        188,
+        226
+      ]
+    },
+    {
+      "name": "bountyPaid",
+      "discriminator": [
+        60,
+        72,
+        10,
+        171,
+        122,
+        59,
+        238,
+        148
+      ]
+    },
+    {
+      "name": "bountyRefunded",
+      "discriminator": [
+        158,
+        18,
+        109,
+        41,
+        239,
+        13,
+        79,
+        43
+      ]
+    },
+    {
+      "name": "bountySwept",
+      "discriminator": [
+        123,
+        120,
+        103,
+        72,
+        203,
+        224,
+        212,
+        168
+      ]
+    },
+    {
+      "name": "changesUndone",
+      "discriminator": [
+        226,
+        39,
+        198,
+        209,
+        163,
+        143,
+        122,
+        242
+      ]
+    },
+    {
+      "name": "checkpointFinalized",
+      "discriminator": [
+        199,
+        122,
+        185,
+        86,
+        255,
+        76,
+        62,
+        43
+      ]
+    },
+    {
+      "name": "checkpointStarted",
+      "discriminator": [
+        113,
+        56,
+        158,
+        176,
+        1,
+        115,
+        123,
+        0
+      ]
+    },
+    {
+      "name": "cooldownTriggered",
+      "discriminator": [
+        227,
+        79,
+        170,
+        242,
+        223,
+        65,
+        125,
+        212
+      ]
+    },
+    {
+      "name": "factionJoined",
+      "discriminator": [
+        29,
+        162,
+        4,
+        243,
+        90,
+        166,
+        94,
+        158
+      ]
+    },
+    {
+      "name": "leaderboardUpdated",
+      "discriminator": [
+        28,
+        209,
+        133,
+        1,
+        229,
+        195,
+        230,
+        228
+      ]
+    },
+    {
+      "name": "painterReverted",
+      "discriminator": [
+        113,
+        208,
+        79,
+        79,
+        121,
+        152,
+        114,
+        213
+      ]
+    },
+    {
+      "name": "pixelChanged",
+      "discriminator": [
+        140,
+        76,
+        21,
+        27,
+        179,
+        226,
+        1,
+        84
+      ]
+    },
+    {
+      "name": "pixelsBulkChanged",
+      "discriminator": [
+        74,
+        182,
+        181,
+        228,
+        31,
+        159,
+        173,
+        227
+      ]
+    },
+    {
+      "name": "referralRecorded",
+      "discriminator": [
+        128,
+        33,
+        88,
+        93,
+        58,
+        242,
+        121,
+        168
+      ]
+    },
+    {
+      "name": "referralRewarded",
+      "discriminator": [
+        9,
+        69,
+        68,
+        66,
+        160,
+        28,
+        147,
+        12
+      ]
+    },
+    {
+      "name": "regionChanged",
+      "discriminator": [
+        247,
+        132,
+        16,
+        27,
+        206,
+        77,
+        128,
+        249
+      ]
+    },
+    {
+      "name": "rewardsClaimed",
+      "discriminator": [
+        75,
+        98,
+        88,
+        18,
+        219,
+        112,
+        88,
+        121
+      ]
+    },
+    {
+      "name": "seasonFinalized",
+      "discriminator": [
+        17,
+        0,
+        146,
+        8,
+        1,
+        167,
+        147,
+        101
+      ]
+    },
+    {
+      "name": "seasonOpened",
+      "discriminator": [
+        188,
+        53,
+        161,
+        112,
+        10,
+        91,
+        118,
+        131
+      ]
+    },
+    {
+      "name": "sessionCreated",
+      "discriminator": [
+        107,
+        111,
+        254,
+        25,
+        21,
+        122,
+        220,
+        225
+      ]
+    },
+    {
+      "name": "sessionDelegated",
+      "discriminator": [
+        196,
+        91,
+        183,
+        117,
+        146,
+        74,
+        195,
+        49
+      ]
+    },
+    {
+      "name": "shardCommitted",
+      "discriminator": [
+        101,
+        191,
+        133,
+        26,
+        249,
+        100,
+        41,
+        174
+      ]
+    },
+    {
+      "name": "shardDelegated",
+      "discriminator": [
+        252,
+        9,
+        214,
+        249,
+        36,
+        217,
+        191,
+        161
+      ]
+    },
+    {
+      "name": "shardInitialized",
+      "discriminator": [
+        183,
+        80,
+        70,
+        77,
+        210,
+        226,
+        161,
+        150
+      ]
+    },
+    {
+      "name": "shardMigrated",
+      "discriminator": [
+        86,
+        50,
+        97,
+        111,
+        104,
+        136,
+        5,
+        79
+      ]
+    },
+    {
+      "name": "shardRestored",
+      "discriminator": [
+        189,
+        204,
+        126,
+        47,
+        60,
+        215,
+        66,
+        214
+      ]
+    },
+    {
+      "name": "templateProgressChecked",
+      "discriminator": [
+        93,
+        42,
+        245,
+        146,
+        195,
+        58,
+        233,
+        77
+      ]
+    },
+    {
+      "name": "territoryMoved",
+      "discriminator": [
+        25,
+        222,
+        32,
+        97,
+        223,
+        51,
+        205,
+        99
+      ]
+    }
+  ],
+  "errors": [
+    {
+      "code": 6000,
+      "name": "invalidShardCoord",
+      "msg": "Invalid shard coordinates: must be 0-5825"
+    },
+    {
+      "code": 6001,
+      "name": "invalidPixelCoord",
+      "msg": "Invalid pixel coordinates: must be 0-524287"
+    },
+    {
+      "code": 6002,
+      "name": "shardMismatch",
+      "msg": "Shard coordinates don't match pixel location"
+    },
+    {
+      "code": 6003,
+      "name": "invalidColor",
+      "msg": "Invalid color: must be 1-255"
+    },
+    {
+      "code": 6004,
+      "name": "invalidAuth",
+      "msg": "Invalid authentication"
+    },
+    {
+      "code": 6005,
+      "name": "cooldown",
+      "msg": "Cooldown active: limit reached"
+    },
+    {
+      "code": 6006,
+      "name": "emptyBulkPixels",
+      "msg": "Bulk pixels array is empty"
+    },
+    {
+      "code": 6007,
+      "name": "bulkTooLarge",
+      "msg": "Too many pixels for one call"
+    },
+    {
+      "code": 6008,
+      "name": "bulkExceedsCooldown",
+      "msg": "Bulk placement would exceed cooldown limit"
+    },
+    {
+      "code": 6009,
+      "name": "invalidShardAccount",
+      "msg": "Invalid shard account"
+    },
+    {
+      "code": 6010,
+      "name": "invalidShardCount",
+      "msg": "Multi-shard placement needs 1-4 shard accounts"
+    },
+    {
+      "code": 6011,
+      "name": "duplicateShard",
+      "msg": "Shard account passed more than once"
+    },
+    {
+      "code": 6012,
+      "name": "invalidPackedData",
+      "msg": "Packed pixel data length doesn't match pixel count"
+    },
+    {
+      "code": 6013,
+      "name": "floodFillTooLarge",
+      "msg": "Flood fill area exceeds maximum of 4096 pixels"
+    },
+    {
+      "code": 6014,
+      "name": "notShardOwner",
+      "msg": "Only the shard owner can do this"
+    },
+    {
+      "code": 6015,
+      "name": "invalidTransform",
+      "msg": "Invalid transform: quarter turns must be 0-3"
+    },
+    {
+      "code": 6016,
+      "name": "snapshotLimit",
+      "msg": "Snapshot limit reached for this shard"
+    },
+    {
+      "code": 6017,
+      "name": "invalidSnapshot",
+      "msg": "Unsupported snapshot version"
+    },
+    {
+      "code": 6018,
+      "name": "painterLayerRequired",
+      "msg": "Shard has a painter layer: pass its ShardPainters account"
+    },
+    {
+      "code": 6019,
+      "name": "painterNotFound",
+      "msg": "Wallet has no attributed pixels on this shard"
+    },
+    {
+      "code": 6020,
+      "name": "historyLayerRequired",
+      "msg": "Shard has change history enabled: pass its ShardHistory account"
+    },
+    {
+      "code": 6021,
+      "name": "checkpointAlreadyFinalized",
+      "msg": "Checkpoint is already finalized"
+    },
+    {
+      "code": 6022,
+      "name": "checkpointNotFinalized",
+      "msg": "Checkpoint is not finalized yet"
+    },
+    {
+      "code": 6023,
+      "name": "checkpointOutOfOrder",
+      "msg": "Shards must be added to a checkpoint in creation order"
+    },
+    {
+      "code": 6024,
+      "name": "invalidCheckpointProof",
+      "msg": "Checkpoint proof does not match the root"
+    },
+    {
+      "code": 6025,
+      "name": "regionTooLarge",
+      "msg": "Region exceeds maximum of 1000 pixels"
+    },
+    {
+      "code": 6026,
+      "name": "notAdmin",
+      "msg": "Only the program upgrade authority can do this"
+    },
+    {
+      "code": 6027,
+      "name": "invalidPartnerQuota",
+      "msg": "Partner quota and period must be greater than zero"
+    },
+    {
+      "code": 6028,
+      "name": "invalidPartnerAuthority",
+      "msg": "Signer is not the partner program's authority PDA"
+    },
+    {
+      "code": 6029,
+      "name": "partnerDisabled",
+      "msg": "Partner is disabled"
+    },
+    {
+      "code": 6030,
+      "name": "partnerQuotaExceeded",
+      "msg": "Partner quota exceeded, wait for the next window"
+    },
+    {
+      "code": 6031,
+      "name": "shardHasPaintHook",
+      "msg": "Shard has a paint hook: only place_pixel and place_pixels_bulk may paint it"
+    },
+    {
+      "code": 6032,
+      "name": "invalidHookAccounts",
+      "msg": "Paint hook accounts missing or not matching the validation account"
+    },
+    {
+      "code": 6033,
+      "name": "invalidProfile",
+      "msg": "Profile does not belong to the session's main wallet"
+    },
+    {
+      "code": 6034,
+      "name": "nothingToClaim",
+      "msg": "No rewards to claim"
+    },
+    {
+      "code": 6035,
+      "name": "invalidBadge",
+      "msg": "Unknown badge"
+    },
+    {
+      "code": 6036,
+      "name": "badgeThresholdNotMet",
+      "msg": "Not enough shards owned for this badge"
+    },
+    {
+      "code": 6037,
+      "name": "invalidFaction",
+      "msg": "Invalid faction, or faction account not matching the profile"
+    },
+    {
+      "code": 6038,
+      "name": "factionSwitchCooldown",
+      "msg": "Faction switch cooldown active"
+    },
+    {
+      "code": 6039,
+      "name": "seasonFrozen",
+      "msg": "Season is frozen: painting reopens with the next season"
+    },
+    {
+      "code": 6040,
+      "name": "invalidSeason",
+      "msg": "Invalid season"
+    },
+    {
+      "code": 6041,
+      "name": "seasonNotFinalized",
+      "msg": "Previous season not finalized"
+    },
+    {
+      "code": 6042,
+      "name": "seasonNotRunning",
+      "msg": "No season running"
+    },
+    {
+      "code": 6043,
+      "name": "seasonNotEnded",
+      "msg": "Season has not ended yet"
+    },
+    {
+      "code": 6044,
+      "name": "invalidScoreSource",
+      "msg": "Score source is not the wallet's counter account for this metric"
+    },
+    {
+      "code": 6045,
+      "name": "invalidArtwork",
+      "msg": "Invalid artwork: size must be 1-360 per side and the title at most 32 bytes"
+    },
+    {
+      "code": 6046,
+      "name": "notCardOwner",
+      "msg": "Signer does not own this artwork card"
+    },
+    {
+      "code": 6047,
+      "name": "invalidTemplate",
+      "msg": "Invalid template: size must be 1-90 per side, threshold 1-10000 bps and target within bounds"
+    },
+    {
+      "code": 6048,
+      "name": "templateLocked",
+      "msg": "Template is locked"
+    },
+    {
+      "code": 6049,
+      "name": "templateNotLocked",
+      "msg": "Template must be locked first"
+    },
+    {
+      "code": 6050,
+      "name": "bountyClosed",
+      "msg": "Bounty already completed"
+    },
+    {
+      "code": 6051,
+      "name": "bountyNotCompleted",
+      "msg": "Bounty has not completed yet"
+    },
+    {
+      "code": 6052,
+      "name": "notBountyContributor",
+      "msg": "Signer is not a contributor of this bounty"
+    },
+    {
+      "code": 6053,
+      "name": "ownerRewardsRequired",
+      "msg": "The shard owner's reward accrual must be passed for pixels on someone else's shard"
+    },
+    {
+      "code": 6054,
+      "name": "invalidReferrer",
+      "msg": "Invalid referrer or referral accounts"
+    },
+    {
+      "code": 6055,
+      "name": "bountyExpired",
+      "msg": "Bounty deadline has passed"
+    },
+    {
+      "code": 6056,
+      "name": "bountyOutstanding",
+      "msg": "Bounty still has claims or refunds outstanding"
+    },
+    {
+      "code": 6057,
+      "name": "shardAlreadyMigrated",
+      "msg": "Shard already uses the current layout"
+    }
+  ],
+  "types": [
+    {
+      "name": "achievementUnlocked",
+      "docs": [
+        "`wallet` unlocked ACHIEVEMENT_* bit `achievement`; `streak_days` is the current",
+        "streak for painting achievements and 0 for shard ones"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "achievement",
+            "type": "u8"
+          },
+          {
+            "name": "streakDays",
+            "type": "u16"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "artwork",
+      "docs": [
+        "A rectangle of the canvas registered by its artist (base layer only)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "x",
+            "docs": [
+              "Global top-left corner"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "y",
+            "type": "u32"
+          },
+          {
+            "name": "w",
+            "type": "u16"
+          },
+          {
+            "name": "h",
+            "type": "u16"
+          },
+          {
+            "name": "title",
+            "type": "string"
+          },
+          {
+            "name": "createdAt",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "artworkCard",
+      "docs": [
+        "Transferable ownership record of an artwork (base layer only)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "artwork",
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "mintedAt",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "artworkCardTransferred",
+      "docs": [
+        "An artwork card changed hands (`from` is the default key when it was minted)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "artwork",
+            "type": "pubkey"
+          },
+          {
+            "name": "from",
+            "type": "pubkey"
+          },
+          {
+            "name": "to",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "artworkRegistered",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "artwork",
+            "type": "pubkey"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "x",
+            "type": "u32"
+          },
+          {
+            "name": "y",
+            "type": "u32"
+          },
+          {
+            "name": "w",
+            "type": "u16"
+          },
+          {
+            "name": "h",
+            "type": "u16"
+          },
+          {
+            "name": "title",
+            "type": "string"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "badgeClaimed",
+      "docs": [
+        "A wallet minted badge `badge` (index into the badge ladder)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "badge",
+            "type": "u8"
+          },
+          {
+            "name": "shardsOwned",
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bounty",
+      "docs": [
+        "Lamport escrow of a template; the lamports are held by this account (base layer only)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "template",
+            "type": "pubkey"
+          },
+          {
+            "name": "funded",
+            "docs": [
+              "Lamports deposited by funders, less refunds"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "deadline",
+            "docs": [
+              "Last moment (unix time) the bounty can complete"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "completedAt",
+            "docs": [
+              "When the template crossed its threshold (0 = still open)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "contributorPixels",
+            "docs": [
+              "Sum of the contributors' pixels, the denominator of each share"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "contributors",
+            "docs": [
+              "Top painters of matching pixels at completion, most pixels first"
+            ],
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "bountyContributor"
+                }
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bountyCompleted",
+      "docs": [
+        "A template crossed its threshold; `contributors` can now claim their shares"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "template",
+            "type": "pubkey"
+          },
+          {
+            "name": "funded",
+            "type": "u64"
+          },
+          {
+            "name": "contributors",
+            "type": "u8"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bountyContributor",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "docs": [
+              "Main wallet"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "pixels",
+            "docs": [
+              "Matching template pixels it painted last"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "claimed",
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bountyFunded",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "template",
+            "type": "pubkey"
+          },
+          {
+            "name": "funder",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "funded",
+            "docs": [
+              "Total lamports funded so far"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bountyFunding",
+      "docs": [
+        "One funder's total deposit into a bounty, refundable if the bounty expires"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "bounty",
+            "type": "pubkey"
+          },
+          {
+            "name": "funder",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bountyPaid",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "template",
+            "type": "pubkey"
+          },
+          {
+            "name": "contributor",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bountyRefunded",
+      "docs": [
+        "`funder` took back `amount` lamports from a bounty that missed its deadline"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "template",
+            "type": "pubkey"
+          },
+          {
+            "name": "funder",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bountySwept",
+      "docs": [
+        "A settled bounty was closed, returning `amount` lamports (leftovers and rent) to `creator`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "template",
+            "type": "pubkey"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "bulkPixel",
+      "docs": [
+        "Pixel data for bulk placement",
+        "Uses local coordinates within a shard (0-89)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "localX",
+            "docs": [
+              "Local X coordinate within shard (0-89)"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "localY",
+            "docs": [
+              "Local Y coordinate within shard (0-89)"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "color",
+            "docs": [
+              "Color index (1-255, 0 is reserved for transparent)"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "canvasCheckpoint",
+      "docs": [
+        "Merkle commitment to every shard's (seq, pixels hash)",
+        "Leaf i is shard index i (see checkpoint_leaf); the tree is padded to",
+        "CHECKPOINT_DEPTH with zero leaves and the root is mixed with the shard count.",
+        "Built incrementally by crank_checkpoint, keeping only the left frontier. Cranks",
+        "span many slots, so it is not a point-in-time snapshot: each leaf is its shard as",
+        "of the crank that added it, somewhere in [started_at, finalized_at]."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "checkpointId",
+            "type": "u32"
+          },
+          {
+            "name": "shardCount",
+            "docs": [
+              "Shards covered: the canvas shard count when the checkpoint was started"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "nextIndex",
+            "docs": [
+              "Index of the next shard to add"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "season",
+            "docs": [
+              "Season running when the checkpoint was started (0 before the first one)"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "minSeq",
+            "docs": [
+              "Lowest and highest shard seq committed to (u64::MAX and 0 while empty)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "maxSeq",
+            "type": "u64"
+          },
+          {
+            "name": "frontier",
+            "docs": [
+              "Rightmost completed left subtree at each height"
+            ],
+            "type": {
+              "array": [
+                {
+                  "array": [
+                    "u8",
+                    32
+                  ]
+                },
+                26
+              ]
+            }
+          },
+          {
+            "name": "root",
+            "docs": [
+              "Published root, set once every shard has been added"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "finalized",
+            "type": "bool"
+          },
+          {
+            "name": "startedAt",
+            "type": "u64"
+          },
+          {
+            "name": "finalizedAt",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "canvasState",
+      "docs": [
+        "Canvas-wide counters (base layer only, never delegated)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardCount",
+            "docs": [
+              "Number of shards created so far, also the index of the next one"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "checkpointCount",
+            "docs": [
+              "Number of checkpoints started so far, also the id of the next one"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "changesUndone",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "changesUndone",
+            "docs": [
+              "Number of changes actually rolled back (may be less than requested)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "chunks",
+            "docs": [
+              "PixelsBulkChanged events carrying the rolled back changes, in undo order"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          },
+          {
+            "name": "seq",
+            "type": "u64"
+          },
+          {
+            "name": "contentHash",
+            "docs": [
+              "PixelShard.content_hash after the undo"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "checkpointFinalized",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "checkpointId",
+            "type": "u32"
+          },
+          {
+            "name": "shardCount",
+            "type": "u32"
+          },
+          {
+            "name": "root",
+            "type": {
+              "array": [
+                "u8",
+                32
+              ]
+            }
+          },
+          {
+            "name": "season",
+            "type": "u32"
+          },
+          {
+            "name": "minSeq",
+            "type": "u64"
+          },
+          {
+            "name": "maxSeq",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "checkpointStarted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "checkpointId",
+            "type": "u32"
+          },
+          {
+            "name": "shardCount",
+            "type": "u32"
+          },
+          {
+            "name": "season",
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "cooldownStatus",
+      "docs": [
+        "Returned by get_cooldown_status"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pixelsRemaining",
+            "docs": [
+              "Pixels the session can still place on others' shards right now"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "remainingSeconds",
+            "docs": [
+              "Seconds until the burst resets (0 when not cooling down)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "tier",
+            "docs": [
+              "XP tier the burst and period were computed for"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "cooldownTriggered",
+      "docs": [
+        "A session used up its non-owner burst; painting on others' shards is blocked",
+        "for `remaining_seconds`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "sessionKey",
+            "type": "pubkey"
+          },
+          {
+            "name": "remainingSeconds",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "faction",
+      "docs": [
+        "Faction membership and painting score (delegated to the ER)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u8"
+          },
+          {
+            "name": "members",
+            "type": "u32"
+          },
+          {
+            "name": "pixelsPlaced",
+            "docs": [
+              "Pixels placed by members, on any shard"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "season",
+            "docs": [
+              "Season the season_* counters belong to; they restart when a new season shows up"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "seasonPoints",
+            "docs": [
+              "Points scored in `season`, rolled up into the Season by finalize_season"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "seasonPixels",
+            "docs": [
+              "Pixels placed by members in `season`"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "factionJoined",
+      "docs": [
+        "A wallet joined `faction`, leaving `previous_faction` (0 when it had none)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "faction",
+            "type": "u8"
+          },
+          {
+            "name": "previousFaction",
+            "type": "u8"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "factionTerritory",
+      "docs": [
+        "Shards owned by a faction's members (base layer only, never delegated)",
+        "A wallet's shards are counted here while its WalletStats.territory_faction is this id."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u8"
+          },
+          {
+            "name": "shards",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "globalPixel",
+      "docs": [
+        "Pixel data for multi-shard placement",
+        "Uses global coordinates (0-524,287); the shard is derived from them"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "px",
+            "docs": [
+              "Global X coordinate"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "py",
+            "docs": [
+              "Global Y coordinate"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "color",
+            "docs": [
+              "Color index (1-255, 0 is reserved for transparent)"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "leaderboard",
+      "docs": [
+        "Top LEADERBOARD_SIZE wallets for one metric, best first (delegated to the ER)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "metric",
+            "type": {
+              "defined": {
+                "name": "leaderboardMetric"
+              }
+            }
+          },
+          {
+            "name": "entries",
+            "type": {
+              "vec": {
+                "defined": {
+                  "name": "leaderboardEntry"
+                }
+              }
+            }
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "leaderboardEntry",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "score",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "leaderboardMetric",
+      "docs": [
+        "Metric a leaderboard ranks wallets by"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "shardsOwned"
+          },
+          {
+            "name": "enemyPixels"
+          },
+          {
+            "name": "xp"
+          },
+          {
+            "name": "rewardsEarned"
+          }
+        ]
+      }
+    },
+    {
+      "name": "leaderboardUpdated",
+      "docs": [
+        "`wallet` now ranks `rank` (0 = first) on the `metric` leaderboard"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "metric",
+            "type": {
+              "defined": {
+                "name": "leaderboardMetric"
+              }
+            }
+          },
+          {
+            "name": "wallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "score",
+            "type": "u64"
+          },
+          {
+            "name": "rank",
+            "type": "u8"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "painterReverted",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "painter",
+            "docs": [
+              "Main wallet whose pixels were reverted"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "snapshotId",
+            "docs": [
+              "Snapshot the pixels were reverted to, or None when they were cleared"
+            ],
+            "type": {
+              "option": "u32"
+            }
+          },
+          {
+            "name": "pixelsReverted",
+            "type": "u16"
+          },
+          {
+            "name": "chunks",
+            "docs": [
+              "PixelsBulkChanged events carrying the reverted pixels"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          },
+          {
+            "name": "seq",
+            "type": "u64"
+          },
+          {
+            "name": "contentHash",
+            "docs": [
+              "PixelShard.content_hash after the revert"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "partner",
+      "docs": [
+        "Registry entry letting a partner program paint through CPI"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "programId",
+            "docs": [
+              "Program allowed to paint; it signs with its [PARTNER_AUTHORITY_SEED] PDA"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "authorityBump",
+            "docs": [
+              "Bump of that signer PDA under program_id"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "quota",
+            "docs": [
+              "Pixels allowed per quota window"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "quotaPeriod",
+            "docs": [
+              "Quota window length in seconds"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "used",
+            "docs": [
+              "Pixels placed in the current window"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "windowStart",
+            "type": "u64"
+          },
+          {
+            "name": "totalPixels",
+            "docs": [
+              "Pixels placed over the partner's lifetime"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "enabled",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pixelChange",
+      "docs": [
+        "One recorded pixel change"
+      ],
+      "serialization": "bytemuckunsafe",
+      "repr": {
+        "kind": "c"
+      },
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "timestamp",
+            "type": "u64"
+          },
+          {
+            "name": "localPixelId",
+            "docs": [
+              "Same indexing as PixelShard.pixels"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "oldColor",
+            "type": "u8"
+          },
+          {
+            "name": "newColor",
+            "type": "u8"
+          },
+          {
+            "name": "painterSlot",
+            "docs": [
+              "Painter layer slot of whoever made the change (0 = unknown)"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "pixelChanged",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "px",
+            "type": "u32"
+          },
+          {
+            "name": "py",
+            "type": "u32"
+          },
+          {
+            "name": "color",
+            "type": "u8"
+          },
+          {
+            "name": "painter",
+            "type": "pubkey"
+          },
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          },
+          {
+            "name": "seq",
+            "docs": [
+              "PixelShard.seq of the shard containing (px, py) after this write"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "faction",
+            "docs": [
+              "Painter's faction (0 = none or no profile passed)"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pixelShard",
+      "docs": [
+        "A single shard of the pixel canvas",
+        "Each shard stores 8,100 pixels (90×90 grid) using 8-bit colors = ~8KB",
+        "Up to 33,942,276 shards (5826×5826 grid) can cover the full 524,288×524,288 canvas",
+        "Shards are created on-demand when users paint in new regions"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardX",
+            "docs": [
+              "Shard X coordinate (0-5825)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "docs": [
+              "Shard Y coordinate (0-5825)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "pixels",
+            "docs": [
+              "Pixel data - 8-bit storage (1 byte per pixel)",
+              "Index = local_y * 90 + local_x",
+              "Value = color_index (0 = unset/transparent, 1-255 = palette colors)"
+            ],
+            "type": "bytes"
+          },
+          {
+            "name": "creator",
+            "docs": [
+              "Creator of the shard (who paid for initialization)"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "PDA bump seed"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "layers",
+            "docs": [
+              "Companion layers that writes must keep in sync (LAYER_* bit flags)"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "seq",
+            "docs": [
+              "Number of write operations applied so far; events carry it so indexers can spot gaps"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "contentHash",
+            "docs": [
+              "Wrapping sum of pixel_hash over all pixels, to verify a locally rebuilt shard"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "index",
+            "docs": [
+              "Position in creation order (0, 1, 2, ...), the shard's leaf in canvas checkpoints"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "hookProgram",
+            "docs": [
+              "Program whose `on_paint` vets place_pixel / place_pixels_bulk (default = no hook)"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "pixelsBulkChanged",
+      "docs": [
+        "One event per bulk write to a shard, in place of a PixelChanged per pixel",
+        "`data` holds `count` 21-bit (local index, color) records, encoded as for place_pixels_packed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "count",
+            "type": "u16"
+          },
+          {
+            "name": "data",
+            "type": "bytes"
+          },
+          {
+            "name": "painter",
+            "type": "pubkey"
+          },
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          },
+          {
+            "name": "seq",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "playerProfile",
+      "docs": [
+        "Per main wallet XP and placement counters, shared by all of its sessions"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "xp",
+            "type": "u64"
+          },
+          {
+            "name": "pixelsOnOthers",
+            "docs": [
+              "Pixels placed on shards the wallet does not own"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "pixelsOnOwn",
+            "docs": [
+              "Pixels placed on the wallet's own shards"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "faction",
+            "docs": [
+              "Faction id, 0 when not in a faction"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "factionJoinedAt",
+            "docs": [
+              "When the wallet joined its current faction"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "streakDays",
+            "docs": [
+              "Consecutive days (unix time / SECONDS_PER_DAY) with at least one placement"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "bestStreak",
+            "type": "u16"
+          },
+          {
+            "name": "lastActiveDay",
+            "docs": [
+              "Last day with a placement, 0 before the first one"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "achievements",
+            "docs": [
+              "Unlocked ACHIEVEMENT_* bits (painting ones; shard ones live in WalletStats)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referral",
+      "docs": [
+        "Who referred a main wallet and whom it referred (base layer only, never delegated)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "referrer",
+            "docs": [
+              "Main wallet that referred this one, default when none"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "referrals",
+            "docs": [
+              "Main wallets this one referred"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "referralRewards",
+            "docs": [
+              "Reward base units earned as a referrer"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referralRecorded",
+      "docs": [
+        "`wallet` signed up referred by `referrer`, who now has `referrals` referrals"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "referrals",
+            "type": "u32"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "referralRewarded",
+      "docs": [
+        "`referrer` received `amount` reward base units from a claim by `wallet`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "referrer",
+            "type": "pubkey"
+          },
+          {
+            "name": "wallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "regionChanged",
+      "docs": [
+        "Summary of a drawing primitive; (x0, y0)-(x1, y1) is the inclusive local bounding box",
+        "of the affected area. The exact changes precede it as `chunks` PixelsBulkChanged",
+        "events with the same seq; a consumer that saw fewer (e.g. truncated logs) should",
+        "refetch the shard."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "op",
+            "type": {
+              "defined": {
+                "name": "regionOp"
+              }
+            }
+          },
+          {
+            "name": "x0",
+            "type": "u8"
+          },
+          {
+            "name": "y0",
+            "type": "u8"
+          },
+          {
+            "name": "x1",
+            "type": "u8"
+          },
+          {
+            "name": "y1",
+            "type": "u8"
+          },
+          {
+            "name": "color",
+            "docs": [
+              "Fill color, or 0 when the operation wrote mixed colors"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "pixelsChanged",
+            "type": "u16"
+          },
+          {
+            "name": "chunks",
+            "docs": [
+              "PixelsBulkChanged events carrying the changed pixels"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "painter",
+            "type": "pubkey"
+          },
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          },
+          {
+            "name": "seq",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "regionCopy",
+      "docs": [
+        "Source rectangle and transform for copy_region (local coordinates, 0-89)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "srcX",
+            "docs": [
+              "Top-left corner of the source rectangle"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "srcY",
+            "type": "u8"
+          },
+          {
+            "name": "width",
+            "docs": [
+              "Source rectangle size"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "height",
+            "type": "u8"
+          },
+          {
+            "name": "dstX",
+            "docs": [
+              "Top-left corner of the destination rectangle"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "dstY",
+            "type": "u8"
+          },
+          {
+            "name": "flipX",
+            "docs": [
+              "Mirror the source left-right"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "flipY",
+            "docs": [
+              "Mirror the source top-bottom"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "quarterTurns",
+            "docs": [
+              "Clockwise 90° rotations applied after flipping (0-3)"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "skipTransparent",
+            "docs": [
+              "Leave destination pixels unchanged where the source is transparent (0)"
+            ],
+            "type": "bool"
+          }
+        ]
+      }
+    },
+    {
+      "name": "regionOp",
+      "docs": [
+        "Kind of multi-pixel operation reported by RegionChanged"
+      ],
+      "type": {
+        "kind": "enum",
+        "variants": [
+          {
+            "name": "fillRect"
+          },
+          {
+            "name": "line"
+          },
+          {
+            "name": "floodFill"
+          },
+          {
+            "name": "chunkWrite"
+          },
+          {
+            "name": "copy"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardAccrual",
+      "docs": [
+        "Token rewards a shard owner has earned from visitors' pixels (delegated to the ER)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "docs": [
+              "Main wallet owning the shards"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "accruedPixels",
+            "docs": [
+              "Pixels placed by others on the owner's shards, ever"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardClaim",
+      "docs": [
+        "How much of an owner's accrual has been minted (base layer only, never delegated)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "claimedPixels",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardsClaimed",
+      "docs": [
+        "A shard owner minted the rewards for `pixels` visitor pixels (`amount` in base units, after any referral share)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "pixels",
+            "type": "u64"
+          },
+          {
+            "name": "amount",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "season",
+      "docs": [
+        "The current (or last) season, delegated to the ER"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "docs": [
+              "Season number, 0 before the first season"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "start",
+            "docs": [
+              "Painting is open in [start, end)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "end",
+            "type": "u64"
+          },
+          {
+            "name": "rules",
+            "type": {
+              "defined": {
+                "name": "seasonRules"
+              }
+            }
+          },
+          {
+            "name": "factionScores",
+            "docs": [
+              "Points per faction, indexed by faction id - 1, rolled up from the Faction accounts",
+              "by finalize_season"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          },
+          {
+            "name": "pixelsPlaced",
+            "docs": [
+              "Pixels placed this season by faction members, rolled up by finalize_season"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "winnerFaction",
+            "docs": [
+              "Winning faction once finalized (0 = none)"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "finalized",
+            "type": "bool"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "admin",
+            "docs": [
+              "Wallet allowed to open seasons, the program upgrade authority at creation"
+            ],
+            "type": "pubkey"
+          }
+        ]
+      }
+    },
+    {
+      "name": "seasonFinalized",
+      "docs": [
+        "`faction_scores` is indexed by faction id - 1"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "season",
+            "type": "u32"
+          },
+          {
+            "name": "winnerFaction",
+            "type": "u8"
+          },
+          {
+            "name": "factionScores",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          },
+          {
+            "name": "pixelsPlaced",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "seasonOpened",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "season",
+            "type": "u32"
+          },
+          {
+            "name": "start",
+            "type": "u64"
+          },
+          {
+            "name": "end",
+            "type": "u64"
+          },
+          {
+            "name": "rules",
+            "type": {
+              "defined": {
+                "name": "seasonRules"
+              }
+            }
+          }
+        ]
+      }
+    },
     {
-      "code": 6006,
-      "name": "emptyBulkPixels",
-      "msg": "Bulk pixels array is empty"
+      "name": "seasonResult",
+      "docs": [
+        "Scores of a finished season (base layer only)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "id",
+            "type": "u32"
+          },
+          {
+            "name": "start",
+            "type": "u64"
+          },
+          {
+            "name": "end",
+            "type": "u64"
+          },
+          {
+            "name": "rules",
+            "type": {
+              "defined": {
+                "name": "seasonRules"
+              }
+            }
+          },
+          {
+            "name": "factionScores",
+            "type": {
+              "array": [
+                "u64",
+                4
+              ]
+            }
+          },
+          {
+            "name": "pixelsPlaced",
+            "type": "u64"
+          },
+          {
+            "name": "winnerFaction",
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     },
     {
-      "code": 6007,
-      "name": "bulkTooLarge",
-      "msg": "Bulk pixels exceeds maximum limit of 50"
+      "name": "seasonRules",
+      "docs": [
+        "How a season scores painting"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pointsPerOwnPixel",
+            "docs": [
+              "Points per pixel placed on the painter's own shards"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "pointsPerEnemyPixel",
+            "docs": [
+              "Points per pixel placed on someone else's shards"
+            ],
+            "type": "u32"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sessionAccount",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mainAddress",
+            "type": "pubkey"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "cooldownCounter",
+            "type": "u8"
+          },
+          {
+            "name": "lastPlaceTimestamp",
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sessionCreated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "sessionKey",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "sessionDelegated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "sessionKey",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "shardCommitted",
+      "docs": [
+        "Emitted on the ER when a shard commit is scheduled; seq and content_hash are the",
+        "state being committed"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "seq",
+            "type": "u64"
+          },
+          {
+            "name": "contentHash",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "shardDelegated",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "authority",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "shardHistory",
+      "docs": [
+        "Ring buffer of the most recent pixel changes on a shard, used by undo_last",
+        "Zero-copy for the same reason as ShardPainters. Once full, new changes",
+        "overwrite the oldest ones."
+      ],
+      "serialization": "bytemuckunsafe",
+      "repr": {
+        "kind": "c"
+      },
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "changes",
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "pixelChange"
+                  }
+                },
+                256
+              ]
+            }
+          },
+          {
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "head",
+            "docs": [
+              "Index the next change is written to"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "len",
+            "docs": [
+              "Number of recorded changes (at most HISTORY_CAPACITY)"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "type": {
+              "array": [
+                "u8",
+                7
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "shardInitialized",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "creator",
+            "type": "pubkey"
+          },
+          {
+            "name": "mainWallet",
+            "type": "pubkey"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "shardMigrated",
+      "docs": [
+        "A legacy shard was upgraded to the current layout and given canvas index `index`"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "index",
+            "type": "u32"
+          },
+          {
+            "name": "contentHash",
+            "type": "u64"
+          },
+          {
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
     },
     {
-      "code": 6008,
-      "name": "bulkExceedsCooldown",
-      "msg": "Bulk placement would exceed cooldown limit"
-    }
-  ],
-  "types": [
-    {
-      "name": "bulkPixel",
+      "name": "shardPainters",
       "docs": [
-        "Pixel data for bulk placement",
-        "Uses local coordinates within a shard (0-89)"
+        "Per-pixel \"last painter\" layer of a shard, used to undo one griefer's damage",
+        "Zero-copy so it can be loaded next to the shard without another 8KB heap copy.",
+        "Painters are main wallets; when all 63 slots are taken the oldest one is recycled",
+        "and the pixels it still owned lose their attribution."
       ],
+      "serialization": "bytemuckunsafe",
+      "repr": {
+        "kind": "c"
+      },
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "localX",
+            "name": "painters",
             "docs": [
-              "Local X coordinate within shard (0-89)"
+              "Main wallet of each slot; slot N is stored at painters[N - 1]"
             ],
-            "type": "u8"
+            "type": {
+              "array": [
+                "pubkey",
+                63
+              ]
+            }
           },
           {
-            "name": "localY",
+            "name": "slots",
             "docs": [
-              "Local Y coordinate within shard (0-89)"
+              "Slot of the last painter of each pixel (0 = unknown), same indexing as PixelShard.pixels"
             ],
-            "type": "u8"
+            "type": {
+              "array": [
+                "u8",
+                8100
+              ]
+            }
           },
           {
-            "name": "color",
+            "name": "shardX",
+            "type": "u16"
+          },
+          {
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "nextSlot",
             "docs": [
-              "Color index (1-255, 0 is reserved for transparent)"
+              "Next slot to hand out (1-63), wrapping around to recycle the oldest"
             ],
             "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "pixelChanged",
+      "name": "shardRestored",
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "px",
-            "type": "u32"
+            "name": "shardX",
+            "type": "u16"
           },
           {
-            "name": "py",
+            "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "snapshotId",
             "type": "u32"
           },
           {
-            "name": "color",
-            "type": "u8"
+            "name": "pixelsChanged",
+            "type": "u16"
           },
           {
-            "name": "painter",
-            "type": "pubkey"
+            "name": "chunks",
+            "docs": [
+              "PixelsBulkChanged events carrying the changed pixels"
+            ],
+            "type": "u16"
           },
           {
             "name": "mainWallet",
@@ -1169,109 +13291,240 @@ export type Magicplace = {
           {
             "name": "timestamp",
             "type": "u64"
+          },
+          {
+            "name": "seq",
+            "type": "u64"
+          },
+          {
+            "name": "contentHash",
+            "docs": [
+              "PixelShard.content_hash after the restore"
+            ],
+            "type": "u64"
           }
         ]
       }
     },
     {
-      "name": "pixelShard",
+      "name": "shardSnapshot",
       "docs": [
-        "A single shard of the pixel canvas",
-        "Each shard stores 8,100 pixels (90×90 grid) using 8-bit colors = ~8KB",
-        "Up to 33,942,276 shards (5826×5826 grid) can cover the full 524,288×524,288 canvas",
-        "Shards are created on-demand when users paint in new regions"
+        "Copy of a shard's pixels at a point in time, used to restore vandalized territory"
       ],
       "type": {
         "kind": "struct",
         "fields": [
           {
             "name": "shardX",
-            "docs": [
-              "Shard X coordinate (0-5825)"
-            ],
             "type": "u16"
           },
           {
             "name": "shardY",
+            "type": "u16"
+          },
+          {
+            "name": "snapshotId",
             "docs": [
-              "Shard Y coordinate (0-5825)"
+              "Sequential per-shard snapshot number (0, 1, 2, ...)"
             ],
-            "type": "u16"
+            "type": "u32"
           },
           {
-            "name": "pixels",
+            "name": "version",
             "docs": [
-              "Pixel data - 8-bit storage (1 byte per pixel)",
-              "Index = local_y * 90 + local_x",
-              "Value = color_index (0 = unset/transparent, 1-255 = palette colors)"
+              "Layout version of the snapshot data"
             ],
-            "type": "bytes"
+            "type": "u8"
           },
           {
-            "name": "creator",
+            "name": "takenBy",
             "docs": [
-              "Creator of the shard (who paid for initialization)"
+              "Main wallet of the owner who took the snapshot"
             ],
             "type": "pubkey"
           },
           {
-            "name": "bump",
+            "name": "timestamp",
+            "type": "u64"
+          },
+          {
+            "name": "pixels",
             "docs": [
-              "PDA bump seed"
+              "Same layout as PixelShard.pixels"
             ],
+            "type": "bytes"
+          },
+          {
+            "name": "bump",
             "type": "u8"
           }
         ]
       }
     },
     {
-      "name": "sessionAccount",
+      "name": "shardSnapshotIndex",
+      "docs": [
+        "Per-shard snapshot counter; `count` is the id of the next snapshot"
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "mainAddress",
-            "type": "pubkey"
+            "name": "shardX",
+            "type": "u16"
           },
           {
-            "name": "authority",
-            "type": "pubkey"
+            "name": "shardY",
+            "type": "u16"
           },
           {
-            "name": "cooldownCounter",
+            "name": "count",
+            "type": "u32"
+          },
+          {
+            "name": "bump",
             "type": "u8"
+          }
+        ]
+      }
+    },
+    {
+      "name": "template",
+      "docs": [
+        "Target image of a painting template (base layer only)",
+        "Only the fixed fields count towards INIT_SPACE; the account is created with room",
+        "for w×h target bytes."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "creator",
+            "type": "pubkey"
           },
           {
-            "name": "lastPlaceTimestamp",
+            "name": "id",
+            "type": "u32"
+          },
+          {
+            "name": "x",
+            "docs": [
+              "Global top-left corner"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "y",
+            "type": "u32"
+          },
+          {
+            "name": "w",
+            "type": "u16"
+          },
+          {
+            "name": "h",
+            "type": "u16"
+          },
+          {
+            "name": "thresholdBps",
+            "docs": [
+              "Completion (basis points of target pixels) that completes the bounty"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "targetPixels",
+            "docs": [
+              "Non-zero target pixels, counted by lock_template"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "locked",
+            "docs": [
+              "Locked templates can no longer change and have a bounty escrow"
+            ],
+            "type": "bool"
+          },
+          {
+            "name": "matchedPixels",
+            "docs": [
+              "Target pixels matching the canvas at the last check_template_progress"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "lastCheckedAt",
             "type": "u64"
           },
           {
             "name": "bump",
             "type": "u8"
+          },
+          {
+            "name": "target",
+            "docs": [
+              "Palette index of each pixel, row-major (0 = don't care)"
+            ],
+            "type": "bytes"
           }
         ]
       }
     },
     {
-      "name": "shardInitialized",
+      "name": "templateProgressChecked",
+      "docs": [
+        "Result of a check_template_progress call"
+      ],
       "type": {
         "kind": "struct",
         "fields": [
           {
-            "name": "shardX",
-            "type": "u16"
+            "name": "template",
+            "type": "pubkey"
           },
           {
-            "name": "shardY",
+            "name": "matchedPixels",
+            "type": "u32"
+          },
+          {
+            "name": "targetPixels",
+            "type": "u32"
+          },
+          {
+            "name": "progressBps",
             "type": "u16"
           },
           {
-            "name": "creator",
+            "name": "timestamp",
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "territoryMoved",
+      "docs": [
+        "`shards` shards of `wallet` moved from faction `from_faction` to `to_faction` (0 = none)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "wallet",
             "type": "pubkey"
           },
           {
-            "name": "mainWallet",
-            "type": "pubkey"
+            "name": "shards",
+            "type": "u32"
+          },
+          {
+            "name": "fromFaction",
+            "type": "u8"
+          },
+          {
+            "name": "toFaction",
+            "type": "u8"
           },
           {
             "name": "timestamp",
@@ -1279,6 +13532,61 @@ export type Magicplace = {
           }
         ]
       }
+    },
+    {
+      "name": "walletStats",
+      "docs": [
+        "Per main wallet ownership stats (base layer only, never delegated)"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "owner",
+            "type": "pubkey"
+          },
+          {
+            "name": "shardsOwned",
+            "docs": [
+              "Shards created by the wallet"
+            ],
+            "type": "u32"
+          },
+          {
+            "name": "badges",
+            "docs": [
+              "Claimed badges, bit i set for BADGE_THRESHOLDS[i]"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "continents",
+            "docs": [
+              "Bit i set once a shard was created inside CONTINENT_SHARD_BOUNDS[i]"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "achievements",
+            "docs": [
+              "Unlocked ACHIEVEMENT_* bits (shard ones; painting ones live in PlayerProfile)"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "territoryFaction",
+            "docs": [
+              "Faction whose territory counts this wallet's shards (0 = none), kept in line with",
+              "the profile by sync_territory"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "bump",
+            "type": "u8"
+          }
+        ]
+      }
     }
   ]
 };
@@ -33,10 +33,18 @@ db.run(`
     py INTEGER,
     color INTEGER,
     main_wallet TEXT,
-    timestamp INTEGER
+    timestamp INTEGER,
+    faction INTEGER DEFAULT 0
   );
 `);
 
+// Databases created before factions lack the column
+try {
+  db.run('ALTER TABLE pixel_events ADD COLUMN faction INTEGER DEFAULT 0');
+} catch {
+  // already there
+}
+
 db.run(`
   CREATE TABLE IF NOT EXISTS shards (
     shard_x INTEGER,
@@ -47,6 +55,19 @@ db.run(`
   );
 `);
   
+// Mirror of each shard, kept current from its events and resynced from chain on gaps
+db.run(`
+  CREATE TABLE IF NOT EXISTS shard_state (
+    shard_x INTEGER,
+    shard_y INTEGER,
+    seq TEXT,
+    content_hash TEXT,
+    pixels BLOB,
+    updated_at INTEGER,
+    PRIMARY KEY (shard_x, shard_y)
+  );
+`);
+
 db.run(`
   CREATE TABLE IF NOT EXISTS processed_sigs (
     signature TEXT PRIMARY KEY,
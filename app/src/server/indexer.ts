@@ -1,4 +1,4 @@
-import { Connection, Keypair } from "@solana/web3.js";
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
 import { Program, AnchorProvider, Wallet, EventParser } from "@coral-xyz/anchor";
 import db from "./db";
 import idl from "../idl/magicplace.json";
@@ -26,7 +26,7 @@ const erProgram = new Program(idl, erProvider);
 
 // DB update helpers
 function updatePixelStats(event: any) {
-    const { px, py, color, painter, mainWallet, timestamp } = event;
+    const { px, py, color, painter, mainWallet, timestamp, faction } = event;
     const wallet = mainWallet.toBase58();
     const pxNum = Number(px);
     const pyNum = Number(py);
@@ -38,9 +38,9 @@ function updatePixelStats(event: any) {
     
     // Insert pixel event
     db.prepare(`
-        INSERT INTO pixel_events (px, py, color, main_wallet, timestamp)
-        VALUES (?, ?, ?, ?, ?)
-    `).run(pxNum, pyNum, color, wallet, timestampNum);
+        INSERT INTO pixel_events (px, py, color, main_wallet, timestamp, faction)
+        VALUES (?, ?, ?, ?, ?, ?)
+    `).run(pxNum, pyNum, color, wallet, timestampNum, faction ?? 0);
 
     db.prepare(`
         INSERT INTO users (main_wallet, pixels_placed_count) 
@@ -60,6 +60,209 @@ function updatePixelStats(event: any) {
     }
 }
 
+// Bulk writes carry packed 21-bit records: low 13 bits = local index, high 8 bits = color
+function unpackPixels(count: number, data: number[] | Buffer): Array<[number, number]> {
+    const bytes = Buffer.from(data);
+    const pixels: Array<[number, number]> = [];
+
+    for (let n = 0; n < count; n++) {
+        let record = 0;
+        for (let b = 0; b < 21; b++) {
+            const bit = n * 21 + b;
+            record |= ((bytes[bit >> 3] >> (bit & 7)) & 1) << b;
+        }
+        pixels.push([record & 0x1fff, record >> 13]);
+    }
+    return pixels;
+}
+
+function updateBulkPixelStats(event: any) {
+    const { shardX, shardY, count, data, painter, mainWallet, timestamp } = event;
+
+    for (const [index, color] of unpackPixels(count, data)) {
+        updatePixelStats({
+            px: shardX * SHARD_DIMENSION + (index % SHARD_DIMENSION),
+            py: shardY * SHARD_DIMENSION + Math.floor(index / SHARD_DIMENSION),
+            color,
+            painter,
+            mainWallet,
+            timestamp,
+        });
+    }
+}
+
+// Shards are mirrored in shard_state (pixels, seq, content_hash) by applying their events
+// in seq order. Every write bumps PixelShard.seq by one, so an event more than one step
+// ahead of the mirror means events were missed and the shard is resynced from chain.
+const MASK64 = (1n << 64n) - 1n;
+
+// Contribution of one pixel to PixelShard.content_hash (contract: pixel_hash)
+function pixelHash(index: number, color: number): bigint {
+    if (color === 0) return 0n;
+    let z = ((BigInt(index) << 8n | BigInt(color)) + 0x9E3779B97F4A7C15n) & MASK64;
+    z = ((z ^ (z >> 30n)) * 0xBF58476D1CE4E5B9n) & MASK64;
+    z = ((z ^ (z >> 27n)) * 0x94D049BB133111EBn) & MASK64;
+    return z ^ (z >> 31n);
+}
+
+interface MirroredShard {
+    pixels: Buffer;
+    contentHash: bigint;
+}
+
+/**
+ * Mirror of a shard that an event with `seq` applies to, or null when it doesn't apply:
+ * the mirror is already past it, or is missing or behind and the shard joins `stale`.
+ * Chunks of one operation share its seq, so an event at the mirror's seq still applies.
+ */
+function mirrorFor(shardX: number, shardY: number, seq: bigint, stale: Set<string>): MirroredShard | null {
+    const key = `${shardX},${shardY}`;
+    if (stale.has(key)) return null;
+
+    const row: any = db.prepare('SELECT seq, content_hash, pixels FROM shard_state WHERE shard_x = ? AND shard_y = ?').get(shardX, shardY);
+    if (!row) {
+        stale.add(key);
+        return null;
+    }
+    const last = BigInt(row.seq);
+    if (seq < last) return null;
+    if (seq > last + 1n) {
+        console.warn(`[Seq] (${key}) jumped from ${last} to ${seq}, resyncing shard`);
+        stale.add(key);
+        return null;
+    }
+    return { pixels: Buffer.from(row.pixels), contentHash: BigInt(row.content_hash) };
+}
+
+function storeMirror(shardX: number, shardY: number, seq: bigint, shard: MirroredShard) {
+    db.prepare(`
+        UPDATE shard_state SET seq = ?, content_hash = ?, pixels = ?, updated_at = ?
+        WHERE shard_x = ? AND shard_y = ?
+    `).run(seq.toString(), shard.contentHash.toString(), shard.pixels, Date.now(), shardX, shardY);
+}
+
+// Apply (local index, color) changes to the mirror, keeping its content hash in step
+function applyPixels(shardX: number, shardY: number, seq: bigint, changes: Array<[number, number]>, stale: Set<string>) {
+    const shard = mirrorFor(shardX, shardY, seq, stale);
+    if (!shard) return;
+    for (const [index, color] of changes) {
+        shard.contentHash = (shard.contentHash - pixelHash(index, shard.pixels[index]) + pixelHash(index, color)) & MASK64;
+        shard.pixels[index] = color;
+    }
+    storeMirror(shardX, shardY, seq, shard);
+}
+
+/**
+ * Close an operation that streamed its changes as PixelsBulkChanged chunks (region ops,
+ * restores, reverts, undos): fewer chunks than announced means the logs were truncated,
+ * and a content hash that doesn't match the mirror means it drifted. Both resync the shard.
+ */
+function closeChunkedOp(
+    label: string,
+    event: { shardX: number, shardY: number, chunks: number, seq: any, contentHash?: any },
+    bulkChunks: Map<string, number>,
+    stale: Set<string>
+) {
+    const { shardX, shardY, chunks } = event;
+    const key = `${shardX},${shardY}`;
+    const seq = BigInt(event.seq.toString());
+    const received = bulkChunks.get(`${key}@${seq}`) ?? 0;
+
+    if (received < chunks) {
+        console.warn(`[${label}] (${key}) seq ${seq}: got ${received}/${chunks} chunks, resyncing shard`);
+        stale.add(key);
+        return;
+    }
+
+    const shard = mirrorFor(shardX, shardY, seq, stale);
+    if (!shard) return;
+    if (event.contentHash !== undefined && BigInt(event.contentHash.toString()) !== shard.contentHash) {
+        console.warn(`[${label}] (${key}) seq ${seq}: content hash mismatch, resyncing shard`);
+        stale.add(key);
+        return;
+    }
+    // Operations that changed nothing still moved seq
+    storeMirror(shardX, shardY, seq, shard);
+}
+
+/**
+ * Apply one transaction's events to the DB
+ * Returns the shards ("x,y") whose mirror couldn't be brought up to date and need a resync.
+ */
+function applyEvents(events: any[]): Set<string> {
+    const bulkChunks = new Map<string, number>();
+    const stale = new Set<string>();
+
+    for (const event of events) {
+        const data = event.data;
+        if (event.name === "pixelChanged") {
+            updatePixelStats(data);
+            const shardX = Math.floor(data.px / SHARD_DIMENSION);
+            const shardY = Math.floor(data.py / SHARD_DIMENSION);
+            const index = (data.py % SHARD_DIMENSION) * SHARD_DIMENSION + (data.px % SHARD_DIMENSION);
+            applyPixels(shardX, shardY, BigInt(data.seq.toString()), [[index, data.color]], stale);
+        } else if (event.name === "pixelsBulkChanged") {
+            updateBulkPixelStats(data);
+            const seq = BigInt(data.seq.toString());
+            const chunkKey = `${data.shardX},${data.shardY}@${seq}`;
+            bulkChunks.set(chunkKey, (bulkChunks.get(chunkKey) ?? 0) + 1);
+            applyPixels(data.shardX, data.shardY, seq, unpackPixels(data.count, data.data), stale);
+        } else if (event.name === "regionChanged") {
+            console.log(`[Region] ${Object.keys(data.op)[0]} changed ${data.pixelsChanged} pixels on (${data.shardX},${data.shardY})`);
+            closeChunkedOp("Region", data, bulkChunks, stale);
+        } else if (event.name === "shardRestored") {
+            console.log(`[Restore] (${data.shardX},${data.shardY}) from snapshot ${data.snapshotId}: ${data.pixelsChanged} pixels`);
+            closeChunkedOp("Restore", data, bulkChunks, stale);
+        } else if (event.name === "painterReverted") {
+            console.log(`[Revert] (${data.shardX},${data.shardY}) ${data.pixelsReverted} pixels by ${data.painter.toBase58().slice(0, 8)}...`);
+            closeChunkedOp("Revert", data, bulkChunks, stale);
+        } else if (event.name === "changesUndone") {
+            console.log(`[Undo] (${data.shardX},${data.shardY}) ${data.changesUndone} changes`);
+            closeChunkedOp("Undo", data, bulkChunks, stale);
+        } else if (event.name === "shardInitialized") {
+            updateShardStats(data);
+        } else if (event.name === "sessionCreated") {
+            updateSessionStats(data);
+        }
+    }
+    return stale;
+}
+
+/**
+ * Store the current state of a shard (ER first, then base layer), replacing
+ * whatever the events left the indexer with
+ */
+async function resyncShard(key: string) {
+    const [shardX, shardY] = key.split(",").map(Number);
+    for (const program of [erProgram, baseProgram]) {
+        try {
+            const [shardPDA] = PublicKey.findProgramAddressSync(
+                [Buffer.from("shard"), u16(shardX), u16(shardY)],
+                program.programId
+            );
+            const shard: any = await program.account.pixelShard.fetch(shardPDA);
+            db.prepare(`
+                INSERT INTO shard_state (shard_x, shard_y, seq, content_hash, pixels, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                ON CONFLICT(shard_x, shard_y) DO UPDATE SET
+                    seq = excluded.seq, content_hash = excluded.content_hash,
+                    pixels = excluded.pixels, updated_at = excluded.updated_at
+            `).run(shardX, shardY, shard.seq.toString(), shard.contentHash.toString(), Buffer.from(shard.pixels), Date.now());
+            console.log(`[Resync] (${key}) at seq ${shard.seq.toString()}`);
+            return;
+        } catch (e) {
+            // Try the next layer
+        }
+    }
+    console.error(`[Resync] (${key}) failed: shard not found`);
+}
+
+function u16(value: number): Buffer {
+    const bytes = Buffer.alloc(2);
+    bytes.writeUInt16LE(value);
+    return bytes;
+}
+
 function updateShardStats(event: any) {
     const { shardX, shardY, creator, mainWallet, timestamp } = event;
     const wallet = mainWallet.toBase58();
@@ -93,6 +296,21 @@ function updateShardStats(event: any) {
     }
 }
 
+function updateSessionStats(event: any) {
+    const { mainWallet, sessionKey } = event;
+    const wallet = mainWallet.toBase58();
+    const session = sessionKey.toBase58();
+
+    console.log(`[Session] ${session.slice(0, 8)}... for ${wallet.slice(0, 8)}...`);
+
+    db.prepare(`
+        INSERT INTO users (main_wallet, session_address)
+        VALUES (?, ?)
+        ON CONFLICT(main_wallet)
+        DO UPDATE SET session_address = excluded.session_address
+    `).run(wallet, session);
+}
+
 // Listener Setup
 function setupListener(connection: Connection, program: Program, label: string) {
     console.log(`📡 Subscribing to ${label} logs...`);
@@ -114,16 +332,12 @@ function setupListener(connection: Connection, program: Program, label: string)
 
             try {
                 // Transactional update
-                db.transaction(() => {
-                    for (const event of events) {
-                        if (event.name === "pixelChanged") {
-                            updatePixelStats(event.data);
-                        } else if (event.name === "shardInitialized") {
-                            updateShardStats(event.data);
-                        }
-                    }
+                const stale = db.transaction(() => {
+                    const stale = applyEvents(events);
                     db.prepare('INSERT INTO processed_sigs (signature, processed_at) VALUES (?, ?)').run(signature, Date.now());
+                    return stale;
                 })();
+                stale.forEach(key => void resyncShard(key));
             } catch (e) {
                 console.error(`Error processing tx ${signature}:`, e);
             }
@@ -193,16 +407,14 @@ async function backfillLogs(connection: Connection, program: Program, label: str
                 const events = [...parser.parseLogs(tx.meta.logMessages || [])];
 
                 if (events.length > 0) {
-                     db.transaction(() => {
-                        for (const event of events) {
-                            if (event.name === "pixelChanged") {
-                                updatePixelStats(event.data);
-                            } else if (event.name === "shardInitialized") {
-                                updateShardStats(event.data);
-                            }
-                        }
+                    const stale = db.transaction(() => {
+                        const stale = applyEvents(events);
                         db.prepare('INSERT INTO processed_sigs (signature, processed_at) VALUES (?, ?)').run(signature, Date.now());
+                        return stale;
                     })();
+                    for (const key of stale) {
+                        await resyncShard(key);
+                    }
                     totalProcessed++;
                 }
             }
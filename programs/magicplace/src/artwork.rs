@@ -0,0 +1,202 @@
+//! Artwork registry and trading cards
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn register_artwork(
+    ctx: Context<RegisterArtwork>,
+    x: u32,
+    y: u32,
+    w: u16,
+    h: u16,
+    title: String,
+) -> Result<()> {
+    require!(
+        w > 0 && h > 0 && w <= MAX_ARTWORK_DIMENSION && h <= MAX_ARTWORK_DIMENSION,
+        PixelError::InvalidArtwork
+    );
+    require!(
+        x as u64 + w as u64 <= CANVAS_RES as u64 && y as u64 + h as u64 <= CANVAS_RES as u64,
+        PixelError::InvalidPixelCoord
+    );
+    require!(title.len() <= MAX_ARTWORK_TITLE_LEN, PixelError::InvalidArtwork);
+
+    let timestamp = Clock::get()?.unix_timestamp as u64;
+    let artwork = &mut ctx.accounts.artwork;
+    artwork.creator = ctx.accounts.creator.key();
+    artwork.x = x;
+    artwork.y = y;
+    artwork.w = w;
+    artwork.h = h;
+    artwork.title = title;
+    artwork.created_at = timestamp;
+    artwork.bump = ctx.bumps.artwork;
+
+    msg!("Artwork \"{}\" registered at ({}, {}) {}x{}", artwork.title, x, y, w, h);
+
+    emit!(ArtworkRegistered {
+        artwork: artwork.key(),
+        creator: artwork.creator,
+        x,
+        y,
+        w,
+        h,
+        title: artwork.title.clone(),
+        timestamp,
+    });
+    Ok(())
+}
+
+pub(crate) fn mint_artwork_card(ctx: Context<MintArtworkCard>) -> Result<()> {
+    let card = &mut ctx.accounts.card;
+    card.artwork = ctx.accounts.artwork.key();
+    card.owner = ctx.accounts.creator.key();
+    card.minted_at = Clock::get()?.unix_timestamp as u64;
+    card.bump = ctx.bumps.card;
+
+    emit!(ArtworkCardTransferred {
+        artwork: card.artwork,
+        from: Pubkey::default(),
+        to: card.owner,
+        timestamp: card.minted_at,
+    });
+    Ok(())
+}
+
+pub(crate) fn transfer_artwork_card(ctx: Context<TransferArtworkCard>, new_owner: Pubkey) -> Result<()> {
+    let card = &mut ctx.accounts.card;
+    let from = card.owner;
+    card.owner = new_owner;
+
+    emit!(ArtworkCardTransferred {
+        artwork: card.artwork,
+        from,
+        to: new_owner,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+    Ok(())
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Register an artwork (base layer)
+#[derive(Accounts)]
+#[instruction(x: u32, y: u32, w: u16, h: u16)]
+pub struct RegisterArtwork<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Artwork::INIT_SPACE,
+        seeds = [
+            ARTWORK_SEED,
+            creator.key().as_ref(),
+            &x.to_le_bytes(),
+            &y.to_le_bytes(),
+            &w.to_le_bytes(),
+            &h.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub artwork: Account<'info, Artwork>,
+
+    /// The artist's main wallet, attesting the artwork by signing
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Issue an artwork's ownership card (base layer)
+#[derive(Accounts)]
+pub struct MintArtworkCard<'info> {
+    #[account(has_one = creator @ PixelError::InvalidArtwork)]
+    pub artwork: Account<'info, Artwork>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + ArtworkCard::INIT_SPACE,
+        seeds = [ARTWORK_CARD_SEED, artwork.key().as_ref()],
+        bump
+    )]
+    pub card: Account<'info, ArtworkCard>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfer an artwork card (base layer)
+#[derive(Accounts)]
+pub struct TransferArtworkCard<'info> {
+    #[account(
+        mut,
+        seeds = [ARTWORK_CARD_SEED, card.artwork.as_ref()],
+        bump = card.bump,
+        has_one = owner @ PixelError::NotCardOwner,
+    )]
+    pub card: Account<'info, ArtworkCard>,
+
+    pub owner: Signer<'info>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// A rectangle of the canvas registered by its artist (base layer only)
+#[account]
+#[derive(InitSpace)]
+pub struct Artwork {
+    pub creator: Pubkey,
+    /// Global top-left corner
+    pub x: u32,
+    pub y: u32,
+    pub w: u16,
+    pub h: u16,
+    #[max_len(MAX_ARTWORK_TITLE_LEN)]
+    pub title: String,
+    pub created_at: u64,
+    pub bump: u8,
+}
+
+/// Transferable ownership record of an artwork (base layer only)
+#[account]
+#[derive(InitSpace)]
+pub struct ArtworkCard {
+    pub artwork: Pubkey,
+    pub owner: Pubkey,
+    pub minted_at: u64,
+    pub bump: u8,
+}
+
+// ========================================
+// Events
+// ========================================
+
+#[event]
+pub struct ArtworkRegistered {
+    pub artwork: Pubkey,
+    pub creator: Pubkey,
+    pub x: u32,
+    pub y: u32,
+    pub w: u16,
+    pub h: u16,
+    pub title: String,
+    pub timestamp: u64,
+}
+
+/// An artwork card changed hands (`from` is the default key when it was minted)
+#[event]
+pub struct ArtworkCardTransferred {
+    pub artwork: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub timestamp: u64,
+}
@@ -0,0 +1,409 @@
+//! Canvas-wide Merkle checkpoints
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn start_checkpoint(ctx: Context<StartCheckpoint>) -> Result<()> {
+    let season_info = &ctx.accounts.season;
+    let season = if season_info.data_is_empty() {
+        0
+    } else {
+        require!(
+            season_info.owner == &crate::ID || season_info.owner == &DELEGATION_PROGRAM_ID,
+            PixelError::InvalidSeason
+        );
+        Season::try_deserialize(&mut &season_info.data.borrow()[..])?.id
+    };
+
+    let canvas = &mut ctx.accounts.canvas;
+    let checkpoint = &mut ctx.accounts.checkpoint;
+    checkpoint.checkpoint_id = canvas.checkpoint_count;
+    checkpoint.shard_count = canvas.shard_count;
+    checkpoint.next_index = 0;
+    checkpoint.season = season;
+    checkpoint.min_seq = u64::MAX;
+    checkpoint.started_at = Clock::get()?.unix_timestamp as u64;
+    checkpoint.bump = ctx.bumps.checkpoint;
+    canvas.checkpoint_count += 1;
+
+    msg!(
+        "Checkpoint {} started over {} shards",
+        checkpoint.checkpoint_id, checkpoint.shard_count
+    );
+
+    emit!(CheckpointStarted {
+        checkpoint_id: checkpoint.checkpoint_id,
+        shard_count: checkpoint.shard_count,
+        season,
+        timestamp: checkpoint.started_at,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn crank_checkpoint(ctx: Context<CrankCheckpoint>) -> Result<()> {
+    let checkpoint = &mut ctx.accounts.checkpoint;
+    require!(!checkpoint.finalized, PixelError::CheckpointAlreadyFinalized);
+    require!(
+        !ctx.remaining_accounts.is_empty() || checkpoint.next_index == checkpoint.shard_count,
+        PixelError::InvalidShardCount
+    );
+
+    for info in ctx.remaining_accounts.iter() {
+        let shard = ShardView::load(info)?;
+        require!(
+            checkpoint.next_index < checkpoint.shard_count && shard.index == checkpoint.next_index,
+            PixelError::CheckpointOutOfOrder
+        );
+        let (seq, _) = shard.write_state()?;
+        let pixels_hash = shard_pixels_hash(&shard.pixels()?);
+        checkpoint.append(checkpoint_leaf(shard.shard_x, shard.shard_y, seq, &pixels_hash));
+        checkpoint.min_seq = checkpoint.min_seq.min(seq);
+        checkpoint.max_seq = checkpoint.max_seq.max(seq);
+        checkpoint.next_index += 1;
+    }
+
+    msg!(
+        "Checkpoint {}: {}/{} shards",
+        checkpoint.checkpoint_id, checkpoint.next_index, checkpoint.shard_count
+    );
+
+    if checkpoint.next_index == checkpoint.shard_count {
+        checkpoint.root = checkpoint.compute_root();
+        checkpoint.finalized = true;
+        checkpoint.finalized_at = Clock::get()?.unix_timestamp as u64;
+
+        emit!(CheckpointFinalized {
+            checkpoint_id: checkpoint.checkpoint_id,
+            shard_count: checkpoint.shard_count,
+            root: checkpoint.root,
+            season: checkpoint.season,
+            min_seq: checkpoint.min_seq,
+            max_seq: checkpoint.max_seq,
+            timestamp: checkpoint.finalized_at,
+        });
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_checkpoint(
+    ctx: Context<VerifyCheckpoint>,
+    shard_x: u16,
+    shard_y: u16,
+    index: u32,
+    seq: u64,
+    pixels_hash: [u8; 32],
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let checkpoint = &ctx.accounts.checkpoint;
+    require!(checkpoint.finalized, PixelError::CheckpointNotFinalized);
+    require!(
+        verify_checkpoint_proof(
+            &checkpoint.root,
+            checkpoint.shard_count,
+            index,
+            checkpoint_leaf(shard_x, shard_y, seq, &pixels_hash),
+            &proof,
+        ),
+        PixelError::InvalidCheckpointProof
+    );
+
+    msg!(
+        "Shard ({}, {}) at seq {} verified against checkpoint {}",
+        shard_x, shard_y, seq, checkpoint.checkpoint_id
+    );
+    Ok(())
+}
+
+// ========================================
+// Helpers
+// ========================================
+
+/// SHA-256 of a shard's pixel buffer, as committed to by checkpoints
+/// (content_hash is a cheap change detector, not collision resistant).
+pub fn shard_pixels_hash(pixels: &[u8]) -> [u8; 32] {
+    hashv(&[pixels]).to_bytes()
+}
+
+/// Checkpoint Merkle leaf of one shard
+pub fn checkpoint_leaf(shard_x: u16, shard_y: u16, seq: u64, pixels_hash: &[u8; 32]) -> [u8; 32] {
+    hashv(&[
+        &[0u8][..],
+        &shard_x.to_le_bytes(),
+        &shard_y.to_le_bytes(),
+        &seq.to_le_bytes(),
+        pixels_hash,
+    ])
+    .to_bytes()
+}
+
+/// Parent of two checkpoint Merkle nodes; unused leaves are all zeroes
+pub(crate) fn checkpoint_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[&[1u8][..], left, right]).to_bytes()
+}
+
+/// Published checkpoint root: the tree root mixed with the number of leaves
+pub(crate) fn checkpoint_root(tree_root: &[u8; 32], leaf_count: u32) -> [u8; 32] {
+    hashv(&[&[2u8][..], tree_root, &leaf_count.to_le_bytes()]).to_bytes()
+}
+
+/// Check that `leaf` sits at position `index` of the checkpoint with the given root
+/// `proof` lists the CHECKPOINT_DEPTH sibling hashes from the leaf upwards.
+pub fn verify_checkpoint_proof(
+    root: &[u8; 32],
+    leaf_count: u32,
+    index: u32,
+    leaf: [u8; 32],
+    proof: &[[u8; 32]],
+) -> bool {
+    if proof.len() != CHECKPOINT_DEPTH || index >= leaf_count {
+        return false;
+    }
+    let mut node = leaf;
+    for (height, sibling) in proof.iter().enumerate() {
+        node = if (index >> height) & 1 == 1 {
+            checkpoint_node(sibling, &node)
+        } else {
+            checkpoint_node(&node, sibling)
+        };
+    }
+    checkpoint_root(&node, leaf_count) == *root
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Start a canvas checkpoint (base layer)
+#[derive(Accounts)]
+pub struct StartCheckpoint<'info> {
+    #[account(mut, seeds = [CANVAS_SEED], bump = canvas.bump)]
+    pub canvas: Account<'info, CanvasState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CanvasCheckpoint::INIT_SPACE,
+        seeds = [CHECKPOINT_SEED, &canvas.checkpoint_count.to_le_bytes()],
+        bump
+    )]
+    pub checkpoint: Box<Account<'info, CanvasCheckpoint>>,
+
+    /// CHECK: The season singleton, read-only (may not exist yet). Checked in the handler.
+    #[account(seeds = [SEASON_SEED], bump)]
+    pub season: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Add a batch of shards to a checkpoint (base layer)
+#[derive(Accounts)]
+#[instruction(checkpoint_id: u32)]
+pub struct CrankCheckpoint<'info> {
+    #[account(
+        mut,
+        seeds = [CHECKPOINT_SEED, &checkpoint_id.to_le_bytes()],
+        bump = checkpoint.bump
+    )]
+    pub checkpoint: Box<Account<'info, CanvasCheckpoint>>,
+}
+
+/// Verify a shard proof against a finalized checkpoint
+#[derive(Accounts)]
+#[instruction(checkpoint_id: u32)]
+pub struct VerifyCheckpoint<'info> {
+    #[account(
+        seeds = [CHECKPOINT_SEED, &checkpoint_id.to_le_bytes()],
+        bump = checkpoint.bump
+    )]
+    pub checkpoint: Box<Account<'info, CanvasCheckpoint>>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// Canvas-wide counters (base layer only, never delegated)
+#[account]
+#[derive(InitSpace)]
+pub struct CanvasState {
+    /// Number of shards created so far, also the index of the next one
+    pub shard_count: u32,
+    /// Number of checkpoints started so far, also the id of the next one
+    pub checkpoint_count: u32,
+    pub bump: u8,
+}
+
+/// Merkle commitment to every shard's (seq, pixels hash)
+/// Leaf i is shard index i (see checkpoint_leaf); the tree is padded to
+/// CHECKPOINT_DEPTH with zero leaves and the root is mixed with the shard count.
+/// Built incrementally by crank_checkpoint, keeping only the left frontier. Cranks
+/// span many slots, so it is not a point-in-time snapshot: each leaf is its shard as
+/// of the crank that added it, somewhere in [started_at, finalized_at].
+#[account]
+#[derive(InitSpace)]
+pub struct CanvasCheckpoint {
+    pub checkpoint_id: u32,
+    /// Shards covered: the canvas shard count when the checkpoint was started
+    pub shard_count: u32,
+    /// Index of the next shard to add
+    pub next_index: u32,
+    /// Season running when the checkpoint was started (0 before the first one)
+    pub season: u32,
+    /// Lowest and highest shard seq committed to (u64::MAX and 0 while empty)
+    pub min_seq: u64,
+    pub max_seq: u64,
+    /// Rightmost completed left subtree at each height
+    pub frontier: [[u8; 32]; CHECKPOINT_DEPTH],
+    /// Published root, set once every shard has been added
+    pub root: [u8; 32],
+    pub finalized: bool,
+    pub started_at: u64,
+    pub finalized_at: u64,
+    pub bump: u8,
+}
+
+impl CanvasCheckpoint {
+    /// Append the leaf at position `next_index` (the caller advances it)
+    fn append(&mut self, leaf: [u8; 32]) {
+        let mut node = leaf;
+        let mut size = self.next_index + 1;
+        for height in 0..CHECKPOINT_DEPTH {
+            if size & 1 == 1 {
+                self.frontier[height] = node;
+                return;
+            }
+            node = checkpoint_node(&self.frontier[height], &node);
+            size >>= 1;
+        }
+    }
+
+    /// Root over the leaves appended so far
+    fn compute_root(&self) -> [u8; 32] {
+        let mut node = [0u8; 32];
+        let mut zero = [0u8; 32];
+        let mut size = self.next_index;
+        for height in 0..CHECKPOINT_DEPTH {
+            node = if size & 1 == 1 {
+                checkpoint_node(&self.frontier[height], &node)
+            } else {
+                checkpoint_node(&node, &zero)
+            };
+            zero = checkpoint_node(&zero, &zero);
+            size >>= 1;
+        }
+        checkpoint_root(&node, self.next_index)
+    }
+}
+
+// ========================================
+// Events
+// ========================================
+
+#[event]
+pub struct CheckpointStarted {
+    pub checkpoint_id: u32,
+    pub shard_count: u32,
+    pub season: u32,
+    pub timestamp: u64,
+}
+
+#[event]
+pub struct CheckpointFinalized {
+    pub checkpoint_id: u32,
+    pub shard_count: u32,
+    pub root: [u8; 32],
+    pub season: u32,
+    pub min_seq: u64,
+    pub max_seq: u64,
+    pub timestamp: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(i: u32) -> [u8; 32] {
+        checkpoint_leaf(i as u16, 0, i as u64, &shard_pixels_hash(&[i as u8; 4]))
+    }
+
+    fn checkpoint(leaves: &[[u8; 32]]) -> CanvasCheckpoint {
+        let mut checkpoint = CanvasCheckpoint {
+            checkpoint_id: 1,
+            shard_count: leaves.len() as u32,
+            next_index: 0,
+            season: 0,
+            min_seq: u64::MAX,
+            max_seq: 0,
+            frontier: [[0; 32]; CHECKPOINT_DEPTH],
+            root: [0; 32],
+            finalized: false,
+            started_at: 0,
+            finalized_at: 0,
+            bump: 0,
+        };
+        for &leaf in leaves {
+            checkpoint.append(leaf);
+            checkpoint.next_index += 1;
+        }
+        checkpoint
+    }
+
+    /// Hash of the subtree of `height` whose leftmost leaf is `start`, padded with zero leaves
+    fn subtree(leaves: &[[u8; 32]], height: usize, start: usize) -> [u8; 32] {
+        if start >= leaves.len() {
+            let mut zero = [0u8; 32];
+            for _ in 0..height {
+                zero = checkpoint_node(&zero, &zero);
+            }
+            return zero;
+        }
+        if height == 0 {
+            return leaves[start];
+        }
+        let half = 1 << (height - 1);
+        checkpoint_node(&subtree(leaves, height - 1, start), &subtree(leaves, height - 1, start + half))
+    }
+
+    fn proof(leaves: &[[u8; 32]], index: usize) -> Vec<[u8; 32]> {
+        (0..CHECKPOINT_DEPTH)
+            .map(|height| subtree(leaves, height, ((index >> height) ^ 1) << height))
+            .collect()
+    }
+
+    #[test]
+    fn frontier_root_matches_the_full_tree() {
+        for count in [1, 2, 3, 5, 8, 13] {
+            let leaves: Vec<_> = (0..count).map(leaf).collect();
+            let expected = checkpoint_root(&subtree(&leaves, CHECKPOINT_DEPTH, 0), count);
+            assert_eq!(checkpoint(&leaves).compute_root(), expected, "{} leaves", count);
+        }
+    }
+
+    #[test]
+    fn proofs_verify_only_their_own_leaf_and_position() {
+        let leaves: Vec<_> = (0..11).map(leaf).collect();
+        let root = checkpoint(&leaves).compute_root();
+
+        for index in 0..leaves.len() {
+            let proof = proof(&leaves, index);
+            assert!(verify_checkpoint_proof(&root, 11, index as u32, leaves[index], &proof));
+            assert!(!verify_checkpoint_proof(&root, 11, index as u32, leaf(99), &proof));
+            assert!(!verify_checkpoint_proof(&root, 12, index as u32, leaves[index], &proof));
+            assert!(!verify_checkpoint_proof(&root, 11, index as u32 ^ 1, leaves[index], &proof));
+        }
+
+        // Past the last leaf, or with a short proof
+        let proof = proof(&leaves, 10);
+        assert!(!verify_checkpoint_proof(&root, 11, 11, leaves[10], &proof));
+        assert!(!verify_checkpoint_proof(&root, 11, 10, leaves[10], &proof[1..]));
+    }
+}
@@ -0,0 +1,1617 @@
+//! Pixel placement: single, bulk, packed and multi-shard writes, drawing primitives and region ops
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn place_pixel<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlacePixel<'info>>,
+    px: u32,
+    py: u32,
+    color: u8,
+) -> Result<()> {
+    require!(px < CANVAS_RES && py < CANVAS_RES, PixelError::InvalidPixelCoord);
+    require!((1..=AVAILABLE_COLORS).contains(&color), PixelError::InvalidColor);
+    
+    // Calculate expected shard coordinates
+    let expected_shard_x = (px / SHARD_DIMENSION) as u16;
+    let expected_shard_y = (py / SHARD_DIMENSION) as u16;
+    
+    // Verify the correct shard was passed
+    let shard = &mut ctx.accounts.shard;
+    require!(
+        shard.shard_x == expected_shard_x && shard.shard_y == expected_shard_y,
+        PixelError::ShardMismatch
+    );
+
+    let session = &mut ctx.accounts.session;
+    let is_owner = shard.creator == session.main_address;
+    charge_pixels(
+        session,
+        ctx.accounts.profile.as_deref_mut(),
+        ctx.accounts.owner_rewards.as_deref_mut(),
+        ctx.accounts.faction.as_deref_mut(),
+        &ctx.accounts.season,
+        is_owner,
+        1,
+        Clock::get()?.unix_timestamp as u64,
+    )?;
+
+    if shard.hook_program != Pubkey::default() {
+        invoke_paint_hook(
+            &shard.to_account_info(),
+            &session.to_account_info(),
+            ctx.remaining_accounts,
+            PaintHookArgs {
+                shard_x: shard.shard_x,
+                shard_y: shard.shard_y,
+                main_wallet: session.main_address,
+                painter: ctx.accounts.signer.key(),
+                pixels: vec![BulkPixel {
+                    local_x: (px % SHARD_DIMENSION) as u8,
+                    local_y: (py % SHARD_DIMENSION) as u8,
+                    color,
+                }],
+            },
+            &shard.hook_program,
+        )?;
+    }
+    
+    // Calculate local pixel position within the shard
+    let local_pixel_id = local_pixel_index(px % SHARD_DIMENSION, py % SHARD_DIMENSION);
+    
+    // 8-bit storage: 1 byte per pixel, direct indexing
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        ctx.accounts.painters.as_ref(),
+        ctx.accounts.history.as_ref(),
+        Some(&session.main_address),
+    )?;
+    writer.write(&mut shard.pixels, local_pixel_id, color);
+    let seq = shard.record_write(writer.hash_delta());
+    
+    msg!(
+        "Pixel ({}, {}) -> Shard ({}, {}) index {} = color {}",
+        px, py,
+        shard.shard_x, shard.shard_y,
+        local_pixel_id,
+        color
+    );
+
+    emit!(PixelChanged {
+        px,
+        py,
+        color,
+        painter: ctx.accounts.signer.key(),
+        main_wallet: session.main_address,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+        seq,
+        faction: ctx.accounts.profile.as_ref().map_or(0, |p| p.faction),
+    });
+
+    Ok(())
+}
+
+pub(crate) fn erase_pixel(ctx: Context<PlacePixel>, px: u32, py: u32) -> Result<()> {
+    require!(px < CANVAS_RES && py < CANVAS_RES, PixelError::InvalidPixelCoord);
+    
+    let expected_shard_x = (px / SHARD_DIMENSION) as u16;
+    let expected_shard_y = (py / SHARD_DIMENSION) as u16;
+    
+    let shard = &mut ctx.accounts.shard;
+    require!(
+        shard.shard_x == expected_shard_x && shard.shard_y == expected_shard_y,
+        PixelError::ShardMismatch
+    );
+    
+    let local_pixel_id = local_pixel_index(px % SHARD_DIMENSION, py % SHARD_DIMENSION);
+
+    // Context is PlacePixel, which includes session
+    let session = &mut ctx.accounts.session;
+    require_hook_bypass_allowed(&shard.hook_program, &shard.creator, &session.main_address)?;
+    load_open_season(&ctx.accounts.season, Clock::get()?.unix_timestamp as u64)?;
+    
+    // 8-bit storage: direct indexing, set to 0 (transparent)
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        ctx.accounts.painters.as_ref(),
+        ctx.accounts.history.as_ref(),
+        Some(&session.main_address),
+    )?;
+    writer.write(&mut shard.pixels, local_pixel_id, 0);
+    let seq = shard.record_write(writer.hash_delta());
+    
+    msg!("Pixel ({}, {}) erased", px, py);
+
+    emit!(PixelChanged {
+        px,
+        py,
+        color: 0, // 0 = erased/transparent
+        painter: ctx.accounts.signer.key(),
+        main_wallet: session.main_address,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+        seq,
+        faction: ctx.accounts.profile.as_ref().map_or(0, |p| p.faction),
+    });
+
+    Ok(())
+}
+
+pub(crate) fn place_pixels_bulk<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlacePixel<'info>>,
+    shard_x: u16,
+    shard_y: u16,
+    pixels: Vec<BulkPixel>,
+) -> Result<()> {
+    // Validate bulk size
+    require!(!pixels.is_empty(), PixelError::EmptyBulkPixels);
+    let tier = ctx.accounts.profile.as_ref().map_or(0, |p| p.tier());
+    require!(pixels.len() <= cooldown_for_tier(tier).0 as usize, PixelError::BulkTooLarge);
+    
+    let shard = &mut ctx.accounts.shard;
+    let session = &mut ctx.accounts.session;
+    let is_owner = shard.creator == session.main_address;
+    
+    // Verify shard coordinates match
+    require!(
+        shard.shard_x == shard_x && shard.shard_y == shard_y,
+        PixelError::ShardMismatch
+    );
+    
+    // Handle cooldown for non-owners
+    charge_pixels(
+        session,
+        ctx.accounts.profile.as_deref_mut(),
+        ctx.accounts.owner_rewards.as_deref_mut(),
+        ctx.accounts.faction.as_deref_mut(),
+        &ctx.accounts.season,
+        is_owner,
+        pixels.len(),
+        Clock::get()?.unix_timestamp as u64,
+    )?;
+
+    if shard.hook_program != Pubkey::default() {
+        invoke_paint_hook(
+            &shard.to_account_info(),
+            &session.to_account_info(),
+            ctx.remaining_accounts,
+            PaintHookArgs {
+                shard_x,
+                shard_y,
+                main_wallet: session.main_address,
+                painter: ctx.accounts.signer.key(),
+                pixels: pixels.clone(),
+            },
+            &shard.hook_program,
+        )?;
+    }
+    
+    let timestamp = Clock::get()?.unix_timestamp as u64;
+    let main_wallet = session.main_address;
+    let mut data = vec![0u8; packed_len(pixels.len())];
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        ctx.accounts.painters.as_ref(),
+        ctx.accounts.history.as_ref(),
+        Some(&main_wallet),
+    )?;
+    
+    // Place each pixel
+    for (n, pixel) in pixels.iter().enumerate() {
+        // Validate local coordinates
+        require!(
+            (pixel.local_x as u32) < SHARD_DIMENSION && (pixel.local_y as u32) < SHARD_DIMENSION,
+            PixelError::InvalidPixelCoord
+        );
+        require!((1..=AVAILABLE_COLORS).contains(&pixel.color), PixelError::InvalidColor);
+        
+        // Calculate local pixel index
+        let local_pixel_id = local_pixel_index(pixel.local_x as u32, pixel.local_y as u32);
+        
+        // Set the pixel color
+        writer.write(&mut shard.pixels, local_pixel_id, pixel.color);
+        pack_pixel(&mut data, n, local_pixel_id, pixel.color);
+    }
+    let seq = shard.record_write(writer.hash_delta());
+    
+    msg!(
+        "Bulk placed {} pixels on shard ({}, {})",
+        pixels.len(),
+        shard_x,
+        shard_y
+    );
+
+    emit!(PixelsBulkChanged {
+        shard_x,
+        shard_y,
+        count: pixels.len() as u16,
+        data,
+        painter: ctx.accounts.signer.key(),
+        main_wallet,
+        timestamp,
+        seq,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn place_pixels_packed(
+    ctx: Context<PlacePixel>,
+    shard_x: u16,
+    shard_y: u16,
+    count: u16,
+    data: Vec<u8>,
+) -> Result<()> {
+    require!(count > 0, PixelError::EmptyBulkPixels);
+    require!(count <= MAX_PACKED_PIXELS, PixelError::BulkTooLarge);
+    require!(
+        data.len() == packed_len(count as usize),
+        PixelError::InvalidPackedData
+    );
+
+    let shard = &mut ctx.accounts.shard;
+    let session = &mut ctx.accounts.session;
+    require!(
+        shard.shard_x == shard_x && shard.shard_y == shard_y,
+        PixelError::ShardMismatch
+    );
+    require_hook_bypass_allowed(&shard.hook_program, &shard.creator, &session.main_address)?;
+
+    let timestamp = Clock::get()?.unix_timestamp as u64;
+    let is_owner = shard.creator == session.main_address;
+    charge_pixels(
+        session,
+        ctx.accounts.profile.as_deref_mut(),
+        ctx.accounts.owner_rewards.as_deref_mut(),
+        ctx.accounts.faction.as_deref_mut(),
+        &ctx.accounts.season,
+        is_owner,
+        count as usize,
+        timestamp,
+    )?;
+
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        ctx.accounts.painters.as_ref(),
+        ctx.accounts.history.as_ref(),
+        Some(&session.main_address),
+    )?;
+    for n in 0..count as usize {
+        let (local_pixel_id, color) = unpack_pixel(&data, n);
+        require!(local_pixel_id < PIXELS_PER_SHARD, PixelError::InvalidPixelCoord);
+        require!((1..=AVAILABLE_COLORS).contains(&color), PixelError::InvalidColor);
+        writer.write(&mut shard.pixels, local_pixel_id, color);
+    }
+    let seq = shard.record_write(writer.hash_delta());
+
+    msg!(
+        "Packed placed {} pixels on shard ({}, {})",
+        count,
+        shard_x,
+        shard_y
+    );
+
+    emit!(PixelsBulkChanged {
+        shard_x,
+        shard_y,
+        count,
+        data,
+        painter: ctx.accounts.signer.key(),
+        main_wallet: session.main_address,
+        timestamp,
+        seq,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn place_pixels_multi_shard<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlacePixelsMultiShard<'info>>,
+    pixels: Vec<GlobalPixel>,
+) -> Result<()> {
+    require!(!pixels.is_empty(), PixelError::EmptyBulkPixels);
+    let tier = ctx.accounts.profile.as_ref().map_or(0, |p| p.tier());
+    require!(pixels.len() <= cooldown_for_tier(tier).0 as usize, PixelError::BulkTooLarge);
+    require!(!ctx.remaining_accounts.is_empty(), PixelError::InvalidShardCount);
+
+    // Load every shard up front (read in place, no 8KB heap copies).
+    // A shard is followed by its ShardPainters and then its ShardHistory account
+    // when those layers are enabled on it, then by its owner's RewardAccrual if any.
+    let main_wallet = ctx.accounts.session.main_address;
+    let mut shards: Vec<ShardView> = Vec::with_capacity(MAX_SHARDS_PER_MULTI);
+    let mut layers = Vec::with_capacity(MAX_SHARDS_PER_MULTI);
+    let mut accruals: Vec<Account<RewardAccrual>> = Vec::with_capacity(MAX_SHARDS_PER_MULTI);
+    let mut accounts = ctx.remaining_accounts.iter().peekable();
+    while let Some(info) = accounts.next() {
+        let shard = ShardView::load(info)?;
+        require!(info.is_writable, PixelError::InvalidShardAccount);
+        require!(
+            !shards.iter().any(|s| s.key() == shard.key()),
+            PixelError::DuplicateShard
+        );
+        require!(shards.len() < MAX_SHARDS_PER_MULTI, PixelError::InvalidShardCount);
+
+        let painters = if shard.layers & LAYER_PAINTERS != 0 {
+            let info = accounts.next().ok_or(PixelError::PainterLayerRequired)?;
+            let painters = AccountLoader::<ShardPainters>::try_from(info)?;
+            let layer = painters.load()?;
+            require!(
+                layer.shard_x == shard.shard_x && layer.shard_y == shard.shard_y,
+                PixelError::PainterLayerRequired
+            );
+            drop(layer);
+            Some(painters)
+        } else {
+            None
+        };
+
+        let history = if shard.layers & LAYER_HISTORY != 0 {
+            let info = accounts.next().ok_or(PixelError::HistoryLayerRequired)?;
+            let history = AccountLoader::<ShardHistory>::try_from(info)?;
+            let layer = history.load()?;
+            require!(
+                layer.shard_x == shard.shard_x && layer.shard_y == shard.shard_y,
+                PixelError::HistoryLayerRequired
+            );
+            drop(layer);
+            Some(history)
+        } else {
+            None
+        };
+
+        let accrual_follows = accounts
+            .peek()
+            .is_some_and(|info| info.data.borrow().starts_with(RewardAccrual::DISCRIMINATOR));
+        if shard.creator != main_wallet
+            && !accruals.iter().any(|a| a.owner == shard.creator)
+            && accrual_follows
+        {
+            let info = accounts.next().ok_or(PixelError::OwnerRewardsRequired)?;
+            let accrual = Account::<RewardAccrual>::try_from(info)?;
+            let expected = Pubkey::create_program_address(
+                &[REWARDS_SEED, shard.creator.as_ref(), &[accrual.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| PixelError::OwnerRewardsRequired)?;
+            require!(
+                info.is_writable && info.key() == expected && accrual.owner == shard.creator,
+                PixelError::OwnerRewardsRequired
+            );
+            accruals.push(accrual);
+        }
+
+        shards.push(shard);
+        layers.push((painters, history));
+    }
+
+    let session = &mut ctx.accounts.session;
+
+    // Route every pixel before writing anything so cooldown is charged once
+    let mut targets = Vec::with_capacity(pixels.len());
+    let mut non_owner_pixels = 0;
+    let mut accrued = vec![0usize; accruals.len()];
+    let mut unaccrued = 0;
+    for pixel in pixels.iter() {
+        require!(pixel.px < CANVAS_RES && pixel.py < CANVAS_RES, PixelError::InvalidPixelCoord);
+        require!((1..=AVAILABLE_COLORS).contains(&pixel.color), PixelError::InvalidColor);
+
+        let shard_x = (pixel.px / SHARD_DIMENSION) as u16;
+        let shard_y = (pixel.py / SHARD_DIMENSION) as u16;
+        let shard_index = shards
+            .iter()
+            .position(|s| s.shard_x == shard_x && s.shard_y == shard_y)
+            .ok_or(PixelError::ShardMismatch)?;
+
+        let shard = &shards[shard_index];
+        require_hook_bypass_allowed(&shard.hook_program, &shard.creator, &main_wallet)?;
+        if shard.creator != main_wallet {
+            non_owner_pixels += 1;
+            match accruals.iter().position(|a| a.owner == shard.creator) {
+                Some(accrual_index) => accrued[accrual_index] += 1,
+                None => unaccrued += 1,
+            }
+        }
+        targets.push(shard_index);
+    }
+
+    let timestamp = Clock::get()?.unix_timestamp as u64;
+    let mut profile = ctx.accounts.profile.as_deref_mut();
+    let mut faction = ctx.accounts.faction.as_deref_mut();
+    // Visitor pixels are charged per shard owner so each owner accrues its share
+    for (accrual, &count) in accruals.iter_mut().zip(accrued.iter()) {
+        if count == 0 {
+            continue;
+        }
+        charge_pixels(
+            session,
+            profile.as_deref_mut(),
+            Some(accrual),
+            faction.as_deref_mut(),
+            &ctx.accounts.season,
+            false,
+            count,
+            timestamp,
+        )?;
+        accrual.exit(&crate::ID)?;
+    }
+    if unaccrued > 0 {
+        charge_pixels(
+            session,
+            profile.as_deref_mut(),
+            None,
+            faction.as_deref_mut(),
+            &ctx.accounts.season,
+            false,
+            unaccrued,
+            timestamp,
+        )?;
+    }
+    if pixels.len() > non_owner_pixels {
+        charge_pixels(
+            session,
+            profile,
+            None,
+            faction,
+            &ctx.accounts.season,
+            true,
+            pixels.len() - non_owner_pixels,
+            timestamp,
+        )?;
+    }
+
+    let mut writers = Vec::with_capacity(shards.len());
+    for (shard, (painters, history)) in shards.iter().zip(layers.iter()) {
+        writers.push(PixelWriter::new(
+            shard.layers,
+            painters.as_ref(),
+            history.as_ref(),
+            Some(&main_wallet),
+        )?);
+    }
+
+    // Packed (local index, color) records per shard for the events
+    let mut counts = vec![0usize; shards.len()];
+    for &shard_index in targets.iter() {
+        counts[shard_index] += 1;
+    }
+    let mut packed: Vec<Vec<u8>> = counts.iter().map(|&count| vec![0u8; packed_len(count)]).collect();
+    let mut packed_count = vec![0usize; shards.len()];
+
+    for (pixel, &shard_index) in pixels.iter().zip(targets.iter()) {
+        let local_pixel_id = local_pixel_index(pixel.px % SHARD_DIMENSION, pixel.py % SHARD_DIMENSION);
+        let mut shard_pixels = shards[shard_index].pixels_mut()?;
+        writers[shard_index].write(&mut shard_pixels, local_pixel_id, pixel.color);
+        pack_pixel(&mut packed[shard_index], packed_count[shard_index], local_pixel_id, pixel.color);
+        packed_count[shard_index] += 1;
+    }
+
+    msg!(
+        "Bulk placed {} pixels across {} shards",
+        pixels.len(),
+        shards.len()
+    );
+
+    // One write and one event per shard
+    let painter = ctx.accounts.signer.key();
+    for ((shard, writer), (data, count)) in shards
+        .iter()
+        .zip(writers.iter())
+        .zip(packed.into_iter().zip(counts))
+    {
+        if count == 0 {
+            continue;
+        }
+        let seq = shard.record_write(writer.hash_delta())?;
+        emit!(PixelsBulkChanged {
+            shard_x: shard.shard_x,
+            shard_y: shard.shard_y,
+            count: count as u16,
+            data,
+            painter,
+            main_wallet,
+            timestamp,
+            seq,
+        });
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn fill_rect(
+    ctx: Context<PlacePixel>,
+    shard_x: u16,
+    shard_y: u16,
+    x0: u8,
+    y0: u8,
+    x1: u8,
+    y1: u8,
+    color: u8,
+) -> Result<()> {
+    require!(valid_local(x0, y0) && valid_local(x1, y1), PixelError::InvalidPixelCoord);
+    require!((1..=AVAILABLE_COLORS).contains(&color), PixelError::InvalidColor);
+
+    let (min_x, max_x) = (x0.min(x1), x0.max(x1));
+    let (min_y, max_y) = (y0.min(y1), y0.max(y1));
+
+    let shard = &mut ctx.accounts.shard;
+    require!(
+        shard.shard_x == shard_x && shard.shard_y == shard_y,
+        PixelError::ShardMismatch
+    );
+
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        ctx.accounts.painters.as_ref(),
+        ctx.accounts.history.as_ref(),
+        Some(&ctx.accounts.session.main_address),
+    )?;
+    let mut changes = ChangeList::new(shard, ctx.accounts.signer.key(), ctx.accounts.session.main_address)?;
+    for y in min_y..=max_y {
+        for x in min_x..=max_x {
+            let local_pixel_id = local_pixel_index(x as u32, y as u32);
+            if shard.pixels[local_pixel_id] != color {
+                writer.write(&mut shard.pixels, local_pixel_id, color);
+                changes.push(local_pixel_id, color);
+            }
+        }
+    }
+
+    shard.record_write(writer.hash_delta());
+    finish_region_op(
+        shard,
+        &mut ctx.accounts.session,
+        ctx.accounts.profile.as_deref_mut(),
+        ctx.accounts.owner_rewards.as_deref_mut(),
+        ctx.accounts.faction.as_deref_mut(),
+        &ctx.accounts.season,
+        RegionOp::FillRect,
+        [min_x, min_y, max_x, max_y],
+        color,
+        changes,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn draw_line(
+    ctx: Context<PlacePixel>,
+    shard_x: u16,
+    shard_y: u16,
+    x0: u8,
+    y0: u8,
+    x1: u8,
+    y1: u8,
+    color: u8,
+) -> Result<()> {
+    require!(valid_local(x0, y0) && valid_local(x1, y1), PixelError::InvalidPixelCoord);
+    require!((1..=AVAILABLE_COLORS).contains(&color), PixelError::InvalidColor);
+
+    let shard = &mut ctx.accounts.shard;
+    require!(
+        shard.shard_x == shard_x && shard.shard_y == shard_y,
+        PixelError::ShardMismatch
+    );
+
+    let (mut x, mut y) = (x0 as i32, y0 as i32);
+    let (end_x, end_y) = (x1 as i32, y1 as i32);
+    let dx = (end_x - x).abs();
+    let dy = -(end_y - y).abs();
+    let step_x = if x < end_x { 1 } else { -1 };
+    let step_y = if y < end_y { 1 } else { -1 };
+    let mut error = dx + dy;
+
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        ctx.accounts.painters.as_ref(),
+        ctx.accounts.history.as_ref(),
+        Some(&ctx.accounts.session.main_address),
+    )?;
+    let mut changes = ChangeList::new(shard, ctx.accounts.signer.key(), ctx.accounts.session.main_address)?;
+    loop {
+        let local_pixel_id = local_pixel_index(x as u32, y as u32);
+        if shard.pixels[local_pixel_id] != color {
+            writer.write(&mut shard.pixels, local_pixel_id, color);
+            changes.push(local_pixel_id, color);
+        }
+        if x == end_x && y == end_y {
+            break;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+
+    shard.record_write(writer.hash_delta());
+    finish_region_op(
+        shard,
+        &mut ctx.accounts.session,
+        ctx.accounts.profile.as_deref_mut(),
+        ctx.accounts.owner_rewards.as_deref_mut(),
+        ctx.accounts.faction.as_deref_mut(),
+        &ctx.accounts.season,
+        RegionOp::Line,
+        [x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)],
+        color,
+        changes,
+    )
+}
+
+pub(crate) fn flood_fill(
+    ctx: Context<PlacePixel>,
+    shard_x: u16,
+    shard_y: u16,
+    x: u8,
+    y: u8,
+    color: u8,
+) -> Result<()> {
+    require!(valid_local(x, y), PixelError::InvalidPixelCoord);
+    require!((1..=AVAILABLE_COLORS).contains(&color), PixelError::InvalidColor);
+
+    let shard = &mut ctx.accounts.shard;
+    require!(
+        shard.shard_x == shard_x && shard.shard_y == shard_y,
+        PixelError::ShardMismatch
+    );
+
+    let target = shard.pixels[local_pixel_index(x as u32, y as u32)];
+    let mut bounds = [x, y, x, y];
+    let mut changes = ChangeList::new(shard, ctx.accounts.signer.key(), ctx.accounts.session.main_address)?;
+    let mut hash_delta = 0;
+
+    // Recolor pixels as they are pushed, so each one enters the stack at most once
+    if target != color {
+        let mut writer = PixelWriter::new(
+            shard.layers,
+            ctx.accounts.painters.as_ref(),
+            ctx.accounts.history.as_ref(),
+            Some(&ctx.accounts.session.main_address),
+        )?;
+        let mut stack = Vec::with_capacity(MAX_FLOOD_FILL_PIXELS);
+        let seed_pixel_id = local_pixel_index(x as u32, y as u32);
+        writer.write(&mut shard.pixels, seed_pixel_id, color);
+        changes.push(seed_pixel_id, color);
+        stack.push((x, y));
+
+        while let Some((cx, cy)) = stack.pop() {
+            bounds = [bounds[0].min(cx), bounds[1].min(cy), bounds[2].max(cx), bounds[3].max(cy)];
+
+            for (dx, dy) in [(-1i32, 0i32), (1, 0), (0, -1), (0, 1)] {
+                let (nx, ny) = (cx as i32 + dx, cy as i32 + dy);
+                if nx < 0 || ny < 0 || !valid_local(nx as u8, ny as u8) {
+                    continue;
+                }
+                let local_pixel_id = local_pixel_index(nx as u32, ny as u32);
+                if shard.pixels[local_pixel_id] == target {
+                    require!(changes.len() < MAX_FLOOD_FILL_PIXELS, PixelError::FloodFillTooLarge);
+                    writer.write(&mut shard.pixels, local_pixel_id, color);
+                    changes.push(local_pixel_id, color);
+                    stack.push((nx as u8, ny as u8));
+                }
+            }
+        }
+        hash_delta = writer.hash_delta();
+    }
+
+    shard.record_write(hash_delta);
+    finish_region_op(
+        shard,
+        &mut ctx.accounts.session,
+        ctx.accounts.profile.as_deref_mut(),
+        ctx.accounts.owner_rewards.as_deref_mut(),
+        ctx.accounts.faction.as_deref_mut(),
+        &ctx.accounts.season,
+        RegionOp::FloodFill,
+        bounds,
+        color,
+        changes,
+    )
+}
+
+pub(crate) fn write_shard_chunk(
+    ctx: Context<PlacePixel>,
+    shard_x: u16,
+    shard_y: u16,
+    offset: u16,
+    bytes: Vec<u8>,
+    skip_transparent: bool,
+) -> Result<()> {
+    require!(!bytes.is_empty(), PixelError::EmptyBulkPixels);
+    let start = offset as usize;
+    let end = start + bytes.len();
+    require!(end <= PIXELS_PER_SHARD, PixelError::InvalidPixelCoord);
+
+    let shard = &mut ctx.accounts.shard;
+    require!(
+        shard.shard_x == shard_x && shard.shard_y == shard_y,
+        PixelError::ShardMismatch
+    );
+    require!(
+        shard.creator == ctx.accounts.session.main_address,
+        PixelError::NotShardOwner
+    );
+
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        ctx.accounts.painters.as_ref(),
+        ctx.accounts.history.as_ref(),
+        Some(&ctx.accounts.session.main_address),
+    )?;
+    let mut changes = ChangeList::new(shard, ctx.accounts.signer.key(), ctx.accounts.session.main_address)?;
+    for (local_pixel_id, &color) in (start..end).zip(bytes.iter()) {
+        if (skip_transparent && color == 0) || shard.pixels[local_pixel_id] == color {
+            continue;
+        }
+        writer.write(&mut shard.pixels, local_pixel_id, color);
+        changes.push(local_pixel_id, color);
+    }
+
+    // Bounding box of the rows covered by the run
+    let width = SHARD_DIMENSION as usize;
+    let (first_row, last_row) = (start / width, (end - 1) / width);
+    let bounds = if first_row == last_row {
+        [(start % width) as u8, first_row as u8, ((end - 1) % width) as u8, last_row as u8]
+    } else {
+        [0, first_row as u8, (width - 1) as u8, last_row as u8]
+    };
+
+    shard.record_write(writer.hash_delta());
+    finish_region_op(
+        shard,
+        &mut ctx.accounts.session,
+        ctx.accounts.profile.as_deref_mut(),
+        ctx.accounts.owner_rewards.as_deref_mut(),
+        ctx.accounts.faction.as_deref_mut(),
+        &ctx.accounts.season,
+        RegionOp::ChunkWrite,
+        bounds,
+        0,
+        changes,
+    )
+}
+
+pub(crate) fn copy_region(
+    ctx: Context<CopyRegion>,
+    dst_shard_x: u16,
+    dst_shard_y: u16,
+    region: RegionCopy,
+) -> Result<()> {
+    require!(region.width > 0 && region.height > 0, PixelError::EmptyBulkPixels);
+    require!(region.quarter_turns < 4, PixelError::InvalidTransform);
+
+    let (w, h) = (region.width as u32, region.height as u32);
+    let (out_w, out_h) = if region.quarter_turns % 2 == 1 { (h, w) } else { (w, h) };
+    require!(
+        region.src_x as u32 + w <= SHARD_DIMENSION
+            && region.src_y as u32 + h <= SHARD_DIMENSION
+            && region.dst_x as u32 + out_w <= SHARD_DIMENSION
+            && region.dst_y as u32 + out_h <= SHARD_DIMENSION,
+        PixelError::InvalidPixelCoord
+    );
+
+    let source = &ctx.accounts.source;
+    let shard = &mut ctx.accounts.shard;
+    require!(
+        shard.shard_x == dst_shard_x && shard.shard_y == dst_shard_y,
+        PixelError::ShardMismatch
+    );
+
+    // Walk the destination and map each pixel back into the source rectangle.
+    // `source` is its own deserialized copy, so copying within one shard is safe
+    // even when the rectangles overlap.
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        ctx.accounts.painters.as_ref(),
+        ctx.accounts.history.as_ref(),
+        Some(&ctx.accounts.session.main_address),
+    )?;
+    let mut changes = ChangeList::new(shard, ctx.accounts.signer.key(), ctx.accounts.session.main_address)?;
+    for oy in 0..out_h {
+        for ox in 0..out_w {
+            let (mut u, mut v) = match region.quarter_turns {
+                1 => (oy, h - 1 - ox),
+                2 => (w - 1 - ox, h - 1 - oy),
+                3 => (w - 1 - oy, ox),
+                _ => (ox, oy),
+            };
+            if region.flip_x {
+                u = w - 1 - u;
+            }
+            if region.flip_y {
+                v = h - 1 - v;
+            }
+
+            let color = source.pixels[local_pixel_index(region.src_x as u32 + u, region.src_y as u32 + v)];
+            if region.skip_transparent && color == 0 {
+                continue;
+            }
+
+            let local_pixel_id = local_pixel_index(region.dst_x as u32 + ox, region.dst_y as u32 + oy);
+            if shard.pixels[local_pixel_id] != color {
+                writer.write(&mut shard.pixels, local_pixel_id, color);
+                changes.push(local_pixel_id, color);
+            }
+        }
+    }
+
+    shard.record_write(writer.hash_delta());
+    finish_region_op(
+        shard,
+        &mut ctx.accounts.session,
+        ctx.accounts.profile.as_deref_mut(),
+        ctx.accounts.owner_rewards.as_deref_mut(),
+        ctx.accounts.faction.as_deref_mut(),
+        &ctx.accounts.season,
+        RegionOp::Copy,
+        [
+            region.dst_x,
+            region.dst_y,
+            region.dst_x + (out_w - 1) as u8,
+            region.dst_y + (out_h - 1) as u8,
+        ],
+        0,
+        changes,
+    )
+}
+
+// ========================================
+// Helpers
+// ========================================
+
+/// Charge `count` pixels placed by a session and credit them to the wallet's profile
+/// Pixels on the wallet's own shard are free; the rest go against the burst, which is
+/// sized by the profile's XP tier (tier 0 without a profile), earn XP and accrue
+/// token rewards to the shard owner's `rewards` account when the owner has one.
+/// Every pixel counts towards `faction`, which must be the profile's faction, and
+/// scores for that faction in the running season. Fails while `season` is frozen.
+/// Also advances the profile's daily streak and unlocks painting achievements.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn charge_pixels(
+    session: &mut SessionAccount,
+    profile: Option<&mut PlayerProfile>,
+    rewards: Option<&mut RewardAccrual>,
+    faction: Option<&mut Faction>,
+    season: &AccountInfo,
+    on_own_shard: bool,
+    count: usize,
+    now: u64,
+) -> Result<()> {
+    let season = load_open_season(season, now)?;
+
+    if let Some(faction) = faction {
+        let member = profile.as_ref().is_some_and(|p| p.faction == faction.id);
+        require!(member, PixelError::InvalidFaction);
+        faction.record_pixels(season.as_ref(), on_own_shard, count as u64);
+    }
+
+    let tier = profile.as_ref().map_or(0, |p| p.tier());
+    if !on_own_shard {
+        consume_cooldown(session, tier, count, now)?;
+        if let Some(rewards) = rewards {
+            rewards.accrued_pixels += count as u64;
+        }
+    }
+    if let Some(profile) = profile {
+        profile.record_pixels(on_own_shard, count as u64);
+        let unlocked = profile.record_activity(now);
+        emit_achievements(profile.main_wallet, unlocked, profile.streak_days, now);
+    }
+    Ok(())
+}
+
+/// Charge `count` pixels placed on someone else's shard against the session burst
+/// Resets the burst once the tier's cooldown period has passed since the limit was hit.
+/// Emits CooldownTriggered when this charge uses up the burst.
+pub(crate) fn consume_cooldown(session: &mut SessionAccount, tier: u8, count: usize, now: u64) -> Result<()> {
+    let (limit, period) = cooldown_for_tier(tier);
+
+    // Check if cooldown has reset
+    if session.cooldown_counter >= limit {
+        if now.saturating_sub(session.last_place_timestamp) >= period {
+            session.cooldown_counter = 0;
+        } else {
+            return err!(PixelError::Cooldown);
+        }
+    }
+
+    // Check if we would exceed the limit
+    let new_counter = session.cooldown_counter as usize + count;
+    require!(new_counter <= limit as usize, PixelError::BulkExceedsCooldown);
+    session.cooldown_counter = new_counter as u8;
+
+    // If we hit the limit, record timestamp
+    if session.cooldown_counter >= limit {
+        session.last_place_timestamp = now;
+
+        emit!(CooldownTriggered {
+            main_wallet: session.main_address,
+            session_key: session.authority,
+            remaining_seconds: period,
+            timestamp: now,
+        });
+    }
+    Ok(())
+}
+
+/// Shared tail of every drawing primitive: charge changed pixels against the non-owner
+/// cooldown, emit the rest of the change list and one RegionChanged event covering
+/// `bounds` ([x0, y0, x1, y1], inclusive)
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn finish_region_op(
+    shard: &PixelShard,
+    session: &mut SessionAccount,
+    profile: Option<&mut PlayerProfile>,
+    rewards: Option<&mut RewardAccrual>,
+    faction: Option<&mut Faction>,
+    season: &AccountInfo,
+    op: RegionOp,
+    bounds: [u8; 4],
+    color: u8,
+    changes: ChangeList,
+) -> Result<()> {
+    require_hook_bypass_allowed(&shard.hook_program, &shard.creator, &session.main_address)?;
+
+    let painter = changes.painter;
+    let timestamp = changes.timestamp;
+    let (changed, chunks) = changes.finish();
+    if changed > 0 {
+        let is_owner = shard.creator == session.main_address;
+        charge_pixels(session, profile, rewards, faction, season, is_owner, changed, timestamp)?;
+    }
+
+    msg!(
+        "{:?} changed {} pixels on shard ({}, {})",
+        op,
+        changed,
+        shard.shard_x,
+        shard.shard_y
+    );
+
+    emit!(RegionChanged {
+        shard_x: shard.shard_x,
+        shard_y: shard.shard_y,
+        op,
+        x0: bounds[0],
+        y0: bounds[1],
+        x1: bounds[2],
+        y1: bounds[3],
+        color,
+        pixels_changed: changed as u16,
+        chunks,
+        painter,
+        main_wallet: session.main_address,
+        timestamp,
+        seq: shard.seq,
+    });
+    Ok(())
+}
+
+/// Exact list of the pixels a drawing primitive, restore, revert or undo changes, streamed
+/// as PixelsBulkChanged events of up to MAX_PACKED_PIXELS records each. Chunks are emitted as they fill, so
+/// large operations (a full-shard copy changes 8100 pixels) never hold the whole list on
+/// the heap. Every chunk carries the seq the operation's record_write will produce.
+pub(crate) struct ChangeList {
+    shard_x: u16,
+    shard_y: u16,
+    painter: Pubkey,
+    main_wallet: Pubkey,
+    timestamp: u64,
+    seq: u64,
+    data: Vec<u8>,
+    count: usize,
+    total: usize,
+    chunks: u16,
+}
+
+impl ChangeList {
+    /// Start the change list of one write to `shard`, made by `painter` for `main_wallet`
+    pub(crate) fn new(shard: &PixelShard, painter: Pubkey, main_wallet: Pubkey) -> Result<Self> {
+        Ok(Self {
+            shard_x: shard.shard_x,
+            shard_y: shard.shard_y,
+            painter,
+            main_wallet,
+            timestamp: Clock::get()?.unix_timestamp as u64,
+            seq: shard.seq + 1,
+            data: vec![0u8; packed_len(MAX_PACKED_PIXELS as usize)],
+            count: 0,
+            total: 0,
+            chunks: 0,
+        })
+    }
+
+    /// Record that `local_pixel_id` changed to `color`
+    pub(crate) fn push(&mut self, local_pixel_id: usize, color: u8) {
+        pack_pixel(&mut self.data, self.count, local_pixel_id, color);
+        self.count += 1;
+        self.total += 1;
+        if self.count == MAX_PACKED_PIXELS as usize {
+            self.flush();
+        }
+    }
+
+    /// Pixels recorded so far
+    pub(crate) fn len(&self) -> usize {
+        self.total
+    }
+
+    /// Emit the pending records as one PixelsBulkChanged event
+    fn flush(&mut self) {
+        if self.count == 0 {
+            return;
+        }
+        let mut data = std::mem::replace(&mut self.data, vec![0u8; packed_len(MAX_PACKED_PIXELS as usize)]);
+        data.truncate(packed_len(self.count));
+        emit!(PixelsBulkChanged {
+            shard_x: self.shard_x,
+            shard_y: self.shard_y,
+            count: self.count as u16,
+            data,
+            painter: self.painter,
+            main_wallet: self.main_wallet,
+            timestamp: self.timestamp,
+            seq: self.seq,
+        });
+        self.count = 0;
+        self.chunks += 1;
+    }
+
+    /// Emit the last chunk; returns (pixels changed, chunks emitted)
+    pub(crate) fn finish(mut self) -> (usize, u16) {
+        self.flush();
+        (self.total, self.chunks)
+    }
+}
+
+/// Bytes needed to hold `count` 21-bit packed pixel records
+pub(crate) fn packed_len(count: usize) -> usize {
+    (count * PACKED_PIXEL_BITS).div_ceil(8)
+}
+
+/// Encode (local index, color) as the `n`th 21-bit record of a zeroed packed buffer
+/// Inverse of unpack_pixel
+pub(crate) fn pack_pixel(data: &mut [u8], n: usize, local_pixel_id: usize, color: u8) {
+    let bit = n * PACKED_PIXEL_BITS;
+    let record = ((color as u32) << 13 | local_pixel_id as u32) << (bit % 8);
+    for (i, byte) in data[bit / 8..].iter_mut().take(4).enumerate() {
+        *byte |= (record >> (8 * i)) as u8;
+    }
+}
+
+/// Decode the `n`th 21-bit record of a packed pixel buffer into (local index, color)
+/// Records are packed LSB-first; one record spans at most 4 bytes
+pub(crate) fn unpack_pixel(data: &[u8], n: usize) -> (usize, u8) {
+    let bit = n * PACKED_PIXEL_BITS;
+    let word = data[bit / 8..]
+        .iter()
+        .take(4)
+        .enumerate()
+        .fold(0u32, |word, (i, byte)| word | (*byte as u32) << (8 * i));
+    let record = word >> (bit % 8);
+    ((record & 0x1FFF) as usize, (record >> 13) as u8)
+}
+
+/// Borrowed view of a `PixelShard` account that isn't deserialized by Anchor
+/// Reads the header in place and writes pixels straight into account data, so several
+/// shards fit in one instruction without copying ~8KB each onto the 32KB heap.
+/// Also reads the committed state of delegated shards on the base layer.
+pub(crate) struct ShardView<'a, 'info> {
+    info: &'a AccountInfo<'info>,
+    pub(crate) shard_x: u16,
+    pub(crate) shard_y: u16,
+    pub(crate) creator: Pubkey,
+    pub(crate) layers: u8,
+    pub(crate) index: u32,
+    hook_program: Pubkey,
+}
+
+impl<'a, 'info> ShardView<'a, 'info> {
+    /// Validate owner, discriminator and PDA address of a shard account (possibly delegated)
+    pub(crate) fn load(info: &'a AccountInfo<'info>) -> Result<Self> {
+        require!(
+            info.owner == &crate::ID || info.owner == &DELEGATION_PROGRAM_ID,
+            PixelError::InvalidShardAccount
+        );
+
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= PixelShard::TAIL_OFFSET + PixelShard::TAIL_LEN
+                && data[..8] == *PixelShard::DISCRIMINATOR
+                && data[12..16] == (BYTES_PER_SHARD as u32).to_le_bytes(),
+            PixelError::InvalidShardAccount
+        );
+
+        let shard_x = u16::from_le_bytes([data[8], data[9]]);
+        let shard_y = u16::from_le_bytes([data[10], data[11]]);
+        let tail = &data[PixelShard::TAIL_OFFSET..];
+        let creator = Pubkey::try_from(&tail[..32]).map_err(|_| PixelError::InvalidShardAccount)?;
+        let bump = tail[32];
+        let layers = tail[33];
+        let index = u32::from_le_bytes(tail[50..54].try_into().unwrap());
+        let hook_program = Pubkey::try_from(&tail[54..86]).map_err(|_| PixelError::InvalidShardAccount)?;
+        drop(data);
+
+        let expected = Pubkey::create_program_address(
+            &[SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes(), &[bump]],
+            &crate::ID,
+        )
+        .map_err(|_| PixelError::InvalidShardAccount)?;
+        require_keys_eq!(info.key(), expected, PixelError::InvalidShardAccount);
+
+        Ok(Self { info, shard_x, shard_y, creator, layers, index, hook_program })
+    }
+
+    pub(crate) fn key(&self) -> Pubkey {
+        self.info.key()
+    }
+
+    pub(crate) fn pixels(&self) -> Result<Ref<'_, [u8]>> {
+        let data = self.info.try_borrow_data()?;
+        Ok(Ref::map(data, |data| {
+            &data[PixelShard::PIXELS_OFFSET..PixelShard::TAIL_OFFSET]
+        }))
+    }
+
+    fn pixels_mut(&self) -> Result<RefMut<'_, [u8]>> {
+        let data = self.info.try_borrow_mut_data()?;
+        Ok(RefMut::map(data, |data| {
+            &mut data[PixelShard::PIXELS_OFFSET..PixelShard::TAIL_OFFSET]
+        }))
+    }
+
+    /// Current (seq, content_hash) of the shard
+    pub(crate) fn write_state(&self) -> Result<(u64, u64)> {
+        let data = self.info.try_borrow_data()?;
+        let tail = &data[PixelShard::TAIL_OFFSET..];
+        Ok((
+            u64::from_le_bytes(tail[34..42].try_into().unwrap()),
+            u64::from_le_bytes(tail[42..50].try_into().unwrap()),
+        ))
+    }
+
+    /// Bump the shard's seq and apply `hash_delta` to its content hash; returns the new seq
+    fn record_write(&self, hash_delta: u64) -> Result<u64> {
+        let mut data = self.info.try_borrow_mut_data()?;
+        let tail = &mut data[PixelShard::TAIL_OFFSET..];
+        let seq = u64::from_le_bytes(tail[34..42].try_into().unwrap()) + 1;
+        let content_hash = u64::from_le_bytes(tail[42..50].try_into().unwrap()).wrapping_add(hash_delta);
+        tail[34..42].copy_from_slice(&seq.to_le_bytes());
+        tail[42..50].copy_from_slice(&content_hash.to_le_bytes());
+        Ok(seq)
+    }
+}
+
+/// Bookkeeping that rides along with pixel writes to one shard
+/// Keeps the shard's enabled companion layers in sync: the painter layer points at
+/// whoever wrote each pixel last and the history ring buffer records every change.
+pub(crate) struct PixelWriter<'a> {
+    painters: Option<RefMut<'a, ShardPainters>>,
+    history: Option<RefMut<'a, ShardHistory>>,
+    slot: u8,
+    timestamp: u64,
+    /// Change to apply to the shard's content hash once writing is done
+    hash_delta: u64,
+}
+
+impl<'a> PixelWriter<'a> {
+    /// Load the layers enabled in `layers` (PixelShard.layers) and resolve `painter`'s slot
+    /// Enabled layers must be passed; a `None` painter clears attribution of written
+    /// pixels (restores, reverts).
+    pub(crate) fn new(
+        layers: u8,
+        painters: Option<&'a AccountLoader<'_, ShardPainters>>,
+        history: Option<&'a AccountLoader<'_, ShardHistory>>,
+        painter: Option<&Pubkey>,
+    ) -> Result<Self> {
+        let mut painters = if layers & LAYER_PAINTERS != 0 {
+            Some(painters.ok_or(PixelError::PainterLayerRequired)?.load_mut()?)
+        } else {
+            None
+        };
+        let slot = match (painters.as_mut(), painter) {
+            (Some(painters), Some(painter)) => painters.slot_for(painter),
+            _ => 0,
+        };
+
+        let history = if layers & LAYER_HISTORY != 0 {
+            Some(history.ok_or(PixelError::HistoryLayerRequired)?.load_mut()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            painters,
+            history,
+            slot,
+            timestamp: Clock::get()?.unix_timestamp as u64,
+            hash_delta: 0,
+        })
+    }
+
+    /// Write one pixel; returns whether its color changed
+    pub(crate) fn write(&mut self, pixels: &mut [u8], local_pixel_id: usize, color: u8) -> bool {
+        let old_color = pixels[local_pixel_id];
+        pixels[local_pixel_id] = color;
+
+        if let Some(painters) = self.painters.as_mut() {
+            painters.slots[local_pixel_id] = self.slot;
+        }
+        if old_color == color {
+            return false;
+        }
+        self.hash_delta = self
+            .hash_delta
+            .wrapping_sub(pixel_hash(local_pixel_id, old_color))
+            .wrapping_add(pixel_hash(local_pixel_id, color));
+        if let Some(history) = self.history.as_mut() {
+            history.push(PixelChange {
+                timestamp: self.timestamp,
+                local_pixel_id: local_pixel_id as u16,
+                old_color,
+                new_color: color,
+                painter_slot: self.slot,
+                _padding: [0; 3],
+            });
+        }
+        true
+    }
+
+    /// Content hash change accumulated by the writes so far
+    pub(crate) fn hash_delta(&self) -> u64 {
+        self.hash_delta
+    }
+
+    /// Slot of a main wallet in the painter layer, if it has one
+    pub(crate) fn find_slot(&self, main_wallet: &Pubkey) -> Option<u8> {
+        self.painters.as_ref()?.find_slot(main_wallet)
+    }
+
+    /// Painter slot currently attributed to a pixel (0 without a painter layer)
+    pub(crate) fn painter_slot(&self, local_pixel_id: usize) -> u8 {
+        self.painters.as_ref().map_or(0, |painters| painters.slots[local_pixel_id])
+    }
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct PlacePixel<'info> {
+    #[account(
+        mut,
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = shard.bump
+    )]
+    pub shard: Account<'info, PixelShard>,
+
+    /// Painter attribution layer, required when the shard has one enabled
+    #[account(
+        mut,
+        seeds = [PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = painters.load()?.bump
+    )]
+    pub painters: Option<AccountLoader<'info, ShardPainters>>,
+
+    /// Change history ring buffer, required when the shard has one enabled
+    #[account(
+        mut,
+        seeds = [HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = history.load()?.bump
+    )]
+    pub history: Option<AccountLoader<'info, ShardHistory>>,
+
+    #[account(
+        mut,
+        seeds = [b"session", signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionAccount>,
+
+    /// XP profile of the session's main wallet; without it placements use tier 0 limits
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, session.main_address.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, PlayerProfile>>,
+
+    /// Reward accrual of the shard's owner, credited for pixels placed by visitors
+    /// Visitor pixels on a shard whose owner has none accrue nothing
+    #[account(
+        mut,
+        seeds = [REWARDS_SEED, shard.creator.as_ref()],
+        bump = owner_rewards.bump,
+    )]
+    pub owner_rewards: Option<Account<'info, RewardAccrual>>,
+
+    /// Faction of the session's main wallet, credited with every pixel placed
+    #[account(
+        mut,
+        seeds = [FACTION_SEED, &faction.id.to_le_bytes()],
+        bump = faction.bump,
+    )]
+    pub faction: Option<Account<'info, Faction>>,
+
+    /// CHECK: The season singleton, read-only (may not exist yet). Checked by load_open_season.
+    #[account(seeds = [SEASON_SEED], bump)]
+    pub season: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Place pixels across several shards
+/// Target shards are passed as writable `remaining_accounts`
+#[derive(Accounts)]
+pub struct PlacePixelsMultiShard<'info> {
+    #[account(
+        mut,
+        seeds = [b"session", signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionAccount>,
+
+    /// XP profile of the session's main wallet; without it placements use tier 0 limits
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, session.main_address.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, PlayerProfile>>,
+
+    /// Faction of the session's main wallet, credited with every pixel placed
+    #[account(
+        mut,
+        seeds = [FACTION_SEED, &faction.id.to_le_bytes()],
+        bump = faction.bump,
+    )]
+    pub faction: Option<Account<'info, Faction>>,
+
+    /// CHECK: The season singleton, read-only (may not exist yet). Checked by load_open_season.
+    #[account(seeds = [SEASON_SEED], bump)]
+    pub season: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Copy a region from a source shard into a destination shard
+/// Source and destination may be the same shard
+#[derive(Accounts)]
+#[instruction(src_shard_x: u16, src_shard_y: u16, dst_shard_x: u16, dst_shard_y: u16)]
+pub struct CopyRegion<'info> {
+    #[account(
+        seeds = [SHARD_SEED, &src_shard_x.to_le_bytes(), &src_shard_y.to_le_bytes()],
+        bump = source.bump
+    )]
+    pub source: Account<'info, PixelShard>,
+
+    /// Destination shard
+    #[account(
+        mut,
+        seeds = [SHARD_SEED, &dst_shard_x.to_le_bytes(), &dst_shard_y.to_le_bytes()],
+        bump = shard.bump
+    )]
+    pub shard: Account<'info, PixelShard>,
+
+    /// Painter attribution layer of the destination, required when it has one enabled
+    #[account(
+        mut,
+        seeds = [PAINTERS_SEED, &dst_shard_x.to_le_bytes(), &dst_shard_y.to_le_bytes()],
+        bump = painters.load()?.bump
+    )]
+    pub painters: Option<AccountLoader<'info, ShardPainters>>,
+
+    /// Change history of the destination, required when it has one enabled
+    #[account(
+        mut,
+        seeds = [HISTORY_SEED, &dst_shard_x.to_le_bytes(), &dst_shard_y.to_le_bytes()],
+        bump = history.load()?.bump
+    )]
+    pub history: Option<AccountLoader<'info, ShardHistory>>,
+
+    #[account(
+        mut,
+        seeds = [b"session", signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionAccount>,
+
+    /// XP profile of the session's main wallet; without it placements use tier 0 limits
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, session.main_address.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, PlayerProfile>>,
+
+    /// Reward accrual of the shard's owner, credited for pixels placed by visitors
+    /// Visitor pixels on a shard whose owner has none accrue nothing
+    #[account(
+        mut,
+        seeds = [REWARDS_SEED, shard.creator.as_ref()],
+        bump = owner_rewards.bump,
+    )]
+    pub owner_rewards: Option<Account<'info, RewardAccrual>>,
+
+    /// Faction of the session's main wallet, credited with every pixel placed
+    #[account(
+        mut,
+        seeds = [FACTION_SEED, &faction.id.to_le_bytes()],
+        bump = faction.bump,
+    )]
+    pub faction: Option<Account<'info, Faction>>,
+
+    /// CHECK: The season singleton, read-only (may not exist yet). Checked by load_open_season.
+    #[account(seeds = [SEASON_SEED], bump)]
+    pub season: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// Kind of multi-pixel operation reported by RegionChanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegionOp {
+    FillRect,
+    Line,
+    FloodFill,
+    ChunkWrite,
+    Copy,
+}
+
+/// Source rectangle and transform for copy_region (local coordinates, 0-89)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RegionCopy {
+    /// Top-left corner of the source rectangle
+    pub src_x: u8,
+    pub src_y: u8,
+    /// Source rectangle size
+    pub width: u8,
+    pub height: u8,
+    /// Top-left corner of the destination rectangle
+    pub dst_x: u8,
+    pub dst_y: u8,
+    /// Mirror the source left-right
+    pub flip_x: bool,
+    /// Mirror the source top-bottom
+    pub flip_y: bool,
+    /// Clockwise 90° rotations applied after flipping (0-3)
+    pub quarter_turns: u8,
+    /// Leave destination pixels unchanged where the source is transparent (0)
+    pub skip_transparent: bool,
+}
+
+/// Pixel data for multi-shard placement
+/// Uses global coordinates (0-524,287); the shard is derived from them
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GlobalPixel {
+    /// Global X coordinate
+    pub px: u32,
+    /// Global Y coordinate
+    pub py: u32,
+    /// Color index (1-255, 0 is reserved for transparent)
+    pub color: u8,
+}
+
+/// Pixel data for bulk placement
+/// Uses local coordinates within a shard (0-89)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BulkPixel {
+    /// Local X coordinate within shard (0-89)
+    pub local_x: u8,
+    /// Local Y coordinate within shard (0-89)
+    pub local_y: u8,
+    /// Color index (1-255, 0 is reserved for transparent)
+    pub color: u8,
+}
+
+// ========================================
+// Events
+// ========================================
+
+#[event]
+pub struct PixelChanged {
+    pub px: u32,
+    pub py: u32,
+    pub color: u8,
+    pub painter: Pubkey,
+    pub main_wallet: Pubkey,
+    pub timestamp: u64,
+    /// PixelShard.seq of the shard containing (px, py) after this write
+    pub seq: u64,
+    /// Painter's faction (0 = none or no profile passed)
+    pub faction: u8,
+}
+
+/// One event per bulk write to a shard, in place of a PixelChanged per pixel
+/// `data` holds `count` 21-bit (local index, color) records, encoded as for place_pixels_packed
+#[event]
+pub struct PixelsBulkChanged {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    pub count: u16,
+    pub data: Vec<u8>,
+    pub painter: Pubkey,
+    pub main_wallet: Pubkey,
+    pub timestamp: u64,
+    pub seq: u64,
+}
+
+/// Summary of a drawing primitive; (x0, y0)-(x1, y1) is the inclusive local bounding box
+/// of the affected area. The exact changes precede it as `chunks` PixelsBulkChanged
+/// events with the same seq; a consumer that saw fewer (e.g. truncated logs) should
+/// refetch the shard.
+#[event]
+pub struct RegionChanged {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    pub op: RegionOp,
+    pub x0: u8,
+    pub y0: u8,
+    pub x1: u8,
+    pub y1: u8,
+    /// Fill color, or 0 when the operation wrote mixed colors
+    pub color: u8,
+    pub pixels_changed: u16,
+    /// PixelsBulkChanged events carrying the changed pixels
+    pub chunks: u16,
+    pub painter: Pubkey,
+    pub main_wallet: Pubkey,
+    pub timestamp: u64,
+    pub seq: u64,
+}
+
+/// A session used up its non-owner burst; painting on others' shards is blocked
+/// for `remaining_seconds`
+#[event]
+pub struct CooldownTriggered {
+    pub main_wallet: Pubkey,
+    pub session_key: Pubkey,
+    pub remaining_seconds: u64,
+    pub timestamp: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packed_records_are_21_bits_lsb_first() {
+        assert_eq!(packed_len(1), 3);
+        assert_eq!(packed_len(8), 21);
+        assert_eq!(packed_len(MAX_PACKED_PIXELS as usize), 777);
+
+        let mut data = vec![0u8; packed_len(1)];
+        pack_pixel(&mut data, 0, 1, 1);
+        assert_eq!(data, [0x01, 0x20, 0x00]);
+    }
+
+    #[test]
+    fn pack_pixel_round_trips_at_every_bit_offset() {
+        let records: Vec<(usize, u8)> = (0..16)
+            .map(|n| ((n * 523) % PIXELS_PER_SHARD, (n * 37 + 1) as u8))
+            .chain([(PIXELS_PER_SHARD - 1, 255), (0, 1)])
+            .collect();
+        let mut data = vec![0u8; packed_len(records.len())];
+        for (n, &(id, color)) in records.iter().enumerate() {
+            pack_pixel(&mut data, n, id, color);
+        }
+        for (n, &record) in records.iter().enumerate() {
+            assert_eq!(unpack_pixel(&data, n), record);
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Factions: membership, per-faction scoring and territory
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn initialize_faction(ctx: Context<InitializeFaction>, faction_id: u8) -> Result<()> {
+    require!((1..=FACTION_COUNT).contains(&faction_id), PixelError::InvalidFaction);
+
+    let faction = &mut ctx.accounts.faction;
+    faction.id = faction_id;
+    faction.members = 0;
+    faction.pixels_placed = 0;
+    faction.bump = ctx.bumps.faction;
+
+    let territory = &mut ctx.accounts.faction_territory;
+    territory.id = faction_id;
+    territory.shards = 0;
+    territory.bump = ctx.bumps.faction_territory;
+
+    msg!("Faction {} initialized", faction_id);
+    Ok(())
+}
+
+pub(crate) fn delegate_faction(ctx: Context<DelegateFaction>, faction_id: u8) -> Result<()> {
+    ctx.accounts.delegate_pda(
+        &ctx.accounts.authority,
+        &[FACTION_SEED, &faction_id.to_le_bytes()],
+        DelegateConfig {
+            validator: ctx.remaining_accounts.first().map(|acc| acc.key()),
+            ..Default::default()
+        },
+    )?;
+
+    msg!("Faction {} delegated to ER", faction_id);
+    Ok(())
+}
+
+pub(crate) fn join_faction(ctx: Context<JoinFaction>, faction_id: u8) -> Result<()> {
+    require!((1..=FACTION_COUNT).contains(&faction_id), PixelError::InvalidFaction);
+
+    let profile = &mut ctx.accounts.profile;
+    require!(profile.faction != faction_id, PixelError::InvalidFaction);
+
+    let now = Clock::get()?.unix_timestamp as u64;
+    let previous = profile.faction;
+    if previous != 0 {
+        require!(
+            now.saturating_sub(profile.faction_joined_at) >= FACTION_SWITCH_COOLDOWN,
+            PixelError::FactionSwitchCooldown
+        );
+        let previous_faction = ctx
+            .accounts
+            .previous_faction
+            .as_mut()
+            .ok_or(PixelError::InvalidFaction)?;
+        previous_faction.members = previous_faction.members.saturating_sub(1);
+    }
+
+    ctx.accounts.faction.members += 1;
+    profile.faction = faction_id;
+    profile.faction_joined_at = now;
+
+    msg!("{} joined faction {}", profile.main_wallet, faction_id);
+
+    emit!(FactionJoined {
+        main_wallet: profile.main_wallet,
+        faction: faction_id,
+        previous_faction: previous,
+        timestamp: now,
+    });
+
+    profile.exit(&crate::ID)?;
+    commit_accounts(
+        &ctx.accounts.signer,
+        vec![&ctx.accounts.profile.to_account_info()],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+    )?;
+    Ok(())
+}
+
+pub(crate) fn sync_territory(ctx: Context<SyncTerritory>) -> Result<()> {
+    let profile_info = &ctx.accounts.profile;
+    let faction = if profile_info.data_is_empty() {
+        0
+    } else {
+        require!(
+            profile_info.owner == &crate::ID || profile_info.owner == &DELEGATION_PROGRAM_ID,
+            PixelError::InvalidProfile
+        );
+        PlayerProfile::try_deserialize(&mut &profile_info.data.borrow()[..])?.faction
+    };
+
+    let stats = &mut ctx.accounts.wallet_stats;
+    let previous = stats.territory_faction;
+    if faction == previous {
+        return Ok(());
+    }
+    if previous != 0 {
+        let territory = ctx
+            .accounts
+            .previous_territory
+            .as_mut()
+            .ok_or(PixelError::InvalidFaction)?;
+        territory.shards = territory.shards.saturating_sub(stats.shards_owned);
+    }
+    if faction != 0 {
+        let territory = ctx.accounts.territory.as_mut().ok_or(PixelError::InvalidFaction)?;
+        require!(territory.id == faction, PixelError::InvalidFaction);
+        territory.shards += stats.shards_owned;
+    }
+    stats.territory_faction = faction;
+
+    msg!("{} shards of {} moved to faction {}", stats.shards_owned, stats.owner, faction);
+
+    emit!(TerritoryMoved {
+        wallet: stats.owner,
+        shards: stats.shards_owned,
+        from_faction: previous,
+        to_faction: faction,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+    Ok(())
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Create a faction and its territory counter (base layer)
+#[derive(Accounts)]
+#[instruction(faction_id: u8)]
+pub struct InitializeFaction<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Faction::INIT_SPACE,
+        seeds = [FACTION_SEED, &faction_id.to_le_bytes()],
+        bump
+    )]
+    pub faction: Account<'info, Faction>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FactionTerritory::INIT_SPACE,
+        seeds = [FACTION_TERRITORY_SEED, &faction_id.to_le_bytes()],
+        bump
+    )]
+    pub faction_territory: Account<'info, FactionTerritory>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegate a faction to Ephemeral Rollups
+#[delegate]
+#[derive(Accounts)]
+#[instruction(faction_id: u8)]
+pub struct DelegateFaction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The faction PDA to delegate - validated by seeds constraint
+    #[account(mut, del, seeds = [FACTION_SEED, &faction_id.to_le_bytes()], bump)]
+    pub pda: AccountInfo<'info>,
+}
+
+/// Join or switch factions and commit the profile (ER)
+#[commit]
+#[derive(Accounts)]
+#[instruction(faction_id: u8)]
+pub struct JoinFaction<'info> {
+    #[account(
+        seeds = [b"session", signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionAccount>,
+
+    #[account(
+        mut,
+        seeds = [PROFILE_SEED, session.main_address.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        seeds = [FACTION_SEED, &faction_id.to_le_bytes()],
+        bump = faction.bump,
+    )]
+    pub faction: Account<'info, Faction>,
+
+    /// Faction being left, required when switching
+    #[account(
+        mut,
+        seeds = [FACTION_SEED, &profile.faction.to_le_bytes()],
+        bump = previous_faction.bump,
+    )]
+    pub previous_faction: Option<Account<'info, Faction>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Move a wallet's shards between faction territories (base layer)
+#[derive(Accounts)]
+pub struct SyncTerritory<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_STATS_SEED, wallet_stats.owner.as_ref()],
+        bump = wallet_stats.bump,
+    )]
+    pub wallet_stats: Account<'info, WalletStats>,
+
+    /// CHECK: The wallet's profile, could be delegated. Verified by seeds and custom owner check.
+    #[account(seeds = [PROFILE_SEED, wallet_stats.owner.as_ref()], bump)]
+    pub profile: UncheckedAccount<'info>,
+
+    /// Territory the shards are counted in now, required unless they are in none
+    #[account(
+        mut,
+        seeds = [FACTION_TERRITORY_SEED, &wallet_stats.territory_faction.to_le_bytes()],
+        bump = previous_territory.bump,
+    )]
+    pub previous_territory: Option<Account<'info, FactionTerritory>>,
+
+    /// Territory of the profile's faction, required unless it has left every faction
+    #[account(
+        mut,
+        seeds = [FACTION_TERRITORY_SEED, &territory.id.to_le_bytes()],
+        bump = territory.bump,
+    )]
+    pub territory: Option<Account<'info, FactionTerritory>>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// Faction membership and painting score (delegated to the ER)
+#[account]
+#[derive(InitSpace)]
+pub struct Faction {
+    pub id: u8,
+    pub members: u32,
+    /// Pixels placed by members, on any shard
+    pub pixels_placed: u64,
+    /// Season the season_* counters belong to; they restart when a new season shows up
+    pub season: u32,
+    /// Points scored in `season`, rolled up into the Season by finalize_season
+    pub season_points: u64,
+    /// Pixels placed by members in `season`
+    pub season_pixels: u64,
+    pub bump: u8,
+}
+
+impl Faction {
+    /// Count `count` member pixels and score them in the running season, if any
+    pub(crate) fn record_pixels(&mut self, season: Option<&Season>, on_own_shard: bool, count: u64) {
+        self.pixels_placed += count;
+        let Some(season) = season.filter(|season| season.id != 0) else {
+            return;
+        };
+        if self.season != season.id {
+            self.season = season.id;
+            self.season_points = 0;
+            self.season_pixels = 0;
+        }
+        let points = if on_own_shard {
+            season.rules.points_per_own_pixel
+        } else {
+            season.rules.points_per_enemy_pixel
+        };
+        self.season_points += count * points as u64;
+        self.season_pixels += count;
+    }
+}
+
+/// Shards owned by a faction's members (base layer only, never delegated)
+/// A wallet's shards are counted here while its WalletStats.territory_faction is this id.
+#[account]
+#[derive(InitSpace)]
+pub struct FactionTerritory {
+    pub id: u8,
+    pub shards: u32,
+    pub bump: u8,
+}
+
+// ========================================
+// Events
+// ========================================
+
+/// A wallet joined `faction`, leaving `previous_faction` (0 when it had none)
+#[event]
+pub struct FactionJoined {
+    pub main_wallet: Pubkey,
+    pub faction: u8,
+    pub previous_faction: u8,
+    pub timestamp: u64,
+}
+
+/// `shards` shards of `wallet` moved from faction `from_faction` to `to_faction` (0 = none)
+#[event]
+pub struct TerritoryMoved {
+    pub wallet: Pubkey,
+    pub shards: u32,
+    pub from_faction: u8,
+    pub to_faction: u8,
+    pub timestamp: u64,
+}
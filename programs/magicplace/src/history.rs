@@ -0,0 +1,969 @@
+//! Territory defense: snapshots, the painter layer, the change ring buffer and the shard content hash
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn create_snapshot(ctx: Context<CreateSnapshot>, shard_x: u16, shard_y: u16) -> Result<()> {
+    // Both the shard and the session may be delegated to the ER
+    let shard = ShardView::load(&ctx.accounts.shard)?;
+    require!(
+        shard.shard_x == shard_x && shard.shard_y == shard_y,
+        PixelError::ShardMismatch
+    );
+
+    let session_info = &ctx.accounts.session;
+    require!(
+        session_info.owner == &crate::ID || session_info.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::InvalidAuth
+    );
+    let session = SessionAccount::try_deserialize(&mut &session_info.data.borrow()[..])?;
+    require!(shard.creator == session.main_address, PixelError::NotShardOwner);
+
+    let index = &mut ctx.accounts.snapshot_index;
+    let snapshot_id = index.count;
+    index.shard_x = shard_x;
+    index.shard_y = shard_y;
+    index.count = index.count.checked_add(1).ok_or(PixelError::SnapshotLimit)?;
+    index.bump = ctx.bumps.snapshot_index;
+
+    let snapshot = &mut ctx.accounts.snapshot;
+    snapshot.shard_x = shard_x;
+    snapshot.shard_y = shard_y;
+    snapshot.snapshot_id = snapshot_id;
+    snapshot.version = SNAPSHOT_VERSION;
+    snapshot.taken_by = session.main_address;
+    snapshot.timestamp = Clock::get()?.unix_timestamp as u64;
+    snapshot.pixels = shard.pixels()?.to_vec();
+    snapshot.bump = ctx.bumps.snapshot;
+
+    msg!("Snapshot {} of shard ({}, {}) created", snapshot_id, shard_x, shard_y);
+    Ok(())
+}
+
+pub(crate) fn restore_shard(
+    ctx: Context<RestoreShard>,
+    shard_x: u16,
+    shard_y: u16,
+    snapshot_id: u32,
+) -> Result<()> {
+    let shard = &mut ctx.accounts.shard;
+    let session = &ctx.accounts.session;
+    require!(shard.creator == session.main_address, PixelError::NotShardOwner);
+    load_open_season(&ctx.accounts.season, Clock::get()?.unix_timestamp as u64)?;
+
+    let snapshot = &ctx.accounts.snapshot;
+    require!(snapshot.version == SNAPSHOT_VERSION, PixelError::InvalidSnapshot);
+
+    // Restored pixels no longer belong to whoever painted them last
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        ctx.accounts.painters.as_ref(),
+        ctx.accounts.history.as_ref(),
+        None,
+    )?;
+    let mut changes = ChangeList::new(shard, ctx.accounts.signer.key(), session.main_address)?;
+    for (local_pixel_id, &color) in snapshot.pixels.iter().enumerate() {
+        if shard.pixels[local_pixel_id] != color {
+            writer.write(&mut shard.pixels, local_pixel_id, color);
+            changes.push(local_pixel_id, color);
+        }
+    }
+    let seq = shard.record_write(writer.hash_delta());
+    let (changed, chunks) = changes.finish();
+
+    msg!(
+        "Shard ({}, {}) restored from snapshot {}: {} pixels changed",
+        shard_x, shard_y, snapshot_id, changed
+    );
+
+    emit!(ShardRestored {
+        shard_x,
+        shard_y,
+        snapshot_id,
+        pixels_changed: changed as u16,
+        chunks,
+        main_wallet: session.main_address,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+        seq,
+        content_hash: shard.content_hash,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn initialize_painter_layer(
+    ctx: Context<InitializePainterLayer>,
+    shard_x: u16,
+    shard_y: u16,
+) -> Result<()> {
+    let shard = ShardView::load(&ctx.accounts.shard)?;
+    require!(
+        shard.shard_x == shard_x && shard.shard_y == shard_y,
+        PixelError::ShardMismatch
+    );
+
+    let session_info = &ctx.accounts.session;
+    require!(
+        session_info.owner == &crate::ID || session_info.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::InvalidAuth
+    );
+    let session = SessionAccount::try_deserialize(&mut &session_info.data.borrow()[..])?;
+    require!(shard.creator == session.main_address, PixelError::NotShardOwner);
+
+    let mut painters = ctx.accounts.painters.load_init()?;
+    painters.shard_x = shard_x;
+    painters.shard_y = shard_y;
+    painters.next_slot = 1;
+    painters.bump = ctx.bumps.painters;
+
+    msg!("Painter layer for shard ({}, {}) initialized", shard_x, shard_y);
+    Ok(())
+}
+
+pub(crate) fn delegate_painter_layer(
+    ctx: Context<DelegatePainterLayer>,
+    shard_x: u16,
+    shard_y: u16,
+) -> Result<()> {
+    ctx.accounts.delegate_pda(
+        &ctx.accounts.authority,
+        &[PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        DelegateConfig {
+            validator: ctx.remaining_accounts.first().map(|acc| acc.key()),
+            ..Default::default()
+        },
+    )?;
+
+    msg!("Painter layer for shard ({}, {}) delegated to ER", shard_x, shard_y);
+    Ok(())
+}
+
+pub(crate) fn set_painter_layer(
+    ctx: Context<SetPainterLayer>,
+    shard_x: u16,
+    shard_y: u16,
+    enabled: bool,
+) -> Result<()> {
+    let shard = &mut ctx.accounts.shard;
+    require!(
+        shard.creator == ctx.accounts.session.main_address,
+        PixelError::NotShardOwner
+    );
+
+    if enabled && shard.layers & LAYER_PAINTERS == 0 {
+        let mut painters = ctx.accounts.painters.load_mut()?;
+        painters.painters.fill(Pubkey::default());
+        painters.slots.fill(0);
+        painters.next_slot = 1;
+    }
+    if enabled {
+        shard.layers |= LAYER_PAINTERS;
+    } else {
+        shard.layers &= !LAYER_PAINTERS;
+    }
+
+    msg!("Painter layer for shard ({}, {}) enabled: {}", shard_x, shard_y, enabled);
+    Ok(())
+}
+
+pub(crate) fn revert_painter(
+    ctx: Context<RevertPainter>,
+    shard_x: u16,
+    shard_y: u16,
+    painter: Pubkey,
+) -> Result<()> {
+    let shard = &mut ctx.accounts.shard;
+    let session = &ctx.accounts.session;
+    require!(shard.creator == session.main_address, PixelError::NotShardOwner);
+    load_open_season(&ctx.accounts.season, Clock::get()?.unix_timestamp as u64)?;
+    require!(shard.layers & LAYER_PAINTERS != 0, PixelError::PainterLayerRequired);
+
+    let snapshot = ctx.accounts.snapshot.as_ref();
+    if let Some(snapshot) = snapshot {
+        require!(
+            snapshot.shard_x == shard_x && snapshot.shard_y == shard_y,
+            PixelError::ShardMismatch
+        );
+        require!(snapshot.version == SNAPSHOT_VERSION, PixelError::InvalidSnapshot);
+    }
+
+    // Reverted pixels lose their attribution (written with no painter)
+    let mut writer = PixelWriter::new(
+        shard.layers,
+        Some(&ctx.accounts.painters),
+        ctx.accounts.history.as_ref(),
+        None,
+    )?;
+    let slot = writer.find_slot(&painter).ok_or(PixelError::PainterNotFound)?;
+
+    let mut changes = ChangeList::new(shard, ctx.accounts.signer.key(), session.main_address)?;
+    for local_pixel_id in 0..PIXELS_PER_SHARD {
+        if writer.painter_slot(local_pixel_id) != slot {
+            continue;
+        }
+        let color = snapshot.map_or(0, |snapshot| snapshot.pixels[local_pixel_id]);
+        writer.write(&mut shard.pixels, local_pixel_id, color);
+        changes.push(local_pixel_id, color);
+    }
+    let seq = shard.record_write(writer.hash_delta());
+    let (reverted, chunks) = changes.finish();
+
+    msg!(
+        "Reverted {} pixels by {} on shard ({}, {})",
+        reverted, painter, shard_x, shard_y
+    );
+
+    emit!(PainterReverted {
+        shard_x,
+        shard_y,
+        painter,
+        snapshot_id: snapshot.map(|snapshot| snapshot.snapshot_id),
+        pixels_reverted: reverted as u16,
+        chunks,
+        main_wallet: session.main_address,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+        seq,
+        content_hash: shard.content_hash,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn initialize_history(
+    ctx: Context<InitializeHistory>,
+    shard_x: u16,
+    shard_y: u16,
+) -> Result<()> {
+    let shard = ShardView::load(&ctx.accounts.shard)?;
+    require!(
+        shard.shard_x == shard_x && shard.shard_y == shard_y,
+        PixelError::ShardMismatch
+    );
+
+    let session_info = &ctx.accounts.session;
+    require!(
+        session_info.owner == &crate::ID || session_info.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::InvalidAuth
+    );
+    let session = SessionAccount::try_deserialize(&mut &session_info.data.borrow()[..])?;
+    require!(shard.creator == session.main_address, PixelError::NotShardOwner);
+
+    let mut history = ctx.accounts.history.load_init()?;
+    history.shard_x = shard_x;
+    history.shard_y = shard_y;
+    history.bump = ctx.bumps.history;
+
+    msg!("History for shard ({}, {}) initialized", shard_x, shard_y);
+    Ok(())
+}
+
+pub(crate) fn delegate_history(ctx: Context<DelegateHistory>, shard_x: u16, shard_y: u16) -> Result<()> {
+    ctx.accounts.delegate_pda(
+        &ctx.accounts.authority,
+        &[HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        DelegateConfig {
+            validator: ctx.remaining_accounts.first().map(|acc| acc.key()),
+            ..Default::default()
+        },
+    )?;
+
+    msg!("History for shard ({}, {}) delegated to ER", shard_x, shard_y);
+    Ok(())
+}
+
+pub(crate) fn set_history_layer(
+    ctx: Context<SetHistoryLayer>,
+    shard_x: u16,
+    shard_y: u16,
+    enabled: bool,
+) -> Result<()> {
+    let shard = &mut ctx.accounts.shard;
+    require!(
+        shard.creator == ctx.accounts.session.main_address,
+        PixelError::NotShardOwner
+    );
+
+    if enabled && shard.layers & LAYER_HISTORY == 0 {
+        let mut history = ctx.accounts.history.load_mut()?;
+        history.head = 0;
+        history.len = 0;
+    }
+    if enabled {
+        shard.layers |= LAYER_HISTORY;
+    } else {
+        shard.layers &= !LAYER_HISTORY;
+    }
+
+    msg!("History for shard ({}, {}) enabled: {}", shard_x, shard_y, enabled);
+    Ok(())
+}
+
+pub(crate) fn undo_last(ctx: Context<UndoLast>, shard_x: u16, shard_y: u16, count: u16) -> Result<()> {
+    let shard = &mut ctx.accounts.shard;
+    let session = &ctx.accounts.session;
+    require!(shard.creator == session.main_address, PixelError::NotShardOwner);
+    load_open_season(&ctx.accounts.season, Clock::get()?.unix_timestamp as u64)?;
+    require!(shard.layers & LAYER_HISTORY != 0, PixelError::HistoryLayerRequired);
+
+    let mut history = ctx.accounts.history.load_mut()?;
+    let mut painters = if shard.layers & LAYER_PAINTERS != 0 {
+        let painters = ctx.accounts.painters.as_ref().ok_or(PixelError::PainterLayerRequired)?;
+        Some(painters.load_mut()?)
+    } else {
+        None
+    };
+
+    let mut changes = ChangeList::new(shard, ctx.accounts.signer.key(), session.main_address)?;
+    let mut hash_delta: u64 = 0;
+    while changes.len() < count as usize {
+        let Some(change) = history.pop() else {
+            break;
+        };
+        let local_pixel_id = change.local_pixel_id as usize;
+        hash_delta = hash_delta
+            .wrapping_sub(pixel_hash(local_pixel_id, shard.pixels[local_pixel_id]))
+            .wrapping_add(pixel_hash(local_pixel_id, change.old_color));
+        shard.pixels[local_pixel_id] = change.old_color;
+        if let Some(painters) = painters.as_mut() {
+            painters.slots[local_pixel_id] = 0;
+        }
+        changes.push(local_pixel_id, change.old_color);
+    }
+    let seq = shard.record_write(hash_delta);
+    let (undone, chunks) = changes.finish();
+
+    msg!("Undid {} changes on shard ({}, {})", undone, shard_x, shard_y);
+
+    emit!(ChangesUndone {
+        shard_x,
+        shard_y,
+        changes_undone: undone as u16,
+        chunks,
+        main_wallet: session.main_address,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+        seq,
+        content_hash: shard.content_hash,
+    });
+
+    Ok(())
+}
+
+// ========================================
+// Helpers
+// ========================================
+
+/// Contribution of one pixel to PixelShard.content_hash (0 for transparent pixels)
+/// The content hash is the wrapping sum of this over every pixel, so clients can
+/// recompute it from the pixel buffer alone while writes update it in O(1).
+/// Mixing is the splitmix64 finalizer over (local_pixel_id << 8 | color).
+pub(crate) fn pixel_hash(local_pixel_id: usize, color: u8) -> u64 {
+    if color == 0 {
+        return 0;
+    }
+    let mut z = ((local_pixel_id as u64) << 8 | color as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Content hash of a whole pixel buffer, equal to PixelShard.content_hash for those pixels
+pub fn shard_content_hash(pixels: &[u8]) -> u64 {
+    pixels
+        .iter()
+        .enumerate()
+        .fold(0, |hash, (local_pixel_id, &color)| hash.wrapping_add(pixel_hash(local_pixel_id, color)))
+}
+
+/// Painter layer of a shard, read in place so delegated layers work on the base layer
+/// (AccountLoader requires the account to be owned by this program). Read-only: yields
+/// the last committed state of a delegated layer.
+pub(crate) fn load_painter_layer<'a>(info: &'a AccountInfo, shard: &ShardView) -> Result<Ref<'a, ShardPainters>> {
+    require!(
+        info.owner == &crate::ID || info.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::PainterLayerRequired
+    );
+    let data = info.try_borrow_data()?;
+    let end = 8 + std::mem::size_of::<ShardPainters>();
+    require!(
+        data.len() >= end && data[..8] == *ShardPainters::DISCRIMINATOR,
+        PixelError::PainterLayerRequired
+    );
+    let layer = Ref::map(data, |data| bytemuck::from_bytes::<ShardPainters>(&data[8..end]));
+    require!(
+        layer.shard_x == shard.shard_x && layer.shard_y == shard.shard_y,
+        PixelError::PainterLayerRequired
+    );
+    let expected = Pubkey::create_program_address(
+        &[PAINTERS_SEED, &shard.shard_x.to_le_bytes(), &shard.shard_y.to_le_bytes(), &[layer.bump]],
+        &crate::ID,
+    )
+    .map_err(|_| PixelError::PainterLayerRequired)?;
+    require_keys_eq!(info.key(), expected, PixelError::PainterLayerRequired);
+    Ok(layer)
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Create the next numbered snapshot of a shard (base layer)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct CreateSnapshot<'info> {
+    /// CHECK: The shard, could be delegated. Verified by seeds and loaded via ShardView.
+    #[account(
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump
+    )]
+    pub shard: UncheckedAccount<'info>,
+
+    /// Per-shard snapshot counter, created with the first snapshot
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ShardSnapshotIndex::INIT_SPACE,
+        seeds = [SNAPSHOT_INDEX_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump
+    )]
+    pub snapshot_index: Account<'info, ShardSnapshotIndex>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ShardSnapshot::INIT_SPACE,
+        seeds = [
+            SNAPSHOT_SEED,
+            &shard_x.to_le_bytes(),
+            &shard_y.to_le_bytes(),
+            &snapshot_index.count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub snapshot: Account<'info, ShardSnapshot>,
+
+    /// CHECK: The session account, could be delegated. Verified by seeds and custom owner check.
+    #[account(
+        seeds = [b"session", authority.key().as_ref()],
+        bump,
+    )]
+    pub session: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Restore a delegated shard from one of its snapshots (ER)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16, snapshot_id: u32)]
+pub struct RestoreShard<'info> {
+    #[account(
+        mut,
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = shard.bump
+    )]
+    pub shard: Account<'info, PixelShard>,
+
+    #[account(
+        seeds = [SNAPSHOT_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes(), &snapshot_id.to_le_bytes()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, ShardSnapshot>,
+
+    /// Painter attribution layer, required when the shard has one enabled
+    #[account(
+        mut,
+        seeds = [PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = painters.load()?.bump
+    )]
+    pub painters: Option<AccountLoader<'info, ShardPainters>>,
+
+    /// Change history ring buffer, required when the shard has one enabled
+    #[account(
+        mut,
+        seeds = [HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = history.load()?.bump
+    )]
+    pub history: Option<AccountLoader<'info, ShardHistory>>,
+
+    #[account(
+        seeds = [b"session", signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionAccount>,
+
+    /// CHECK: The season singleton, read-only (may not exist yet). Checked by load_open_season.
+    #[account(seeds = [SEASON_SEED], bump)]
+    pub season: UncheckedAccount<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Create the painter attribution layer of a shard (base layer)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct InitializePainterLayer<'info> {
+    /// CHECK: The shard, could be delegated. Verified by seeds and loaded via ShardView.
+    #[account(
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump
+    )]
+    pub shard: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ShardPainters>(),
+        seeds = [PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump
+    )]
+    pub painters: AccountLoader<'info, ShardPainters>,
+
+    /// CHECK: The session account, could be delegated. Verified by seeds and custom owner check.
+    #[account(
+        seeds = [b"session", authority.key().as_ref()],
+        bump,
+    )]
+    pub session: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegate a shard's painter layer to Ephemeral Rollups
+#[delegate]
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct DelegatePainterLayer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The painter layer PDA to delegate - validated by seeds constraint
+    #[account(mut, del, seeds = [PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()], bump)]
+    pub pda: AccountInfo<'info>,
+}
+
+/// Toggle painter attribution on a shard (ER)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct SetPainterLayer<'info> {
+    #[account(
+        mut,
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = shard.bump
+    )]
+    pub shard: Account<'info, PixelShard>,
+
+    #[account(
+        mut,
+        seeds = [PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = painters.load()?.bump
+    )]
+    pub painters: AccountLoader<'info, ShardPainters>,
+
+    #[account(
+        seeds = [b"session", signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionAccount>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Revert the pixels of one painter on a shard (ER)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct RevertPainter<'info> {
+    #[account(
+        mut,
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = shard.bump
+    )]
+    pub shard: Account<'info, PixelShard>,
+
+    #[account(
+        mut,
+        seeds = [PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = painters.load()?.bump
+    )]
+    pub painters: AccountLoader<'info, ShardPainters>,
+
+    /// Snapshot to revert to; pixels are cleared when omitted
+    pub snapshot: Option<Account<'info, ShardSnapshot>>,
+
+    /// Change history ring buffer, required when the shard has one enabled
+    #[account(
+        mut,
+        seeds = [HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = history.load()?.bump
+    )]
+    pub history: Option<AccountLoader<'info, ShardHistory>>,
+
+    #[account(
+        seeds = [b"session", signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionAccount>,
+
+    /// CHECK: The season singleton, read-only (may not exist yet). Checked by load_open_season.
+    #[account(seeds = [SEASON_SEED], bump)]
+    pub season: UncheckedAccount<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Create the change history of a shard (base layer)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct InitializeHistory<'info> {
+    /// CHECK: The shard, could be delegated. Verified by seeds and loaded via ShardView.
+    #[account(
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump
+    )]
+    pub shard: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<ShardHistory>(),
+        seeds = [HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump
+    )]
+    pub history: AccountLoader<'info, ShardHistory>,
+
+    /// CHECK: The session account, could be delegated. Verified by seeds and custom owner check.
+    #[account(
+        seeds = [b"session", authority.key().as_ref()],
+        bump,
+    )]
+    pub session: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegate a shard's change history to Ephemeral Rollups
+#[delegate]
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct DelegateHistory<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The history PDA to delegate - validated by seeds constraint
+    #[account(mut, del, seeds = [HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()], bump)]
+    pub pda: AccountInfo<'info>,
+}
+
+/// Toggle change recording on a shard (ER)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct SetHistoryLayer<'info> {
+    #[account(
+        mut,
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = shard.bump
+    )]
+    pub shard: Account<'info, PixelShard>,
+
+    #[account(
+        mut,
+        seeds = [HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = history.load()?.bump
+    )]
+    pub history: AccountLoader<'info, ShardHistory>,
+
+    #[account(
+        seeds = [b"session", signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionAccount>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Undo the most recent changes on a shard (ER)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct UndoLast<'info> {
+    #[account(
+        mut,
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = shard.bump
+    )]
+    pub shard: Account<'info, PixelShard>,
+
+    #[account(
+        mut,
+        seeds = [HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = history.load()?.bump
+    )]
+    pub history: AccountLoader<'info, ShardHistory>,
+
+    /// Painter attribution layer, required when the shard has one enabled
+    #[account(
+        mut,
+        seeds = [PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = painters.load()?.bump
+    )]
+    pub painters: Option<AccountLoader<'info, ShardPainters>>,
+
+    #[account(
+        seeds = [b"session", signer.key().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, SessionAccount>,
+
+    /// CHECK: The season singleton, read-only (may not exist yet). Checked by load_open_season.
+    #[account(seeds = [SEASON_SEED], bump)]
+    pub season: UncheckedAccount<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// Per-pixel "last painter" layer of a shard, used to undo one griefer's damage
+/// Zero-copy so it can be loaded next to the shard without another 8KB heap copy.
+/// Painters are main wallets; when all 63 slots are taken the oldest one is recycled
+/// and the pixels it still owned lose their attribution.
+#[account(zero_copy(unsafe))]
+#[repr(C)]
+pub struct ShardPainters {
+    /// Main wallet of each slot; slot N is stored at painters[N - 1]
+    pub painters: [Pubkey; MAX_PAINTER_SLOTS],
+    /// Slot of the last painter of each pixel (0 = unknown), same indexing as PixelShard.pixels
+    pub slots: [u8; PIXELS_PER_SHARD],
+    pub shard_x: u16,
+    pub shard_y: u16,
+    /// Next slot to hand out (1-63), wrapping around to recycle the oldest
+    pub next_slot: u8,
+    pub bump: u8,
+}
+
+impl ShardPainters {
+    /// Slot of a main wallet, if it has one
+    pub(crate) fn find_slot(&self, main_wallet: &Pubkey) -> Option<u8> {
+        self.painters
+            .iter()
+            .position(|painter| painter == main_wallet)
+            .map(|index| index as u8 + 1)
+    }
+
+    /// Slot of a main wallet, registering it in the next (possibly recycled) slot if needed
+    pub(crate) fn slot_for(&mut self, main_wallet: &Pubkey) -> u8 {
+        if let Some(slot) = self.find_slot(main_wallet) {
+            return slot;
+        }
+
+        let slot = self.next_slot;
+        if self.painters[slot as usize - 1] != Pubkey::default() {
+            for pixel_slot in self.slots.iter_mut().filter(|pixel_slot| **pixel_slot == slot) {
+                *pixel_slot = 0;
+            }
+        }
+        self.painters[slot as usize - 1] = *main_wallet;
+        self.next_slot = slot % MAX_PAINTER_SLOTS as u8 + 1;
+        slot
+    }
+}
+
+/// One recorded pixel change
+#[zero_copy(unsafe)]
+#[repr(C)]
+pub struct PixelChange {
+    pub timestamp: u64,
+    /// Same indexing as PixelShard.pixels
+    pub local_pixel_id: u16,
+    pub old_color: u8,
+    pub new_color: u8,
+    /// Painter layer slot of whoever made the change (0 = unknown)
+    pub painter_slot: u8,
+    pub _padding: [u8; 3],
+}
+
+/// Ring buffer of the most recent pixel changes on a shard, used by undo_last
+/// Zero-copy for the same reason as ShardPainters. Once full, new changes
+/// overwrite the oldest ones.
+#[account(zero_copy(unsafe))]
+#[repr(C)]
+pub struct ShardHistory {
+    pub changes: [PixelChange; HISTORY_CAPACITY],
+    pub shard_x: u16,
+    pub shard_y: u16,
+    /// Index the next change is written to
+    pub head: u16,
+    /// Number of recorded changes (at most HISTORY_CAPACITY)
+    pub len: u16,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+}
+
+impl ShardHistory {
+    /// Record a change, overwriting the oldest one when full
+    pub(crate) fn push(&mut self, change: PixelChange) {
+        self.changes[self.head as usize] = change;
+        self.head = (self.head + 1) % HISTORY_CAPACITY as u16;
+        self.len = (self.len + 1).min(HISTORY_CAPACITY as u16);
+    }
+
+    /// Remove and return the newest change
+    fn pop(&mut self) -> Option<PixelChange> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head = (self.head + HISTORY_CAPACITY as u16 - 1) % HISTORY_CAPACITY as u16;
+        self.len -= 1;
+        Some(self.changes[self.head as usize])
+    }
+}
+
+/// Copy of a shard's pixels at a point in time, used to restore vandalized territory
+#[account]
+#[derive(InitSpace)]
+pub struct ShardSnapshot {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    /// Sequential per-shard snapshot number (0, 1, 2, ...)
+    pub snapshot_id: u32,
+    /// Layout version of the snapshot data
+    pub version: u8,
+    /// Main wallet of the owner who took the snapshot
+    pub taken_by: Pubkey,
+    pub timestamp: u64,
+    /// Same layout as PixelShard.pixels
+    #[max_len(8100)]
+    pub pixels: Vec<u8>,
+    pub bump: u8,
+}
+
+/// Per-shard snapshot counter; `count` is the id of the next snapshot
+#[account]
+#[derive(InitSpace)]
+pub struct ShardSnapshotIndex {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    pub count: u32,
+    pub bump: u8,
+}
+
+// ========================================
+// Events
+// ========================================
+
+#[event]
+pub struct ShardRestored {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    pub snapshot_id: u32,
+    pub pixels_changed: u16,
+    /// PixelsBulkChanged events carrying the changed pixels
+    pub chunks: u16,
+    pub main_wallet: Pubkey,
+    pub timestamp: u64,
+    pub seq: u64,
+    /// PixelShard.content_hash after the restore
+    pub content_hash: u64,
+}
+
+#[event]
+pub struct PainterReverted {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    /// Main wallet whose pixels were reverted
+    pub painter: Pubkey,
+    /// Snapshot the pixels were reverted to, or None when they were cleared
+    pub snapshot_id: Option<u32>,
+    pub pixels_reverted: u16,
+    /// PixelsBulkChanged events carrying the reverted pixels
+    pub chunks: u16,
+    pub main_wallet: Pubkey,
+    pub timestamp: u64,
+    pub seq: u64,
+    /// PixelShard.content_hash after the revert
+    pub content_hash: u64,
+}
+
+#[event]
+pub struct ChangesUndone {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    /// Number of changes actually rolled back (may be less than requested)
+    pub changes_undone: u16,
+    /// PixelsBulkChanged events carrying the rolled back changes, in undo order
+    pub chunks: u16,
+    pub main_wallet: Pubkey,
+    pub timestamp: u64,
+    pub seq: u64,
+    /// PixelShard.content_hash after the undo
+    pub content_hash: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(local_pixel_id: u16) -> PixelChange {
+        PixelChange {
+            timestamp: local_pixel_id as u64,
+            local_pixel_id,
+            old_color: 0,
+            new_color: 1,
+            painter_slot: 0,
+            _padding: [0; 3],
+        }
+    }
+
+    #[test]
+    fn history_pops_newest_first_and_drops_the_oldest_when_full() {
+        let mut history: ShardHistory = bytemuck::Zeroable::zeroed();
+        for id in 0..HISTORY_CAPACITY as u16 + 3 {
+            history.push(change(id));
+        }
+        assert_eq!(history.len, HISTORY_CAPACITY as u16);
+        assert_eq!(history.head, 3);
+
+        // Popping walks back across the wraparound down to the oldest surviving change
+        for id in (3..HISTORY_CAPACITY as u16 + 3).rev() {
+            assert_eq!(history.pop().map(|c| c.local_pixel_id), Some(id));
+        }
+        assert!(history.pop().is_none());
+        assert_eq!(history.head, 3);
+    }
+
+    #[test]
+    fn painter_slots_recycle_the_oldest_and_clear_its_pixels() {
+        let mut painters: ShardPainters = bytemuck::Zeroable::zeroed();
+        painters.next_slot = 1;
+        let wallets: Vec<Pubkey> = (1..=MAX_PAINTER_SLOTS as u8 + 1)
+            .map(|i| Pubkey::new_from_array([i; 32]))
+            .collect();
+
+        for (i, wallet) in wallets[..MAX_PAINTER_SLOTS].iter().enumerate() {
+            assert_eq!(painters.slot_for(wallet), i as u8 + 1);
+        }
+        assert_eq!(painters.slot_for(&wallets[4]), 5);
+        assert_eq!(painters.next_slot, 1);
+
+        painters.slots[10] = 1;
+        painters.slots[11] = 2;
+        let newcomer = wallets[MAX_PAINTER_SLOTS];
+        assert_eq!(painters.slot_for(&newcomer), 1);
+        assert_eq!(painters.next_slot, 2);
+        assert_eq!(painters.find_slot(&wallets[0]), None);
+        assert_eq!(painters.find_slot(&newcomer), Some(1));
+        assert_eq!(painters.slots[10], 0);
+        assert_eq!(painters.slots[11], 2);
+    }
+}
@@ -0,0 +1,274 @@
+//! On-chain top-N leaderboards
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn initialize_leaderboard(
+    ctx: Context<InitializeLeaderboard>,
+    metric: LeaderboardMetric,
+) -> Result<()> {
+    let leaderboard = &mut ctx.accounts.leaderboard;
+    leaderboard.metric = metric;
+    leaderboard.entries = Vec::new();
+    leaderboard.bump = ctx.bumps.leaderboard;
+
+    msg!("Leaderboard {:?} initialized", metric);
+    Ok(())
+}
+
+pub(crate) fn delegate_leaderboard(
+    ctx: Context<DelegateLeaderboard>,
+    metric: LeaderboardMetric,
+) -> Result<()> {
+    ctx.accounts.delegate_pda(
+        &ctx.accounts.authority,
+        &[LEADERBOARD_SEED, &[metric as u8]],
+        DelegateConfig {
+            validator: ctx.remaining_accounts.first().map(|acc| acc.key()),
+            ..Default::default()
+        },
+    )?;
+
+    msg!("Leaderboard {:?} delegated to ER", metric);
+    Ok(())
+}
+
+pub(crate) fn submit_score(ctx: Context<SubmitScore>, metric: LeaderboardMetric) -> Result<()> {
+    let (wallet, score) = leaderboard_score(metric, &ctx.accounts.source)?;
+
+    let leaderboard = &mut ctx.accounts.leaderboard;
+    match leaderboard.submit(wallet, score) {
+        Some(rank) => {
+            msg!("{:?}: {} ranked {} with {}", metric, wallet, rank + 1, score);
+            emit!(LeaderboardUpdated {
+                metric,
+                wallet,
+                score,
+                rank,
+                timestamp: Clock::get()?.unix_timestamp as u64,
+            });
+        }
+        None => msg!("{:?}: {} does not qualify with {}", metric, wallet, score),
+    }
+    Ok(())
+}
+
+// ========================================
+// Helpers
+// ========================================
+
+/// Wallet and score for `metric` from its counter account (a PDA of this program,
+/// delegated or not)
+pub(crate) fn leaderboard_score(metric: LeaderboardMetric, source: &AccountInfo) -> Result<(Pubkey, u64)> {
+    require!(
+        source.owner == &crate::ID || source.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::InvalidScoreSource
+    );
+    let data = source.data.borrow();
+    let (seed, wallet, bump, score) = match metric {
+        LeaderboardMetric::ShardsOwned => {
+            let stats = WalletStats::try_deserialize(&mut &data[..])?;
+            (WALLET_STATS_SEED, stats.owner, stats.bump, stats.shards_owned as u64)
+        }
+        LeaderboardMetric::EnemyPixels => {
+            let profile = PlayerProfile::try_deserialize(&mut &data[..])?;
+            (PROFILE_SEED, profile.main_wallet, profile.bump, profile.pixels_on_others)
+        }
+        LeaderboardMetric::Xp => {
+            let profile = PlayerProfile::try_deserialize(&mut &data[..])?;
+            (PROFILE_SEED, profile.main_wallet, profile.bump, profile.xp)
+        }
+        LeaderboardMetric::RewardsEarned => {
+            let rewards = RewardAccrual::try_deserialize(&mut &data[..])?;
+            (REWARDS_SEED, rewards.owner, rewards.bump, rewards.accrued_pixels * REWARD_PER_PIXEL)
+        }
+    };
+
+    // The account must be the wallet's own PDA, not a copy with forged contents
+    let expected = Pubkey::create_program_address(&[seed, wallet.as_ref(), &[bump]], &crate::ID)
+        .map_err(|_| PixelError::InvalidScoreSource)?;
+    require_keys_eq!(source.key(), expected, PixelError::InvalidScoreSource);
+    Ok((wallet, score))
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Create a metric's leaderboard (base layer)
+#[derive(Accounts)]
+#[instruction(metric: LeaderboardMetric)]
+pub struct InitializeLeaderboard<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [LEADERBOARD_SEED, &[metric as u8]],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegate a leaderboard to Ephemeral Rollups
+#[delegate]
+#[derive(Accounts)]
+#[instruction(metric: LeaderboardMetric)]
+pub struct DelegateLeaderboard<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The leaderboard PDA to delegate - validated by seeds constraint
+    #[account(mut, del, seeds = [LEADERBOARD_SEED, &[metric as u8]], bump)]
+    pub pda: AccountInfo<'info>,
+}
+
+/// Submit a score read from a counter account (ER)
+#[derive(Accounts)]
+#[instruction(metric: LeaderboardMetric)]
+pub struct SubmitScore<'info> {
+    #[account(
+        mut,
+        seeds = [LEADERBOARD_SEED, &[metric as u8]],
+        bump = leaderboard.bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    /// CHECK: The wallet's counter account for the metric, could be delegated.
+    /// Verified by owner check, discriminator and PDA address in leaderboard_score.
+    pub source: UncheckedAccount<'info>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// Top LEADERBOARD_SIZE wallets for one metric, best first (delegated to the ER)
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub metric: LeaderboardMetric,
+    #[max_len(LEADERBOARD_SIZE)]
+    pub entries: Vec<LeaderboardEntry>,
+    pub bump: u8,
+}
+
+impl Leaderboard {
+    /// Insert or update `wallet` and return its 0-based rank, or None if it does not
+    /// make the board. Order is score descending, then wallet ascending.
+    fn submit(&mut self, wallet: Pubkey, score: u64) -> Option<u8> {
+        if score == 0 {
+            return None;
+        }
+        if let Some(i) = self.entries.iter().position(|e| e.wallet == wallet) {
+            self.entries.remove(i);
+        } else if self.entries.len() >= LEADERBOARD_SIZE {
+            if score <= self.entries[LEADERBOARD_SIZE - 1].score {
+                return None;
+            }
+            self.entries.pop();
+        }
+
+        let rank = self
+            .entries
+            .iter()
+            .position(|e| e.score < score || (e.score == score && e.wallet > wallet))
+            .unwrap_or(self.entries.len());
+        self.entries.insert(rank, LeaderboardEntry { wallet, score });
+        Some(rank as u8)
+    }
+}
+
+/// Metric a leaderboard ranks wallets by
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum LeaderboardMetric {
+    /// WalletStats.shards_owned
+    ShardsOwned,
+    /// PlayerProfile.pixels_on_others
+    EnemyPixels,
+    /// PlayerProfile.xp
+    Xp,
+    /// Reward token base units earned from visitors (RewardAccrual)
+    RewardsEarned,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct LeaderboardEntry {
+    pub wallet: Pubkey,
+    pub score: u64,
+}
+
+// ========================================
+// Events
+// ========================================
+
+/// `wallet` now ranks `rank` (0 = first) on the `metric` leaderboard
+#[event]
+pub struct LeaderboardUpdated {
+    pub metric: LeaderboardMetric,
+    pub wallet: Pubkey,
+    pub score: u64,
+    pub rank: u8,
+    pub timestamp: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wallet(i: u8) -> Pubkey {
+        Pubkey::new_from_array([i; 32])
+    }
+
+    fn board() -> Leaderboard {
+        Leaderboard {
+            metric: LeaderboardMetric::Xp,
+            entries: Vec::new(),
+            bump: 0,
+        }
+    }
+
+    fn ranking(board: &Leaderboard) -> Vec<(u8, u64)> {
+        board.entries.iter().map(|e| (e.wallet.to_bytes()[0], e.score)).collect()
+    }
+
+    #[test]
+    fn submit_orders_by_score_then_wallet() {
+        let mut board = board();
+        assert_eq!(board.submit(wallet(1), 0), None);
+        assert_eq!(board.submit(wallet(3), 10), Some(0));
+        assert_eq!(board.submit(wallet(1), 5), Some(1));
+        assert_eq!(board.submit(wallet(2), 10), Some(0));
+        assert_eq!(ranking(&board), [(2, 10), (3, 10), (1, 5)]);
+
+        // Resubmitting moves the wallet instead of adding it twice
+        assert_eq!(board.submit(wallet(1), 20), Some(0));
+        assert_eq!(board.submit(wallet(2), 1), Some(2));
+        assert_eq!(ranking(&board), [(1, 20), (3, 10), (2, 1)]);
+    }
+
+    #[test]
+    fn submit_evicts_the_last_entry_of_a_full_board() {
+        let mut board = board();
+        for i in 0..LEADERBOARD_SIZE as u8 {
+            board.submit(wallet(i + 1), 100 + i as u64);
+        }
+        assert_eq!(board.entries.len(), LEADERBOARD_SIZE);
+
+        // Tying the last entry isn't enough to make the board
+        assert_eq!(board.submit(wallet(200), 100), None);
+        assert_eq!(board.entries.len(), LEADERBOARD_SIZE);
+
+        assert_eq!(board.submit(wallet(201), 101), Some(LEADERBOARD_SIZE as u8 - 1));
+        assert_eq!(board.entries.len(), LEADERBOARD_SIZE);
+        assert!(board.entries.iter().all(|e| e.wallet != wallet(1)));
+        assert_eq!(board.entries[LEADERBOARD_SIZE - 2].wallet, wallet(2));
+    }
+}
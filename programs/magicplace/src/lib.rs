@@ -1,5 +1,13 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
 use anchor_lang::solana_program::sysvar::instructions::{self, load_instruction_at_checked};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_2022::spl_token_2022::{self, extension::ExtensionType};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, NonTransferableMintInitialize};
+use std::cell::{Ref, RefMut};
 
 /// Ed25519 program ID: Ed25519SigVerify111111111111111111111111111
 const ED25519_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
@@ -9,12 +17,37 @@ const ED25519_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
 ]);
 use ephemeral_rollups_sdk::anchor::{commit, delegate, ephemeral};
 use ephemeral_rollups_sdk::cpi::DelegateConfig;
-use ephemeral_rollups_sdk::ephem::commit_accounts;
+use ephemeral_rollups_sdk::ephem::{commit_accounts, commit_and_undelegate_accounts};
+use solana_sha256_hasher::hashv;
 
 declare_id!("4j29Do6VWdMhfLBdi4n3AeWdVXNEzJNG72sFVUe9cUSe");
 
 const DELEGATION_PROGRAM_ID: Pubkey = pubkey!("DELeGGvXpWV2fqJUhqcF5ZSYMS4JTLjteaAMARRSaeSh");
 
+mod artwork;
+mod checkpoint;
+mod drawing;
+mod factions;
+mod history;
+mod leaderboards;
+mod profile;
+mod rewards;
+mod seasons;
+mod templates;
+mod views;
+
+pub use artwork::*;
+pub use checkpoint::*;
+pub use drawing::*;
+pub use factions::*;
+pub use history::*;
+pub use leaderboards::*;
+pub use profile::*;
+pub use rewards::*;
+pub use seasons::*;
+pub use templates::*;
+pub use views::*;
+
 // ========================================
 // Canvas Configuration - 524,288 x 524,288 with dynamic sharding
 // ========================================
@@ -26,7 +59,7 @@ const CANVAS_RES: u32 = 524288;
 const SHARD_DIMENSION: u32 = 90;
 
 /// Number of shards per dimension (ceiling division: 524,288 / 90 = 5,826)
-const SHARDS_PER_DIM: u32 = (CANVAS_RES + SHARD_DIMENSION - 1) / SHARD_DIMENSION;
+const SHARDS_PER_DIM: u32 = CANVAS_RES.div_ceil(SHARD_DIMENSION);
 
 /// Total pixels stored in each shard (90 * 90 = 8,100)
 const PIXELS_PER_SHARD: usize = (SHARD_DIMENSION * SHARD_DIMENSION) as usize;
@@ -37,6 +70,185 @@ const BYTES_PER_SHARD: usize = PIXELS_PER_SHARD;
 /// Seed prefix for shard PDAs
 const SHARD_SEED: &[u8] = b"shard";
 
+/// Seed prefix for shard snapshot PDAs
+const SNAPSHOT_SEED: &[u8] = b"snapshot";
+
+/// Seed prefix for per-shard snapshot counters
+const SNAPSHOT_INDEX_SEED: &[u8] = b"snapshots";
+
+/// Layout version written into new snapshots
+const SNAPSHOT_VERSION: u8 = 1;
+
+/// Seed prefix for per-shard painter attribution layers
+const PAINTERS_SEED: &[u8] = b"painters";
+
+/// Painter table size per shard; slot 0 means "no recorded painter"
+const MAX_PAINTER_SLOTS: usize = 63;
+
+/// Seed prefix for per-shard change history ring buffers
+const HISTORY_SEED: &[u8] = b"history";
+
+/// Number of recent changes kept per shard
+const HISTORY_CAPACITY: usize = 256;
+
+/// PixelShard.layers flag: writes maintain the shard's ShardPainters account
+const LAYER_PAINTERS: u8 = 1 << 0;
+
+/// PixelShard.layers flag: writes append to the shard's ShardHistory ring buffer
+const LAYER_HISTORY: u8 = 1 << 1;
+
+/// Seed of the global canvas state singleton
+const CANVAS_SEED: &[u8] = b"canvas";
+
+/// Seed prefix for canvas checkpoints
+const CHECKPOINT_SEED: &[u8] = b"checkpoint";
+
+/// Depth of the checkpoint Merkle tree (2^26 leaves covers all 33,942,276 shards)
+const CHECKPOINT_DEPTH: usize = 26;
+
+/// Seed prefix for partner registry entries
+const PARTNER_SEED: &[u8] = b"partner";
+
+/// Seed of the PDA a partner program signs with when painting through CPI
+pub const PARTNER_AUTHORITY_SEED: &[u8] = b"magicplace_partner";
+
+/// Seed (under the hook program) of a shard hook's validation account
+pub const HOOK_VALIDATION_SEED: &[u8] = b"paint-hook";
+
+/// Instruction discriminator of a hook's `on_paint` (Anchor: sha256("global:on_paint")[..8])
+pub const PAINT_HOOK_DISCRIMINATOR: [u8; 8] = [90, 11, 244, 200, 132, 34, 60, 116];
+
+/// Max extra accounts a hook's validation account may list
+const MAX_HOOK_ACCOUNTS: usize = 8;
+
+/// Seed for the PlayerProfile PDA of a main wallet
+const PROFILE_SEED: &[u8] = b"profile";
+
+/// Seed for the reward token mint PDA
+const REWARD_MINT_SEED: &[u8] = b"reward_mint";
+
+/// Seed for the PDA that is the reward mint's authority
+const MINT_AUTHORITY_SEED: &[u8] = b"mint_authority";
+
+/// Seed for a shard owner's RewardAccrual PDA (ER side)
+const REWARDS_SEED: &[u8] = b"rewards";
+
+/// Seed for a shard owner's RewardClaim PDA (base layer only)
+const REWARD_CLAIM_SEED: &[u8] = b"reward_claim";
+
+/// Decimals of the reward token
+const REWARD_DECIMALS: u8 = 6;
+
+/// Reward base units per visitor pixel: 1 token per 10 pixels
+const REWARD_PER_PIXEL: u64 = 10u64.pow(REWARD_DECIMALS as u32) / 10;
+
+/// Seed for a main wallet's Referral PDA (base layer only)
+const REFERRAL_SEED: &[u8] = b"referral";
+
+/// Share of each reward claim paid to the referrer instead of the owner, in basis points
+const REFERRAL_SHARE_BPS: u64 = 1_000;
+
+/// Seed for a main wallet's WalletStats PDA (base layer only)
+const WALLET_STATS_SEED: &[u8] = b"wallet_stats";
+
+/// Seed for a badge's non-transferable Token-2022 mint PDA, followed by the badge index
+const BADGE_MINT_SEED: &[u8] = b"badge_mint";
+
+/// Shards owned to earn each badge: Rookie, Landlord, Builder, Architect, Collector,
+/// Final Shard Boss. The badge index is the position in this list.
+const BADGE_THRESHOLDS: [u32; 6] = [10, 100, 1_000, 2_500, 5_000, 10_000];
+
+/// Seed for a Faction PDA (ER side), followed by the faction id
+const FACTION_SEED: &[u8] = b"faction";
+
+/// Seed for a FactionTerritory PDA (base layer only), followed by the faction id
+const FACTION_TERRITORY_SEED: &[u8] = b"faction_territory";
+
+/// Factions are numbered 1-4: Red, Blue, Green, Yellow (0 = no faction)
+const FACTION_COUNT: u8 = 4;
+
+/// Seconds a wallet must stay in a faction before switching (7 days)
+const FACTION_SWITCH_COOLDOWN: u64 = 7 * 24 * 60 * 60;
+
+/// Seed for the Season singleton PDA; SeasonResult PDAs append the season id
+const SEASON_SEED: &[u8] = b"season";
+
+/// Seed for a Leaderboard PDA, followed by the metric index
+const LEADERBOARD_SEED: &[u8] = b"leaderboard";
+
+/// Entries kept per leaderboard
+const LEADERBOARD_SIZE: usize = 20;
+
+/// Seed for an Artwork PDA, followed by creator and rectangle
+const ARTWORK_SEED: &[u8] = b"artwork";
+
+/// Seed for an artwork's ArtworkCard PDA, followed by the artwork address
+const ARTWORK_CARD_SEED: &[u8] = b"artwork_card";
+
+/// Max width / height of an artwork in pixels (up to 5 shards across)
+const MAX_ARTWORK_DIMENSION: u16 = 360;
+
+/// Max artwork title length in bytes
+const MAX_ARTWORK_TITLE_LEN: usize = 32;
+
+/// Seed for a Template PDA, followed by creator and template id
+const TEMPLATE_SEED: &[u8] = b"template";
+
+/// Seed for a template's Bounty escrow PDA, followed by the template address
+const BOUNTY_SEED: &[u8] = b"bounty";
+
+/// Seed for a funder's BountyFunding PDA, followed by the bounty and funder addresses
+const BOUNTY_FUNDING_SEED: &[u8] = b"bounty_funding";
+
+/// Max width / height of a template in pixels (one shard's worth, spanning up to 4 shards)
+const MAX_TEMPLATE_DIMENSION: u16 = 90;
+
+/// Max target bytes per write_template_chunk call (fits in one transaction)
+const MAX_TEMPLATE_CHUNK: usize = 900;
+
+/// Completion is measured in basis points of the template's target pixels
+const TEMPLATE_BPS: u32 = 10_000;
+
+/// Top contributors a completed bounty pays out to
+const MAX_BOUNTY_CONTRIBUTORS: usize = 16;
+
+/// Time contributors have to claim a completed bounty before the creator can sweep it
+const BOUNTY_CLAIM_WINDOW: u64 = 30 * SECONDS_PER_DAY;
+
+/// Streaks count UTC days of unix time
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+// Achievement bits, one namespace shared by PlayerProfile.achievements (painting,
+// updated on the ER) and WalletStats.achievements (shards, updated on the base layer)
+
+/// Placed a first pixel
+const ACHIEVEMENT_FIRST_PIXEL: u64 = 1 << 0;
+/// Painted 7 days in a row
+const ACHIEVEMENT_STREAK_7: u64 = 1 << 1;
+/// Painted 30 days in a row
+const ACHIEVEMENT_STREAK_30: u64 = 1 << 2;
+/// Placed 10,000 pixels
+const ACHIEVEMENT_PIXELS_10K: u64 = 1 << 3;
+/// Created a first shard
+const ACHIEVEMENT_FIRST_SHARD: u64 = 1 << 8;
+/// Created a shard inside every CONTINENT_SHARD_BOUNDS box
+const ACHIEVEMENT_ALL_CONTINENTS: u64 = 1 << 9;
+
+/// Rough continent bounding boxes in shard coordinates (min x, min y, max x, max y),
+/// Web Mercator like the canvas: North America, South America, Europe, Africa, Asia, Oceania.
+/// Boxes overlap at the edges; a shard counts for every box it lies in.
+const CONTINENT_SHARD_BOUNDS: [(u16, u16, u16, u16); 6] = [
+    (161, 178, 2103, 2799),
+    (1423, 2700, 2394, 4011),
+    (2508, 1204, 3640, 2307),
+    (2621, 2247, 3754, 3517),
+    (3640, 824, 5824, 3075),
+    (4692, 3075, 5824, 3800),
+];
+
+/// Max pixels returned by get_region (return data is capped at 1024 bytes)
+const MAX_VIEW_REGION_PIXELS: usize = 1000;
+
 /// Available colors using 8-bit storage (0 = unset/transparent, 1-255 = palette colors)
 const AVAILABLE_COLORS: u8 = 255;
 
@@ -46,15 +258,55 @@ const COOLDOWN_LIMIT: u8 = 60;
 /// Cooldown period in seconds resetting the burst counter
 const COOLDOWN_PERIOD: u64 = 15;
 
+/// XP earned per pixel placed on someone else's shard
+const XP_PER_PIXEL: u64 = 1;
+
+/// XP needed to reach each tier above tier 0
+const XP_TIER_THRESHOLDS: [u64; 4] = [1_000, 10_000, 100_000, 1_000_000];
+
+/// Extra burst pixels granted per XP tier
+const XP_TIER_BURST_BONUS: u8 = 10;
+
+/// Seconds taken off the cooldown period per XP tier
+const XP_TIER_PERIOD_CUT: u64 = 2;
+
+/// Max shards a single multi-shard placement may touch (a 2x2 block around a corner)
+const MAX_SHARDS_PER_MULTI: usize = 4;
+
+/// Bits per packed pixel: 13-bit local index (0-8099) + 8-bit color
+const PACKED_PIXEL_BITS: usize = 21;
+
+/// Max serialized transaction size (solana_packet::PACKET_DATA_SIZE: 1280 MTU - 48 header bytes)
+const PACKET_DATA_SIZE: usize = 1232;
+
+/// Bytes of a place_pixels_packed transaction besides the packed pixels, with every
+/// optional PlacePixel account present: one signature (65), header (3), 10 account keys
+/// (1 + 10 * 32: shard, painters, history, session, profile, owner_rewards, faction,
+/// season, signer, program), blockhash (32), instruction count, program index and 9
+/// account indices (1 + 1 + 10), data length (2) and the args before the pixels
+/// (8 discriminator + 3 * 2 u16 + 4 vec length)
+const PACKED_TX_OVERHEAD: usize = 65 + 3 + (1 + 10 * 32) + 32 + (1 + 1 + 10) + 2 + (8 + 3 * 2 + 4);
+
+/// Max pixels in a packed placement: as many 21-bit records as fit the rest of the packet
+const MAX_PACKED_PIXELS: u16 =
+    ((PACKET_DATA_SIZE - PACKED_TX_OVERHEAD) * 8 / PACKED_PIXEL_BITS) as u16;
+
+/// Max pixels a single flood fill may recolor
+const MAX_FLOOD_FILL_PIXELS: usize = 4096;
+
 #[ephemeral]
 #[program]
 pub mod magicplace {
     use super::*;
-
+    /// Create a session account for a session key authorized by `main_wallet`
+    /// With a `referrer`, the signed authorization message must end with
+    /// " referred by: <referrer>" and both wallets' Referral accounts must be passed.
+    /// A main wallet is attributed to its first referrer only.
     pub fn initialize_user(
         ctx: Context<InitializeUser>,
         main_wallet: Pubkey,
         _signature: [u8; 64],
+        referrer: Option<Pubkey>,
     ) -> Result<()> {
         // Verify Ed25519 signature using Solana's native Ed25519 program
         // The frontend must include an Ed25519 verify instruction as the first instruction
@@ -72,23 +324,8 @@ pub mod magicplace {
             PixelError::InvalidAuth
         );
         
-        // Parse the Ed25519 instruction data to verify the signature matches
-        let ix_data = &ed25519_ix.data;
-        require!(ix_data.len() >= 2, PixelError::InvalidAuth);
-        
-        let num_signatures = ix_data[0];
-        require!(num_signatures >= 1, PixelError::InvalidAuth);
-        
-        // Parse the first signature header (starts at offset 2)
-        require!(ix_data.len() >= 18, PixelError::InvalidAuth); // 2 + 16 bytes header
-        
-        let pubkey_offset = u16::from_le_bytes([ix_data[6], ix_data[7]]) as usize;
-        
-        // Extract the public key from the instruction data
-        require!(ix_data.len() >= pubkey_offset + 32, PixelError::InvalidAuth);
-        let pubkey_bytes = &ix_data[pubkey_offset..pubkey_offset + 32];
-        let verified_pubkey = Pubkey::try_from(pubkey_bytes)
-            .map_err(|_| PixelError::InvalidAuth)?;
+        // Extract the public key and signed message from the instruction data
+        let (verified_pubkey, message) = ed25519_signed(&ed25519_ix.data)?;
         
         // Verify the public key matches the main_wallet
         require!(
@@ -97,6 +334,50 @@ pub mod magicplace {
         );
         
         msg!("Ed25519 signature verified for main wallet: {}", main_wallet);
+
+        match referrer {
+            Some(referrer) => {
+                require_keys_neq!(referrer, main_wallet, PixelError::InvalidReferrer);
+                let suffix = format!(" referred by: {}", referrer);
+                require!(
+                    message.ends_with(suffix.as_bytes()),
+                    PixelError::InvalidReferrer
+                );
+
+                let referrer_referral = ctx
+                    .accounts
+                    .referrer_referral
+                    .as_mut()
+                    .ok_or(PixelError::InvalidReferrer)?;
+                if referrer_referral.wallet == Pubkey::default() {
+                    referrer_referral.wallet = referrer;
+                    referrer_referral.bump = ctx.bumps.referrer_referral.ok_or(PixelError::InvalidReferrer)?;
+                }
+
+                let referral = ctx.accounts.referral.as_mut().ok_or(PixelError::InvalidReferrer)?;
+                if referral.wallet == Pubkey::default() {
+                    referral.wallet = main_wallet;
+                    referral.bump = ctx.bumps.referral.ok_or(PixelError::InvalidReferrer)?;
+                }
+                if referral.referrer == Pubkey::default() {
+                    referral.referrer = referrer;
+                    referrer_referral.referrals += 1;
+
+                    emit!(ReferralRecorded {
+                        wallet: main_wallet,
+                        referrer,
+                        referrals: referrer_referral.referrals,
+                        timestamp: Clock::get()?.unix_timestamp as u64,
+                    });
+                } else {
+                    msg!("{} was already referred by {}", main_wallet, referral.referrer);
+                }
+            }
+            None => require!(
+                ctx.accounts.referral.is_none() && ctx.accounts.referrer_referral.is_none(),
+                PixelError::InvalidReferrer
+            ),
+        }
         
         // Initialize the session account
         let user = &mut ctx.accounts.user;
@@ -107,6 +388,12 @@ pub mod magicplace {
         user.bump = ctx.bumps.user;
         
         msg!("Session account initialized for main wallet: {}", main_wallet);
+
+        emit!(SessionCreated {
+            main_wallet,
+            session_key: user.authority,
+            timestamp: Clock::get()?.unix_timestamp as u64,
+        });
         Ok(())
     }
 
@@ -133,9 +420,26 @@ pub mod magicplace {
         )?;
         
         msg!("Session account delegated to ER for wallet: {}", main_wallet);
+
+        emit!(SessionDelegated {
+            main_wallet: ctx.accounts.user.main_address,
+            session_key: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp as u64,
+        });
         Ok(())
     }
 
+    /// Create the XP profile of a main wallet (base layer)
+    /// Any session of the wallet can create it; all of its sessions share it.
+    pub fn initialize_profile(ctx: Context<InitializeProfile>, main_wallet: Pubkey) -> Result<()> {
+        profile::initialize_profile(ctx, main_wallet)
+    }
+
+    /// Delegate a profile to Ephemeral Rollups so placements can update it
+    pub fn delegate_profile(ctx: Context<DelegateProfile>, main_wallet: Pubkey) -> Result<()> {
+        profile::delegate_profile(ctx, main_wallet)
+    }
+
     // ========================================
     // Shard Management
     // ========================================
@@ -147,7 +451,8 @@ pub mod magicplace {
     pub fn initialize_shard(
         ctx: Context<InitializeShard>, 
         shard_x: u16, 
-        shard_y: u16
+        shard_y: u16,
+        main_wallet: Pubkey,
     ) -> Result<()> {
         require!(
             (shard_x as u32) < SHARDS_PER_DIM && (shard_y as u32) < SHARDS_PER_DIM,
@@ -162,6 +467,7 @@ pub mod magicplace {
         );
         
         let session = SessionAccount::try_deserialize(&mut &session_info.data.borrow()[..])?;
+        require_keys_eq!(session.main_address, main_wallet, PixelError::InvalidAuth);
         
         let shard = &mut ctx.accounts.shard;
         shard.shard_x = shard_x;
@@ -169,6 +475,33 @@ pub mod magicplace {
         shard.pixels = vec![0u8; BYTES_PER_SHARD];
         shard.creator = session.main_address;
         shard.bump = ctx.bumps.shard;
+        shard.layers = 0;
+        shard.seq = 0;
+        shard.content_hash = 0;
+
+        let canvas = &mut ctx.accounts.canvas;
+        canvas.bump = ctx.bumps.canvas;
+        shard.index = canvas.shard_count;
+        canvas.shard_count += 1;
+        shard.hook_program = Pubkey::default();
+
+        let stats = &mut ctx.accounts.wallet_stats;
+        stats.owner = session.main_address;
+        stats.bump = ctx.bumps.wallet_stats;
+        let unlocked = stats.record_shard(shard_x, shard_y);
+        emit_achievements(stats.owner, unlocked, 0, Clock::get()?.unix_timestamp as u64);
+
+        // Count the shard in the territory that holds the creator's other shards; they
+        // all move together when sync_territory follows a faction switch
+        if stats.territory_faction != 0 {
+            let territory = ctx
+                .accounts
+                .faction_territory
+                .as_mut()
+                .ok_or(PixelError::InvalidFaction)?;
+            require!(territory.id == stats.territory_faction, PixelError::InvalidFaction);
+            territory.shards += 1;
+        }
         
         msg!(
             "Shard ({}, {}) initialized with {} pixels ({} bytes packed)", 
@@ -203,6 +536,88 @@ pub mod magicplace {
         )?;
         
         msg!("Shard ({}, {}) delegated to ER", shard_x, shard_y);
+
+        emit!(ShardDelegated {
+            shard_x,
+            shard_y,
+            authority: ctx.accounts.authority.key(),
+            timestamp: Clock::get()?.unix_timestamp as u64,
+        });
+        Ok(())
+    }
+
+    /// Upgrade a shard created before the layers/seq/index/hook fields (base layer)
+    /// Permissionless; the payer covers the extra rent. Grows the account to the current
+    /// PixelShard size and backfills the new fields: content_hash recomputed from the
+    /// pixels, the next canvas index, no layers and no hook. Legacy shards predate
+    /// WalletStats too, so the shard is also counted for its creator (badges, continents).
+    /// A delegated legacy shard must first be brought back with undelegate_legacy_shard.
+    pub fn migrate_shard(
+        ctx: Context<MigrateShard>,
+        shard_x: u16,
+        shard_y: u16,
+        creator: Pubkey,
+    ) -> Result<()> {
+        let shard_info = ctx.accounts.shard.to_account_info();
+        require!(shard_info.owner == &crate::ID, PixelError::InvalidShardAccount);
+        require!(is_legacy_shard(&shard_info), PixelError::ShardAlreadyMigrated);
+        require_keys_eq!(shard_creator(&shard_info)?, creator, PixelError::InvalidShardAccount);
+
+        let new_len = PixelShard::TAIL_OFFSET + PixelShard::TAIL_LEN;
+        let shortfall = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(shard_info.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: shard_info.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        shard_info.resize(new_len)?;
+
+        let canvas = &mut ctx.accounts.canvas;
+        canvas.bump = ctx.bumps.canvas;
+        let index = canvas.shard_count;
+        canvas.shard_count += 1;
+
+        let stats = &mut ctx.accounts.wallet_stats;
+        stats.owner = creator;
+        stats.bump = ctx.bumps.wallet_stats;
+        let unlocked = stats.record_shard(shard_x, shard_y);
+        emit_achievements(stats.owner, unlocked, 0, Clock::get()?.unix_timestamp as u64);
+        if stats.territory_faction != 0 {
+            let territory = ctx
+                .accounts
+                .faction_territory
+                .as_mut()
+                .ok_or(PixelError::InvalidFaction)?;
+            require!(territory.id == stats.territory_faction, PixelError::InvalidFaction);
+            territory.shards += 1;
+        }
+
+        // layers, seq and hook_program stay as zeroed by the resize
+        let mut data = shard_info.try_borrow_mut_data()?;
+        let content_hash = shard_content_hash(&data[PixelShard::PIXELS_OFFSET..PixelShard::TAIL_OFFSET]);
+        let tail = &mut data[PixelShard::TAIL_OFFSET..];
+        tail[42..50].copy_from_slice(&content_hash.to_le_bytes());
+        tail[50..54].copy_from_slice(&index.to_le_bytes());
+        drop(data);
+
+        msg!("Shard ({}, {}) migrated as index {}", shard_x, shard_y, index);
+
+        emit!(ShardMigrated {
+            shard_x,
+            shard_y,
+            index,
+            content_hash,
+            timestamp: Clock::get()?.unix_timestamp as u64,
+        });
         Ok(())
     }
 
@@ -213,218 +628,779 @@ pub mod magicplace {
     /// Place a pixel using global coordinates
     /// px, py: 0 to 524,287 (global pixel coordinates)
     /// color: 1-15 (0 is reserved for unset/transparent, 4-bit packing)
-    pub fn place_pixel(
-        ctx: Context<PlacePixel>,
+    /// On a shard with a paint hook, pass the hook accounts as remaining accounts
+    /// (see invoke_paint_hook); the hook can reject the pixel.
+    pub fn place_pixel<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlacePixel<'info>>,
         _shard_x: u16,  // Used in seeds validation
         _shard_y: u16,  // Used in seeds validation
         px: u32,
         py: u32,
         color: u8
     ) -> Result<()> {
-        require!(px < CANVAS_RES && py < CANVAS_RES, PixelError::InvalidPixelCoord);
-        require!(color > 0 && color <= AVAILABLE_COLORS, PixelError::InvalidColor);
-        
-        // Calculate expected shard coordinates
-        let expected_shard_x = (px / SHARD_DIMENSION) as u16;
-        let expected_shard_y = (py / SHARD_DIMENSION) as u16;
-        
-        // Verify the correct shard was passed
+        drawing::place_pixel(ctx, px, py, color)
+    }
+
+    /// Erase a pixel (set to 0/transparent)
+    pub fn erase_pixel(
+        ctx: Context<PlacePixel>,
+        _shard_x: u16,
+        _shard_y: u16,
+        px: u32,
+        py: u32,
+    ) -> Result<()> {
+        drawing::erase_pixel(ctx, px, py)
+    }
+
+    /// Place multiple pixels in bulk (up to the caller's tier burst, 60-100 pixels per call)
+    /// All pixels must be within the same shard
+    /// Each pixel is specified as (local_x, local_y, color) where:
+    /// - local_x: 0-89 (position within shard)
+    /// - local_y: 0-89 (position within shard)
+    /// - color: 1-255 (0 is reserved for transparent)
+    /// On a shard with a paint hook, the hook vets the whole batch (see place_pixel).
+    pub fn place_pixels_bulk<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlacePixel<'info>>,
+        shard_x: u16,
+        shard_y: u16,
+        pixels: Vec<BulkPixel>,
+    ) -> Result<()> {
+        drawing::place_pixels_bulk(ctx, shard_x, shard_y, pixels)
+    }
+
+    /// Place pixels in bulk using the bit-packed encoding (max MAX_PACKED_PIXELS, 296, per call)
+    /// All pixels must be within the same shard. `data` holds `count` 21-bit records packed
+    /// LSB-first: low 13 bits = local pixel index (local_y * 90 + local_x), high 8 bits = color.
+    /// The PixelsBulkChanged event echoes `data` as is.
+    pub fn place_pixels_packed(
+        ctx: Context<PlacePixel>,
+        shard_x: u16,
+        shard_y: u16,
+        count: u16,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        drawing::place_pixels_packed(ctx, shard_x, shard_y, count, data)
+    }
+
+    /// Place multiple pixels across shard boundaries (up to the caller's tier burst, max 4 shards per call)
+    /// Pixels use global coordinates (px, py: 0 to 524,287) and are routed to their shard
+    /// by coordinates. Every target shard must be passed as a writable remaining account,
+    /// followed by its ShardPainters / ShardHistory accounts if those layers are enabled.
+    /// Pixels on the caller's own shards are free, the rest are charged against the
+    /// cooldown as a single burst. Any pixel without a matching shard fails the whole call.
+    /// A shard owned by someone else may be followed by its owner's writable RewardAccrual
+    /// (only after the first such shard of each owner), which is credited for the pixels;
+    /// owners without one accrue nothing.
+    pub fn place_pixels_multi_shard<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlacePixelsMultiShard<'info>>,
+        pixels: Vec<GlobalPixel>,
+    ) -> Result<()> {
+        drawing::place_pixels_multi_shard(ctx, pixels)
+    }
+
+    // ========================================
+    // Drawing Primitives
+    // ========================================
+
+    /// Fill a rectangle within one shard with a single color
+    /// (x0, y0) and (x1, y1) are inclusive local corners (0-89), in any order.
+    /// Only pixels that actually change color are charged against the non-owner cooldown.
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_rect(
+        ctx: Context<PlacePixel>,
+        shard_x: u16,
+        shard_y: u16,
+        x0: u8,
+        y0: u8,
+        x1: u8,
+        y1: u8,
+        color: u8,
+    ) -> Result<()> {
+        drawing::fill_rect(ctx, shard_x, shard_y, x0, y0, x1, y1, color)
+    }
+
+    /// Draw a straight line between two local points (0-89) within one shard
+    /// Uses Bresenham's algorithm; both endpoints are painted.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_line(
+        ctx: Context<PlacePixel>,
+        shard_x: u16,
+        shard_y: u16,
+        x0: u8,
+        y0: u8,
+        x1: u8,
+        y1: u8,
+        color: u8,
+    ) -> Result<()> {
+        drawing::draw_line(ctx, shard_x, shard_y, x0, y0, x1, y1, color)
+    }
+
+    /// Flood fill the 4-connected area of the seed pixel's color within one shard
+    /// Bounded to MAX_FLOOD_FILL_PIXELS; larger areas fail instead of filling partially.
+    pub fn flood_fill(
+        ctx: Context<PlacePixel>,
+        shard_x: u16,
+        shard_y: u16,
+        x: u8,
+        y: u8,
+        color: u8,
+    ) -> Result<()> {
+        drawing::flood_fill(ctx, shard_x, shard_y, x, y, color)
+    }
+
+    /// Copy a contiguous run of palette indices into an owned shard (owner only)
+    /// `offset` is the local pixel index of the first byte (local_y * 90 + local_x) and
+    /// the run continues row by row. With `skip_transparent`, 0 bytes leave the existing
+    /// pixel unchanged instead of erasing it, so non-rectangular art can be stamped.
+    pub fn write_shard_chunk(
+        ctx: Context<PlacePixel>,
+        shard_x: u16,
+        shard_y: u16,
+        offset: u16,
+        bytes: Vec<u8>,
+        skip_transparent: bool,
+    ) -> Result<()> {
+        drawing::write_shard_chunk(ctx, shard_x, shard_y, offset, bytes, skip_transparent)
+    }
+
+    /// Copy a rectangle from one shard into another (or the same) shard
+    /// The source rectangle is flipped first, then rotated clockwise by `quarter_turns`;
+    /// odd turns swap width and height at the destination. Ownership and cooldown rules
+    /// apply to the destination shard only, counting pixels that actually change.
+    pub fn copy_region(
+        ctx: Context<CopyRegion>,
+        _src_shard_x: u16,
+        _src_shard_y: u16,
+        dst_shard_x: u16,
+        dst_shard_y: u16,
+        region: RegionCopy,
+    ) -> Result<()> {
+        drawing::copy_region(ctx, dst_shard_x, dst_shard_y, region)
+    }
+
+    // ========================================
+    // Territory Defense
+    // ========================================
+
+    /// Snapshot an owned shard's pixels into a new numbered snapshot PDA (base layer)
+    /// Reads the last committed state, so call commit_shard first to capture recent
+    /// ER changes. Snapshot ids start at 0 and increase by one per shard.
+    pub fn create_snapshot(
+        ctx: Context<CreateSnapshot>,
+        shard_x: u16,
+        shard_y: u16,
+    ) -> Result<()> {
+        history::create_snapshot(ctx, shard_x, shard_y)
+    }
+
+    /// Write a snapshot back into a delegated shard in one call (owner only, via ER)
+    /// Rejected while the season is frozen, like any other pixel write.
+    pub fn restore_shard(
+        ctx: Context<RestoreShard>,
+        shard_x: u16,
+        shard_y: u16,
+        snapshot_id: u32,
+    ) -> Result<()> {
+        history::restore_shard(ctx, shard_x, shard_y, snapshot_id)
+    }
+
+    /// Create the painter attribution layer of an owned shard (base layer)
+    /// Delegate it with delegate_painter_layer, then turn it on with set_painter_layer.
+    pub fn initialize_painter_layer(
+        ctx: Context<InitializePainterLayer>,
+        shard_x: u16,
+        shard_y: u16,
+    ) -> Result<()> {
+        history::initialize_painter_layer(ctx, shard_x, shard_y)
+    }
+
+    /// Delegate a shard's painter layer to Ephemeral Rollups
+    pub fn delegate_painter_layer(
+        ctx: Context<DelegatePainterLayer>,
+        shard_x: u16,
+        shard_y: u16,
+    ) -> Result<()> {
+        history::delegate_painter_layer(ctx, shard_x, shard_y)
+    }
+
+    /// Turn painter attribution on or off for an owned shard (via ER)
+    /// Enabling starts from a clean layer; from then on every write must pass it.
+    pub fn set_painter_layer(
+        ctx: Context<SetPainterLayer>,
+        shard_x: u16,
+        shard_y: u16,
+        enabled: bool,
+    ) -> Result<()> {
+        history::set_painter_layer(ctx, shard_x, shard_y, enabled)
+    }
+
+    /// Revert every pixel last painted by `painter` (a main wallet, or a partner's registry
+    /// entry) on an owned shard (via ER)
+    /// Pixels go back to the passed snapshot, or are cleared to 0 when no snapshot is given.
+    /// Rejected while the season is frozen.
+    pub fn revert_painter(
+        ctx: Context<RevertPainter>,
+        shard_x: u16,
+        shard_y: u16,
+        painter: Pubkey,
+    ) -> Result<()> {
+        history::revert_painter(ctx, shard_x, shard_y, painter)
+    }
+
+    /// Create the change history ring buffer of an owned shard (base layer)
+    /// Delegate it with delegate_history, then turn it on with set_history_layer.
+    pub fn initialize_history(
+        ctx: Context<InitializeHistory>,
+        shard_x: u16,
+        shard_y: u16,
+    ) -> Result<()> {
+        history::initialize_history(ctx, shard_x, shard_y)
+    }
+
+    /// Delegate a shard's change history to Ephemeral Rollups
+    pub fn delegate_history(
+        ctx: Context<DelegateHistory>,
+        shard_x: u16,
+        shard_y: u16,
+    ) -> Result<()> {
+        history::delegate_history(ctx, shard_x, shard_y)
+    }
+
+    /// Turn change recording on or off for an owned shard (via ER)
+    /// Enabling starts from an empty buffer; from then on every write must pass it.
+    pub fn set_history_layer(
+        ctx: Context<SetHistoryLayer>,
+        shard_x: u16,
+        shard_y: u16,
+        enabled: bool,
+    ) -> Result<()> {
+        history::set_history_layer(ctx, shard_x, shard_y, enabled)
+    }
+
+    /// Roll back the last `count` recorded changes on an owned shard (via ER)
+    /// Stops early when the history runs out. Undone pixels lose their attribution,
+    /// and the undo itself is not recorded. Rejected while the season is frozen.
+    pub fn undo_last(
+        ctx: Context<UndoLast>,
+        shard_x: u16,
+        shard_y: u16,
+        count: u16,
+    ) -> Result<()> {
+        history::undo_last(ctx, shard_x, shard_y, count)
+    }
+
+    /// Set or clear the paint hook of an owned shard (via ER)
+    /// place_pixel and place_pixels_bulk then CPI into the hook's `on_paint` before
+    /// writing; other write paths are limited to the owner while a hook is set.
+    pub fn set_paint_hook(
+        ctx: Context<SetPaintHook>,
+        shard_x: u16,
+        shard_y: u16,
+        hook_program: Option<Pubkey>,
+    ) -> Result<()> {
         let shard = &mut ctx.accounts.shard;
         require!(
-            shard.shard_x == expected_shard_x && shard.shard_y == expected_shard_y,
-            PixelError::ShardMismatch
+            shard.creator == ctx.accounts.session.main_address,
+            PixelError::NotShardOwner
         );
 
-        let session = &mut ctx.accounts.session;
+        shard.hook_program = hook_program.unwrap_or_default();
 
-        if shard.creator != session.main_address {
-             let clock = Clock::get()?;
-             let now = clock.unix_timestamp as u64;
+        msg!("Paint hook for shard ({}, {}) set to {:?}", shard_x, shard_y, hook_program);
+        Ok(())
+    }
 
-             if session.cooldown_counter >= COOLDOWN_LIMIT {
-                 if now.saturating_sub(session.last_place_timestamp) >= COOLDOWN_PERIOD {
-                     session.cooldown_counter = 0;
-                 } else {
-                     return err!(PixelError::Cooldown);
-                 }
-             }
+    // ========================================
+    // Partner Painting (CPI)
+    // ========================================
 
-             session.cooldown_counter = session.cooldown_counter.checked_add(1).unwrap();
+    /// Register a partner program allowed to paint via CPI (base layer, admin only)
+    /// The partner gets `quota` pixels per `quota_period` seconds. Delegate the entry
+    /// with delegate_partner before the partner paints on the ER.
+    pub fn register_partner(
+        ctx: Context<RegisterPartner>,
+        program_id: Pubkey,
+        quota: u32,
+        quota_period: u64,
+    ) -> Result<()> {
+        require!(quota > 0 && quota_period > 0, PixelError::InvalidPartnerQuota);
 
-             if session.cooldown_counter >= COOLDOWN_LIMIT {
-                 session.last_place_timestamp = now;
-             }
-        }
-        
-        // Calculate local pixel position within the shard
-        let local_x = px % SHARD_DIMENSION;
-        let local_y = py % SHARD_DIMENSION;
-        let local_pixel_id = (local_y * SHARD_DIMENSION + local_x) as usize;
-        
-        // 8-bit storage: 1 byte per pixel, direct indexing
-        shard.pixels[local_pixel_id] = color;
-        
-        msg!(
-            "Pixel ({}, {}) -> Shard ({}, {}) index {} = color {}",
-            px, py,
-            shard.shard_x, shard.shard_y,
-            local_pixel_id,
-            color
-        );
+        let (_, authority_bump) = Pubkey::find_program_address(&[PARTNER_AUTHORITY_SEED], &program_id);
 
-        emit!(PixelChanged {
-            px,
-            py,
-            color,
-            painter: ctx.accounts.signer.key(),
-            main_wallet: session.main_address,
-            timestamp: Clock::get()?.unix_timestamp as u64,
-        });
+        let partner = &mut ctx.accounts.partner;
+        partner.program_id = program_id;
+        partner.authority_bump = authority_bump;
+        partner.quota = quota;
+        partner.quota_period = quota_period;
+        partner.used = 0;
+        partner.window_start = 0;
+        partner.total_pixels = 0;
+        partner.enabled = true;
+        partner.bump = ctx.bumps.partner;
 
+        msg!("Partner {} registered: {} pixels / {}s", program_id, quota, quota_period);
         Ok(())
     }
 
-    /// Erase a pixel (set to 0/transparent)
-    pub fn erase_pixel(
-        ctx: Context<PlacePixel>,
-        _shard_x: u16,
-        _shard_y: u16,
-        px: u32,
-        py: u32,
+    /// Delegate a partner registry entry to Ephemeral Rollups
+    pub fn delegate_partner(ctx: Context<DelegatePartner>, program_id: Pubkey) -> Result<()> {
+        ctx.accounts.delegate_pda(
+            &ctx.accounts.payer,
+            &[PARTNER_SEED, program_id.as_ref()],
+            DelegateConfig {
+                validator: ctx.remaining_accounts.first().map(|acc| acc.key()),
+                ..Default::default()
+            },
+        )?;
+
+        msg!("Partner {} delegated to ER", program_id);
+        Ok(())
+    }
+
+    /// Change a partner's quota or turn it off (admin only, on whichever layer holds the entry)
+    pub fn update_partner(
+        ctx: Context<UpdatePartner>,
+        _program_id: Pubkey,
+        quota: u32,
+        quota_period: u64,
+        enabled: bool,
     ) -> Result<()> {
-        require!(px < CANVAS_RES && py < CANVAS_RES, PixelError::InvalidPixelCoord);
-        
-        let expected_shard_x = (px / SHARD_DIMENSION) as u16;
-        let expected_shard_y = (py / SHARD_DIMENSION) as u16;
-        
+        require!(quota > 0 && quota_period > 0, PixelError::InvalidPartnerQuota);
+
+        let partner = &mut ctx.accounts.partner;
+        partner.quota = quota;
+        partner.quota_period = quota_period;
+        partner.enabled = enabled;
+
+        msg!(
+            "Partner {} updated: {} pixels / {}s, enabled: {}",
+            partner.program_id, quota, quota_period, enabled
+        );
+        Ok(())
+    }
+
+    /// Place pixels on behalf of a registered partner program (via ER, called through CPI)
+    /// The partner signs with its [PARTNER_AUTHORITY_SEED] PDA; see `partner::place_pixels`
+    /// under the `cpi` feature. Pixels use local coordinates like place_pixels_bulk, count
+    /// against the partner's quota instead of a session cooldown, and are attributed to
+    /// the partner program id. The painter layer records them under the partner's registry
+    /// entry (partner::registry_address), which is never credited for template bounties.
+    pub fn partner_place_pixels(
+        ctx: Context<PartnerPlacePixels>,
+        shard_x: u16,
+        shard_y: u16,
+        pixels: Vec<BulkPixel>,
+    ) -> Result<()> {
+        require!(!pixels.is_empty(), PixelError::EmptyBulkPixels);
+        // Partners have a quota instead of a tier; a call may be as large as the top tier's burst
+        let max_pixels = cooldown_for_tier(XP_TIER_THRESHOLDS.len() as u8).0;
+        require!(pixels.len() <= max_pixels as usize, PixelError::BulkTooLarge);
+
+        let partner_key = ctx.accounts.partner.key();
+        let partner = &mut ctx.accounts.partner;
+        let expected_authority = Pubkey::create_program_address(
+            &[PARTNER_AUTHORITY_SEED, &[partner.authority_bump]],
+            &partner.program_id,
+        )
+        .map_err(|_| PixelError::InvalidPartnerAuthority)?;
+        require_keys_eq!(
+            ctx.accounts.partner_authority.key(),
+            expected_authority,
+            PixelError::InvalidPartnerAuthority
+        );
+        require!(partner.enabled, PixelError::PartnerDisabled);
+
+        // Quota window resets once quota_period has passed since it opened
+        let timestamp = Clock::get()?.unix_timestamp as u64;
+        load_open_season(&ctx.accounts.season, timestamp)?;
+        if timestamp.saturating_sub(partner.window_start) >= partner.quota_period {
+            partner.window_start = timestamp;
+            partner.used = 0;
+        }
+        require!(
+            partner.used as usize + pixels.len() <= partner.quota as usize,
+            PixelError::PartnerQuotaExceeded
+        );
+        partner.used += pixels.len() as u32;
+        partner.total_pixels += pixels.len() as u64;
+
         let shard = &mut ctx.accounts.shard;
         require!(
-            shard.shard_x == expected_shard_x && shard.shard_y == expected_shard_y,
+            shard.shard_x == shard_x && shard.shard_y == shard_y,
             PixelError::ShardMismatch
         );
-        
-        let local_x = px % SHARD_DIMENSION;
-        let local_y = py % SHARD_DIMENSION;
-        let local_pixel_id = (local_y * SHARD_DIMENSION + local_x) as usize;
-        
-        // 8-bit storage: direct indexing, set to 0 (transparent)
-        shard.pixels[local_pixel_id] = 0;
-        
-        msg!("Pixel ({}, {}) erased", px, py);
-
-        // Context is PlacePixel, which includes session
-        let session = &mut ctx.accounts.session;
-        emit!(PixelChanged {
-            px,
-            py,
-            color: 0, // 0 = erased/transparent
-            painter: ctx.accounts.signer.key(),
-            main_wallet: session.main_address,
-            timestamp: Clock::get()?.unix_timestamp as u64,
+        require_hook_bypass_allowed(&shard.hook_program, &shard.creator, &partner.program_id)?;
+
+        let mut data = vec![0u8; packed_len(pixels.len())];
+        let mut writer = PixelWriter::new(
+            shard.layers,
+            ctx.accounts.painters.as_ref(),
+            ctx.accounts.history.as_ref(),
+            Some(&partner_key),
+        )?;
+        for (n, pixel) in pixels.iter().enumerate() {
+            require!(valid_local(pixel.local_x, pixel.local_y), PixelError::InvalidPixelCoord);
+            require!((1..=AVAILABLE_COLORS).contains(&pixel.color), PixelError::InvalidColor);
+
+            let local_pixel_id = local_pixel_index(pixel.local_x as u32, pixel.local_y as u32);
+            writer.write(&mut shard.pixels, local_pixel_id, pixel.color);
+            pack_pixel(&mut data, n, local_pixel_id, pixel.color);
+        }
+        let seq = shard.record_write(writer.hash_delta());
+
+        msg!(
+            "Partner {} placed {} pixels on shard ({}, {})",
+            partner.program_id, pixels.len(), shard_x, shard_y
+        );
+
+        emit!(PixelsBulkChanged {
+            shard_x,
+            shard_y,
+            count: pixels.len() as u16,
+            data,
+            painter: ctx.accounts.partner_authority.key(),
+            main_wallet: partner.program_id,
+            timestamp,
+            seq,
         });
 
         Ok(())
     }
 
-    /// Place multiple pixels in bulk (max 50 pixels per call)
-    /// All pixels must be within the same shard
-    /// Each pixel is specified as (local_x, local_y, color) where:
-    /// - local_x: 0-89 (position within shard)
-    /// - local_y: 0-89 (position within shard)
-    /// - color: 1-255 (0 is reserved for transparent)
-    pub fn place_pixels_bulk(
-        ctx: Context<PlacePixel>,
+    // ========================================
+    // Token Rewards
+    // ========================================
+
+    /// Create the reward token mint (base layer, once, permissionless)
+    /// Both the mint and its authority are PDAs, so only this program can mint.
+    pub fn initialize_reward_mint(_ctx: Context<InitializeRewardMint>) -> Result<()> {
+        rewards::initialize_reward_mint(_ctx)
+    }
+
+    /// Create the reward accrual account of a shard owner (base layer, permissionless)
+    /// Delegate it with delegate_rewards so visitor placements on the ER can credit it.
+    pub fn initialize_rewards(ctx: Context<InitializeRewards>, owner: Pubkey) -> Result<()> {
+        rewards::initialize_rewards(ctx, owner)
+    }
+
+    /// Delegate a shard owner's reward accrual to Ephemeral Rollups
+    pub fn delegate_rewards(ctx: Context<DelegateRewards>, owner: Pubkey) -> Result<()> {
+        rewards::delegate_rewards(ctx, owner)
+    }
+
+    /// Mint every accrued but unclaimed reward to the owner's token account (base layer)
+    /// Reads the last committed accrual (see commit_rewards) and only moves the base-layer
+    /// claim counter, so the accrual can stay delegated. An owner with a referrer must
+    /// pass the referrer's accounts; the referrer's share is taken out of the owner's amount.
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        rewards::claim_rewards(ctx)
+    }
+
+    // ========================================
+    // Badges
+    // ========================================
+
+    /// Create the non-transferable Token-2022 mint of a badge (base layer, once, permissionless)
+    /// Minted by the same authority PDA as the reward token, 0 decimals.
+    pub fn initialize_badge_mint(ctx: Context<InitializeBadgeMint>, badge: u8) -> Result<()> {
+        rewards::initialize_badge_mint(ctx, badge)
+    }
+
+    /// Mint badge `badge` to the caller once they own enough shards (base layer)
+    /// Idempotent: claiming a badge the wallet already holds succeeds without minting again.
+    pub fn claim_badge(ctx: Context<ClaimBadge>, badge: u8) -> Result<()> {
+        rewards::claim_badge(ctx, badge)
+    }
+
+    // ========================================
+    // Factions
+    // ========================================
+
+    /// Create a faction's accounts (base layer, once per faction, permissionless)
+    /// Delegate the Faction with delegate_faction; its territory counter stays on base.
+    pub fn initialize_faction(ctx: Context<InitializeFaction>, faction_id: u8) -> Result<()> {
+        factions::initialize_faction(ctx, faction_id)
+    }
+
+    /// Delegate a faction to Ephemeral Rollups
+    pub fn delegate_faction(ctx: Context<DelegateFaction>, faction_id: u8) -> Result<()> {
+        factions::delegate_faction(ctx, faction_id)
+    }
+
+    /// Join a faction, or switch to another one (ER)
+    /// Switching is allowed FACTION_SWITCH_COOLDOWN after joining the current faction and
+    /// needs the faction being left as `previous_faction`. The profile is committed so
+    /// sync_territory can move the wallet's shards on the base layer.
+    pub fn join_faction(ctx: Context<JoinFaction>, faction_id: u8) -> Result<()> {
+        factions::join_faction(ctx, faction_id)
+    }
+
+    /// Move a wallet's shards to the territory of its profile's faction (base layer)
+    /// Permissionless. Reads the committed profile (join_faction commits it), so run it
+    /// after a join or switch; until then the shards count for the previous faction.
+    pub fn sync_territory(ctx: Context<SyncTerritory>) -> Result<()> {
+        factions::sync_territory(ctx)
+    }
+
+    // ========================================
+    // Seasons
+    // ========================================
+
+    /// Create the season singleton (base layer, once, admin only)
+    /// Painting stays open until the first season is opened. Delegate it with delegate_season.
+    /// The admin is recorded on the singleton, since the ER cannot check the upgrade authority.
+    pub fn initialize_season(ctx: Context<InitializeSeason>) -> Result<()> {
+        seasons::initialize_season(ctx)
+    }
+
+    /// Delegate the season singleton to Ephemeral Rollups
+    pub fn delegate_season(ctx: Context<DelegateSeason>) -> Result<()> {
+        seasons::delegate_season(ctx)
+    }
+
+    /// Open the next season for [start, end) with the given scoring (ER, admin only)
+    /// The previous season must be finalized. Painting is rejected until `start`.
+    pub fn open_season(
+        ctx: Context<OpenSeason>,
+        start: u64,
+        end: u64,
+        rules: SeasonRules,
+    ) -> Result<()> {
+        seasons::open_season(ctx, start, end, rules)
+    }
+
+    /// Close a season once it has ended and commit the result to the base layer (ER, permissionless)
+    /// Every Faction account is passed as a remaining account; their season counters are
+    /// rolled up into the season. The winner is the faction with the most points (lowest
+    /// id on ties, 0 if nobody scored). Painting stays frozen until the next season opens.
+    pub fn finalize_season<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeSeason<'info>>) -> Result<()> {
+        seasons::finalize_season(ctx)
+    }
+
+    /// Snapshot a finalized season's scores into its own account (base layer, permissionless)
+    /// Reads the season singleton as committed by finalize_season.
+    pub fn archive_season(ctx: Context<ArchiveSeason>, season_id: u32) -> Result<()> {
+        seasons::archive_season(ctx, season_id)
+    }
+
+    // ========================================
+    // Leaderboards
+    // ========================================
+
+    /// Create the leaderboard of a metric (base layer, once per metric, permissionless)
+    /// Delegate it with delegate_leaderboard; scores are submitted on the ER.
+    pub fn initialize_leaderboard(
+        ctx: Context<InitializeLeaderboard>,
+        metric: LeaderboardMetric,
+    ) -> Result<()> {
+        leaderboards::initialize_leaderboard(ctx, metric)
+    }
+
+    /// Delegate a leaderboard to Ephemeral Rollups
+    pub fn delegate_leaderboard(
+        ctx: Context<DelegateLeaderboard>,
+        metric: LeaderboardMetric,
+    ) -> Result<()> {
+        leaderboards::delegate_leaderboard(ctx, metric)
+    }
+
+    /// Submit a wallet's current score to a leaderboard (ER, permissionless)
+    /// The score is read from the wallet's counter account passed as `source`:
+    /// WalletStats for ShardsOwned, PlayerProfile for EnemyPixels and Xp, RewardAccrual
+    /// for RewardsEarned. Entries are ordered by score, then wallet; a full board only
+    /// evicts its last entry for a strictly higher score.
+    pub fn submit_score(ctx: Context<SubmitScore>, metric: LeaderboardMetric) -> Result<()> {
+        leaderboards::submit_score(ctx, metric)
+    }
+
+    // ========================================
+    // Artwork
+    // ========================================
+
+    /// Register a w×h rectangle of the canvas at global (x, y) as an artwork (base layer)
+    /// Signed by the creator's main wallet; the rectangle may span shards. The artwork only
+    /// references the pixels, which keep changing; read them with get_artwork_pixels.
+    pub fn register_artwork(
+        ctx: Context<RegisterArtwork>,
+        x: u32,
+        y: u32,
+        w: u16,
+        h: u16,
+        title: String,
+    ) -> Result<()> {
+        artwork::register_artwork(ctx, x, y, w, h, title)
+    }
+
+    /// Issue the ownership card of an artwork to its creator (base layer, creator only)
+    /// A lightweight, program-tracked alternative to an NFT; move it with transfer_artwork_card.
+    pub fn mint_artwork_card(ctx: Context<MintArtworkCard>) -> Result<()> {
+        artwork::mint_artwork_card(ctx)
+    }
+
+    /// Give an artwork card to another wallet (base layer, current owner only)
+    pub fn transfer_artwork_card(ctx: Context<TransferArtworkCard>, new_owner: Pubkey) -> Result<()> {
+        artwork::transfer_artwork_card(ctx, new_owner)
+    }
+
+    // ========================================
+    // Templates & Bounties
+    // ========================================
+
+    /// Create an empty w×h painting template at global (x, y) (base layer)
+    /// The target is uploaded with write_template_chunk and frozen with lock_template.
+    /// `threshold_bps` is the completion (basis points of target pixels) that releases
+    /// the template's bounty to its contributors. Every shard the template covers must be
+    /// passed as a remaining account and have its painter layer enabled.
+    pub fn create_template<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateTemplate<'info>>,
+        template_id: u32,
+        x: u32,
+        y: u32,
+        w: u16,
+        h: u16,
+        threshold_bps: u16,
+    ) -> Result<()> {
+        templates::create_template(ctx, template_id, x, y, w, h, threshold_bps)
+    }
+
+    /// Write part of a template's target (base layer, creator only, before locking)
+    /// `data` holds palette indices in row-major order starting at `offset`; 0 = don't care.
+    pub fn write_template_chunk(ctx: Context<WriteTemplateChunk>, offset: u16, data: Vec<u8>) -> Result<()> {
+        templates::write_template_chunk(ctx, offset, data)
+    }
+
+    /// Freeze a template's target and open its bounty escrow (base layer, creator only)
+    /// The bounty can complete until `deadline` (unix time); after that, funders of a
+    /// bounty that never completed can take their lamports back with refund_bounty.
+    pub fn lock_template(ctx: Context<LockTemplate>, deadline: u64) -> Result<()> {
+        templates::lock_template(ctx, deadline)
+    }
+
+    /// Add lamports to a template's bounty (base layer, anyone, until it completes or
+    /// its deadline passes)
+    /// The lamports are held by the Bounty PDA itself; each funder's total is recorded in
+    /// a BountyFunding account for refunds.
+    pub fn fund_bounty(ctx: Context<FundBounty>, amount: u64) -> Result<()> {
+        templates::fund_bounty(ctx, amount)
+    }
+
+    /// Compare a locked template against the canvas and record its completion (base layer)
+    /// Permissionless. Every shard the template covers must be passed as a remaining
+    /// account, followed by its ShardPainters account; the painter layer must still be enabled.
+    /// Delegated shards and painter layers are read at their last committed state.
+    ///
+    /// The first check at or above the threshold before the bounty's deadline completes it:
+    /// matching pixels are credited to their last painter and the top contributors are
+    /// snapshotted for claim_bounty. Partner programs are not credited. Without any
+    /// attributed pixel the bounty stays open.
+    pub fn check_template_progress<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CheckTemplateProgress<'info>>,
+    ) -> Result<()> {
+        templates::check_template_progress(ctx)
+    }
+
+    /// Pay a contributor's share of a completed bounty (base layer, contributor's main wallet)
+    /// The share is proportional to the contributor's matching pixels at completion.
+    pub fn claim_bounty(ctx: Context<ClaimBounty>) -> Result<()> {
+        templates::claim_bounty(ctx)
+    }
+
+    /// Return a funder's lamports from a bounty that missed its deadline (base layer)
+    /// Closes the funder's BountyFunding record.
+    pub fn refund_bounty(ctx: Context<RefundBounty>) -> Result<()> {
+        templates::refund_bounty(ctx)
+    }
+
+    /// Close a settled bounty, sending what is left to the template creator (base layer)
+    /// A completed bounty settles once every contributor has claimed or BOUNTY_CLAIM_WINDOW
+    /// has passed, so shares nobody can claim (e.g. partner programs) and rounding dust
+    /// are recovered. A bounty that never completed settles once every funder has refunded.
+    pub fn sweep_bounty(ctx: Context<SweepBounty>) -> Result<()> {
+        templates::sweep_bounty(ctx)
+    }
+
+    // ========================================
+    // Views (return data, meant to be simulated)
+    // ========================================
+
+    /// Color of the pixel at global (px, py); 0 = transparent
+    /// Works on both layers: delegated shards return their last committed state on base.
+    pub fn get_pixel(
+        ctx: Context<GetPixel>,
+        shard_x: u16,
+        shard_y: u16,
+        px: u32,
+        py: u32,
+    ) -> Result<u8> {
+        views::get_pixel(ctx, shard_x, shard_y, px, py)
+    }
+
+    /// Colors of a w×h local rectangle starting at (x0, y0), row by row
+    /// The rectangle must lie within the shard and hold at most MAX_VIEW_REGION_PIXELS.
+    pub fn get_region(
+        ctx: Context<GetPixel>,
+        _shard_x: u16,
+        _shard_y: u16,
+        x0: u8,
+        y0: u8,
+        w: u8,
+        h: u8,
+    ) -> Result<Vec<u8>> {
+        views::get_region(ctx, x0, y0, w, h)
+    }
+
+    /// Current colors of rows [row, row + rows) of an artwork, row by row
+    /// Pass the shards covering those rows as remaining accounts (any order, either layer);
+    /// a shard that is not passed, e.g. because it was never created, reads as transparent.
+    /// At most MAX_VIEW_REGION_PIXELS pixels per call, so page through tall artworks.
+    pub fn get_artwork_pixels<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetArtworkPixels<'info>>,
+        row: u16,
+        rows: u16,
+    ) -> Result<Vec<u8>> {
+        views::get_artwork_pixels(ctx, row, rows)
+    }
+
+    /// Burst and cooldown state of a session (delegated or not)
+    pub fn get_cooldown_status(ctx: Context<GetCooldownStatus>) -> Result<CooldownStatus> {
+        views::get_cooldown_status(ctx)
+    }
+
+    // ========================================
+    // Canvas Checkpoints
+    // ========================================
+
+    /// Start a new canvas checkpoint covering every shard created so far (base layer)
+    /// Permissionless; the caller pays rent. Fill it in with crank_checkpoint. The
+    /// checkpoint is tagged with the current season (0 before the first one).
+    pub fn start_checkpoint(ctx: Context<StartCheckpoint>) -> Result<()> {
+        checkpoint::start_checkpoint(ctx)
+    }
+
+    /// Add the next batch of shards to a checkpoint (base layer, permissionless)
+    /// Shards are passed as remaining accounts in creation order, continuing from
+    /// `next_index`. Delegated shards contribute their last committed state, as of the
+    /// crank that adds them; the seq range seen is recorded. The call that adds the last
+    /// shard finalizes the root.
+    pub fn crank_checkpoint(ctx: Context<CrankCheckpoint>, _checkpoint_id: u32) -> Result<()> {
+        checkpoint::crank_checkpoint(ctx)
+    }
+
+    /// Prove a shard's state at a finalized checkpoint; fails unless the proof holds
+    /// `pixels_hash` is shard_pixels_hash of the claimed pixels and `proof` holds the
+    /// CHECKPOINT_DEPTH sibling hashes from the shard's leaf (at position `index`) upwards.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_checkpoint(
+        ctx: Context<VerifyCheckpoint>,
+        _checkpoint_id: u32,
         shard_x: u16,
         shard_y: u16,
-        pixels: Vec<BulkPixel>,
+        index: u32,
+        seq: u64,
+        pixels_hash: [u8; 32],
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
-        // Validate bulk size
-        require!(pixels.len() > 0, PixelError::EmptyBulkPixels);
-        require!(pixels.len() <= COOLDOWN_LIMIT as usize, PixelError::BulkTooLarge);
-        
-        let shard = &mut ctx.accounts.shard;
-        let session = &mut ctx.accounts.session;
-        let is_owner = shard.creator == session.main_address;
-        
-        // Verify shard coordinates match
-        require!(
-            shard.shard_x == shard_x && shard.shard_y == shard_y,
-            PixelError::ShardMismatch
-        );
-        
-        // Handle cooldown for non-owners
-        if !is_owner {
-            let clock = Clock::get()?;
-            let now = clock.unix_timestamp as u64;
-            let pixels_to_place = pixels.len() as u8;
-            
-            // Check if cooldown has reset
-            if session.cooldown_counter >= COOLDOWN_LIMIT {
-                if now.saturating_sub(session.last_place_timestamp) >= COOLDOWN_PERIOD {
-                    session.cooldown_counter = 0;
-                } else {
-                    return err!(PixelError::Cooldown);
-                }
-            }
-            
-            // Check if we would exceed the limit
-            let new_counter = session.cooldown_counter.saturating_add(pixels_to_place);
-            require!(new_counter <= COOLDOWN_LIMIT, PixelError::BulkExceedsCooldown);
-            
-            // Update counter
-            session.cooldown_counter = new_counter;
-            
-            // If we hit the limit, record timestamp
-            if session.cooldown_counter >= COOLDOWN_LIMIT {
-                session.last_place_timestamp = now;
-            }
-        }
-        
-        // Calculate base global coordinates for this shard
-        let base_px = (shard_x as u32) * SHARD_DIMENSION;
-        let base_py = (shard_y as u32) * SHARD_DIMENSION;
-        let timestamp = Clock::get()?.unix_timestamp as u64;
-        let painter = ctx.accounts.signer.key();
-        let main_wallet = session.main_address;
-        
-        // Place each pixel
-        for pixel in pixels.iter() {
-            // Validate local coordinates
-            require!(
-                (pixel.local_x as u32) < SHARD_DIMENSION && (pixel.local_y as u32) < SHARD_DIMENSION,
-                PixelError::InvalidPixelCoord
-            );
-            require!(pixel.color > 0 && pixel.color <= AVAILABLE_COLORS, PixelError::InvalidColor);
-            
-            // Calculate local pixel index
-            let local_pixel_id = (pixel.local_y as u32 * SHARD_DIMENSION + pixel.local_x as u32) as usize;
-            
-            // Set the pixel color
-            shard.pixels[local_pixel_id] = pixel.color;
-            
-            // Calculate global coordinates for event
-            let global_px = base_px + pixel.local_x as u32;
-            let global_py = base_py + pixel.local_y as u32;
-            
-            // Emit event for each pixel
-            emit!(PixelChanged {
-                px: global_px,
-                py: global_py,
-                color: pixel.color,
-                painter,
-                main_wallet,
-                timestamp,
-            });
-        }
-        
-        msg!(
-            "Bulk placed {} pixels on shard ({}, {})",
-            pixels.len(),
-            shard_x,
-            shard_y
-        );
-
-        Ok(())
+        checkpoint::verify_checkpoint(ctx, shard_x, shard_y, index, seq, pixels_hash, proof)
     }
 
     // ========================================
@@ -434,18 +1410,275 @@ pub mod magicplace {
     /// Commit shard state from ER to base layer
     pub fn commit_shard(
         ctx: Context<CommitShardInput>, 
-        _shard_x: u16, 
-        _shard_y: u16
+        shard_x: u16, 
+        shard_y: u16
     ) -> Result<()> {
+        let shard_info = ctx.accounts.shard.to_account_info();
+        let mut accounts = vec![&shard_info];
+        let painters_info = ctx.accounts.painters.as_ref().map(|painters| painters.to_account_info());
+        if let Some(painters_info) = painters_info.as_ref() {
+            accounts.push(painters_info);
+        }
+        let history_info = ctx.accounts.history.as_ref().map(|history| history.to_account_info());
+        if let Some(history_info) = history_info.as_ref() {
+            accounts.push(history_info);
+        }
         commit_accounts(
             &ctx.accounts.payer,
-            vec![&ctx.accounts.shard.to_account_info()],
+            accounts,
             &ctx.accounts.magic_context,
             &ctx.accounts.magic_program,
         )?;
         msg!("Shard committed to base layer");
+
+        emit!(ShardCommitted {
+            shard_x,
+            shard_y,
+            seq: ctx.accounts.shard.seq,
+            content_hash: ctx.accounts.shard.content_hash,
+            timestamp: Clock::get()?.unix_timestamp as u64,
+        });
+        Ok(())
+    }
+
+    /// Commit and undelegate a shard that still has the old layout (ER, permissionless)
+    /// Legacy shards can't be loaded by the other instructions; back on the base layer
+    /// they can be upgraded with migrate_shard and delegated again.
+    pub fn undelegate_legacy_shard(
+        ctx: Context<UndelegateLegacyShard>,
+        shard_x: u16,
+        shard_y: u16,
+    ) -> Result<()> {
+        require!(is_legacy_shard(&ctx.accounts.shard), PixelError::ShardAlreadyMigrated);
+        commit_and_undelegate_accounts(
+            &ctx.accounts.payer,
+            vec![&ctx.accounts.shard.to_account_info()],
+            &ctx.accounts.magic_context,
+            &ctx.accounts.magic_program,
+        )?;
+        msg!("Legacy shard ({}, {}) undelegated for migration", shard_x, shard_y);
         Ok(())
     }
+
+    /// Commit a shard owner's reward accrual to the base layer so it can be claimed
+    pub fn commit_rewards(ctx: Context<CommitRewards>, _owner: Pubkey) -> Result<()> {
+        rewards::commit_rewards(ctx)
+    }
+}
+// ========================================
+// Partner CPI Helpers
+// ========================================
+
+/// Typed helpers for partner programs painting through CPI (enable the `cpi` feature)
+///
+/// A partner program is registered by the admin with `register_partner`, then calls
+/// `partner::place_pixels` from its own instructions. The call is signed with the
+/// partner's `[PARTNER_AUTHORITY_SEED]` PDA, whose bump is returned by
+/// `partner::authority_address`; pass that PDA as `partner_authority`.
+#[cfg(feature = "cpi")]
+pub mod partner {
+    use super::*;
+
+    /// Signer PDA (and bump) a partner program uses when calling into magicplace
+    pub fn authority_address(partner_program: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[PARTNER_AUTHORITY_SEED], partner_program)
+    }
+
+    /// Registry entry address of a partner program
+    pub fn registry_address(partner_program: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[PARTNER_SEED, partner_program.as_ref()], &crate::ID).0
+    }
+
+    /// Place pixels as the calling partner program, signing with its authority PDA
+    pub fn place_pixels<'info>(
+        magicplace_program: AccountInfo<'info>,
+        accounts: cpi::accounts::PartnerPlacePixels<'info>,
+        authority_bump: u8,
+        shard_x: u16,
+        shard_y: u16,
+        pixels: Vec<BulkPixel>,
+    ) -> Result<()> {
+        let bump = [authority_bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[PARTNER_AUTHORITY_SEED, &bump]];
+        cpi::partner_place_pixels(
+            CpiContext::new_with_signer(magicplace_program, accounts, signer_seeds),
+            shard_x,
+            shard_y,
+            pixels,
+        )
+    }
+}
+
+// ========================================
+// Helpers
+// ========================================
+
+/// Public key and message of the first signature of an Ed25519 verify instruction
+/// The signature, public key and message must all be stored inside the verify instruction
+/// itself (instruction index u16::MAX), otherwise they could be read from another one.
+fn ed25519_signed(ix_data: &[u8]) -> Result<(Pubkey, &[u8])> {
+    // Signature count and padding, then the first signature's seven u16 offsets
+    require!(ix_data.len() >= 16 && ix_data[0] >= 1, PixelError::InvalidAuth);
+    let read_u16 = |at: usize| u16::from_le_bytes([ix_data[at], ix_data[at + 1]]);
+    require!(
+        read_u16(4) == u16::MAX && read_u16(8) == u16::MAX && read_u16(14) == u16::MAX,
+        PixelError::InvalidAuth
+    );
+    let pubkey_offset = read_u16(6) as usize;
+    let offset = read_u16(10) as usize;
+    let size = read_u16(12) as usize;
+    require!(
+        ix_data.len() >= pubkey_offset + 32 && ix_data.len() >= offset + size,
+        PixelError::InvalidAuth
+    );
+    let pubkey = Pubkey::try_from(&ix_data[pubkey_offset..pubkey_offset + 32])
+        .map_err(|_| PixelError::InvalidAuth)?;
+    Ok((pubkey, &ix_data[offset..offset + size]))
+}
+
+/// Create a PDA owned by `owner` with `space` bytes, paying rent from `payer`
+/// Unlike system create_account this still works when someone has already sent lamports
+/// to the (predictable) address: only the missing rent is transferred, then the account
+/// is allocated and assigned with the PDA's signature.
+fn create_pda_account<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let shortfall = rent.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Allocate { account_to_allocate: account.clone() },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Assign { account_to_assign: account.clone() },
+            signer_seeds,
+        ),
+        owner,
+    )?;
+    Ok(())
+}
+
+/// Whether a shard account still has the layout from before PixelShard gained its
+/// layers/seq/content_hash/index/hook_program fields (see migrate_shard)
+fn is_legacy_shard(info: &AccountInfo) -> bool {
+    let data = info.data.borrow();
+    data.len() == PixelShard::TAIL_OFFSET + PixelShard::LEGACY_TAIL_LEN
+        && data[..8] == *PixelShard::DISCRIMINATOR
+        && data[12..16] == (BYTES_PER_SHARD as u32).to_le_bytes()
+}
+
+/// Creator of a shard, read in place (same offset in the legacy and current layouts)
+fn shard_creator(info: &AccountInfo) -> Result<Pubkey> {
+    let data = info.data.borrow();
+    require!(
+        data.len() >= PixelShard::TAIL_OFFSET + 32,
+        PixelError::InvalidShardAccount
+    );
+    Pubkey::try_from(&data[PixelShard::TAIL_OFFSET..PixelShard::TAIL_OFFSET + 32])
+        .map_err(|_| PixelError::InvalidShardAccount.into())
+}
+
+/// Index of a local (x, y) position in a shard's pixel buffer
+fn local_pixel_index(local_x: u32, local_y: u32) -> usize {
+    (local_y * SHARD_DIMENSION + local_x) as usize
+}
+
+/// Whether (x, y) is a valid local position within a shard
+fn valid_local(x: u8, y: u8) -> bool {
+    (x as u32) < SHARD_DIMENSION && (y as u32) < SHARD_DIMENSION
+}
+
+/// Writes that skip the paint hook are limited to the owner on hooked shards
+fn require_hook_bypass_allowed(hook_program: &Pubkey, creator: &Pubkey, main_wallet: &Pubkey) -> Result<()> {
+    require!(
+        *hook_program == Pubkey::default() || creator == main_wallet,
+        PixelError::ShardHasPaintHook
+    );
+    Ok(())
+}
+
+/// Run a shard's paint hook on a pending change; any error from the hook rejects it
+/// `remaining` must start with the hook program and its validation account
+/// ([HOOK_VALIDATION_SEED, shard] under the hook program, holding an 8-byte
+/// discriminator and a borsh `Vec<HookAccount>`), followed by the accounts it lists.
+/// The hook gets the shard, session, validation account and listed accounts, none as signers.
+fn invoke_paint_hook<'info>(
+    shard: &AccountInfo<'info>,
+    session: &AccountInfo<'info>,
+    remaining: &[AccountInfo<'info>],
+    args: PaintHookArgs,
+    hook_program: &Pubkey,
+) -> Result<()> {
+    require!(remaining.len() >= 2, PixelError::InvalidHookAccounts);
+    let (program, validation) = (&remaining[0], &remaining[1]);
+    require_keys_eq!(program.key(), *hook_program, PixelError::InvalidHookAccounts);
+
+    let (expected_validation, _) =
+        Pubkey::find_program_address(&[HOOK_VALIDATION_SEED, shard.key.as_ref()], hook_program);
+    require_keys_eq!(validation.key(), expected_validation, PixelError::InvalidHookAccounts);
+    require!(validation.owner == hook_program, PixelError::InvalidHookAccounts);
+
+    let extra: Vec<HookAccount> = {
+        let data = validation.try_borrow_data()?;
+        require!(data.len() >= 8, PixelError::InvalidHookAccounts);
+        AnchorDeserialize::deserialize(&mut &data[8..]).map_err(|_| PixelError::InvalidHookAccounts)?
+    };
+    require!(
+        extra.len() <= MAX_HOOK_ACCOUNTS && remaining.len() >= 2 + extra.len(),
+        PixelError::InvalidHookAccounts
+    );
+
+    let mut metas = vec![
+        AccountMeta::new_readonly(shard.key(), false),
+        AccountMeta::new_readonly(session.key(), false),
+        AccountMeta::new_readonly(validation.key(), false),
+    ];
+    let mut infos = vec![shard.clone(), session.clone(), validation.clone()];
+    for (account, info) in extra.iter().zip(&remaining[2..]) {
+        require_keys_eq!(info.key(), account.pubkey, PixelError::InvalidHookAccounts);
+        metas.push(if account.is_writable {
+            AccountMeta::new(account.pubkey, false)
+        } else {
+            AccountMeta::new_readonly(account.pubkey, false)
+        });
+        infos.push(info.clone());
+    }
+    infos.push(program.clone());
+
+    let mut data = PAINT_HOOK_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+    anchor_lang::solana_program::program::invoke(
+        &Instruction {
+            program_id: *hook_program,
+            accounts: metas,
+            data,
+        },
+        &infos,
+    )?;
+    Ok(())
 }
 
 // ========================================
@@ -457,7 +1690,7 @@ pub mod magicplace {
 /// IMPORTANT: The transaction must include an Ed25519 verify instruction as the FIRST
 /// instruction, verifying that main_wallet signed the authorization message.
 #[derive(Accounts)]
-#[instruction(main_wallet: Pubkey, signature: [u8; 64])]
+#[instruction(main_wallet: Pubkey, signature: [u8; 64], referrer: Option<Pubkey>)]
 pub struct InitializeUser<'info> {
     /// Session account PDA derived from the MAIN wallet (not session key)
     /// This ensures each main wallet has exactly one session account
@@ -476,6 +1709,26 @@ pub struct InitializeUser<'info> {
     /// CHECK: Instructions sysvar for Ed25519 signature verification
     #[account(address = instructions::ID)]
     pub instructions_sysvar: AccountInfo<'info>,
+    /// Referral record of main_wallet, only passed with a referrer
+    /// A wallet referring itself would alias both records, so it is rejected here
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [REFERRAL_SEED, main_wallet.as_ref()],
+        bump,
+        constraint = referrer != Some(main_wallet) @ PixelError::InvalidReferrer
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+    /// Referral record of the referrer, only passed with a referrer
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [REFERRAL_SEED, referrer.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub referrer_referral: Option<Account<'info, Referral>>,
 }
 
 /// Delegate a user session account to Ephemeral Rollups
@@ -503,7 +1756,7 @@ pub struct DelegateUser<'info> {
 /// Initialize a shard (without delegation)
 /// Call delegate_shard separately after this to delegate to ER
 #[derive(Accounts)]
-#[instruction(shard_x: u16, shard_y: u16)]
+#[instruction(shard_x: u16, shard_y: u16, main_wallet: Pubkey)]
 pub struct InitializeShard<'info> {
     /// The shard account to initialize
     #[account(
@@ -522,12 +1775,82 @@ pub struct InitializeShard<'info> {
     )]
     pub session: UncheckedAccount<'info>,
 
+    /// Global canvas counters, created along with the first shard
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + CanvasState::INIT_SPACE,
+        seeds = [CANVAS_SEED],
+        bump
+    )]
+    pub canvas: Account<'info, CanvasState>,
+
+    /// Shard counter of the session's main wallet, created along with its first shard
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + WalletStats::INIT_SPACE,
+        seeds = [WALLET_STATS_SEED, main_wallet.as_ref()],
+        bump
+    )]
+    pub wallet_stats: Account<'info, WalletStats>,
+
+    /// Territory counter the creator's shards are counted in, required once it has one
+    #[account(
+        mut,
+        seeds = [FACTION_TERRITORY_SEED, &faction_territory.id.to_le_bytes()],
+        bump = faction_territory.bump,
+    )]
+    pub faction_territory: Option<Account<'info, FactionTerritory>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
+/// Upgrade a legacy shard to the current layout (base layer)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16, creator: Pubkey)]
+pub struct MigrateShard<'info> {
+    /// CHECK: Legacy shard that Anchor can't deserialize. Verified by seeds, owner check and is_legacy_shard.
+    #[account(mut, seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()], bump)]
+    pub shard: UncheckedAccount<'info>,
+
+    /// Global canvas counters, the source of the shard's index
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + CanvasState::INIT_SPACE,
+        seeds = [CANVAS_SEED],
+        bump
+    )]
+    pub canvas: Account<'info, CanvasState>,
+
+    /// Shard counter of the shard's creator, credited with the migrated shard
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + WalletStats::INIT_SPACE,
+        seeds = [WALLET_STATS_SEED, creator.as_ref()],
+        bump
+    )]
+    pub wallet_stats: Account<'info, WalletStats>,
+
+    /// Territory counter the creator's shards are counted in, required once it has one
+    #[account(
+        mut,
+        seeds = [FACTION_TERRITORY_SEED, &faction_territory.id.to_le_bytes()],
+        bump = faction_territory.bump,
+    )]
+    pub faction_territory: Option<Account<'info, FactionTerritory>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 /// Delegate an existing shard to Ephemeral Rollups
 /// This should be called after initialize_shard in a separate transaction
 #[delegate]
@@ -543,9 +1866,10 @@ pub struct DelegateShard<'info> {
     pub pda: AccountInfo<'info>,
 }
 
+/// Set a shard's paint hook (ER)
 #[derive(Accounts)]
 #[instruction(shard_x: u16, shard_y: u16)]
-pub struct PlacePixel<'info> {
+pub struct SetPaintHook<'info> {
     #[account(
         mut,
         seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
@@ -554,24 +1878,120 @@ pub struct PlacePixel<'info> {
     pub shard: Account<'info, PixelShard>,
 
     #[account(
-        mut,
         seeds = [b"session", signer.key().as_ref()],
         bump = session.bump,
     )]
     pub session: Account<'info, SessionAccount>,
 
-    #[account(mut)]
     pub signer: Signer<'info>,
 }
 
+/// Register a partner program (base layer, admin only)
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct RegisterPartner<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Partner::INIT_SPACE,
+        seeds = [PARTNER_SEED, program_id.as_ref()],
+        bump
+    )]
+    pub partner: Account<'info, Partner>,
+
+    /// Program upgrade authority, acting as canvas admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PixelError::NotAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegate a partner registry entry to Ephemeral Rollups
+#[delegate]
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct DelegatePartner<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The partner PDA to delegate - validated by seeds constraint
+    #[account(mut, del, seeds = [PARTNER_SEED, program_id.as_ref()], bump)]
+    pub pda: AccountInfo<'info>,
+}
+
+/// Update a partner registry entry (admin only)
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct UpdatePartner<'info> {
+    #[account(
+        mut,
+        seeds = [PARTNER_SEED, program_id.as_ref()],
+        bump = partner.bump
+    )]
+    pub partner: Account<'info, Partner>,
+
+    /// Program upgrade authority, acting as canvas admin
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PixelError::NotAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+}
+
+/// Paint as a partner program (ER, through CPI)
+/// With the `cpi` feature this is `magicplace::cpi::accounts::PartnerPlacePixels`.
 #[derive(Accounts)]
 #[instruction(shard_x: u16, shard_y: u16)]
-pub struct GetPixel<'info> {
+pub struct PartnerPlacePixels<'info> {
     #[account(
+        mut,
         seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
         bump = shard.bump
     )]
     pub shard: Account<'info, PixelShard>,
+
+    /// Painter attribution layer, required when the shard has one enabled
+    #[account(
+        mut,
+        seeds = [PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = painters.load()?.bump
+    )]
+    pub painters: Option<AccountLoader<'info, ShardPainters>>,
+
+    /// Change history ring buffer, required when the shard has one enabled
+    #[account(
+        mut,
+        seeds = [HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump = history.load()?.bump
+    )]
+    pub history: Option<AccountLoader<'info, ShardHistory>>,
+
+    /// The calling program's registry entry
+    #[account(
+        mut,
+        seeds = [PARTNER_SEED, partner.program_id.as_ref()],
+        bump = partner.bump
+    )]
+    pub partner: Account<'info, Partner>,
+
+    /// The calling program's [PARTNER_AUTHORITY_SEED] PDA, signed with invoke_signed
+    pub partner_authority: Signer<'info>,
+
+    /// CHECK: The season singleton, read-only (may not exist yet). Checked by load_open_season.
+    #[account(seeds = [SEASON_SEED], bump)]
+    pub season: UncheckedAccount<'info>,
 }
 
 #[delegate]
@@ -592,6 +2012,23 @@ pub struct CommitShardInput<'info> {
     pub payer: Signer<'info>,
     #[account(mut, seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()], bump = shard.bump)]
     pub shard: Account<'info, PixelShard>,
+    /// Painter attribution layer, committed alongside the shard when passed
+    #[account(mut, seeds = [PAINTERS_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()], bump = painters.load()?.bump)]
+    pub painters: Option<AccountLoader<'info, ShardPainters>>,
+    /// Change history, committed alongside the shard when passed
+    #[account(mut, seeds = [HISTORY_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()], bump = history.load()?.bump)]
+    pub history: Option<AccountLoader<'info, ShardHistory>>,
+}
+
+#[commit]
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct UndelegateLegacyShard<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: Legacy shard that Anchor can't deserialize. Verified by seeds and is_legacy_shard.
+    #[account(mut, seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()], bump)]
+    pub shard: UncheckedAccount<'info>,
 }
 
 // ========================================
@@ -618,6 +2055,55 @@ pub struct PixelShard {
     pub creator: Pubkey,
     /// PDA bump seed
     pub bump: u8,
+    /// Companion layers that writes must keep in sync (LAYER_* bit flags)
+    pub layers: u8,
+    /// Number of write operations applied so far; events carry it so indexers can spot gaps
+    pub seq: u64,
+    /// Wrapping sum of pixel_hash over all pixels, to verify a locally rebuilt shard
+    pub content_hash: u64,
+    /// Position in creation order (0, 1, 2, ...), the shard's leaf in canvas checkpoints
+    pub index: u32,
+    /// Program whose `on_paint` vets place_pixel / place_pixels_bulk (default = no hook)
+    pub hook_program: Pubkey,
+}
+
+impl PixelShard {
+    /// Offset of the pixel bytes in account data (discriminator + coords + Vec length prefix)
+    const PIXELS_OFFSET: usize = 8 + 2 + 2 + 4;
+    /// Offset of the fields stored after the pixel buffer
+    const TAIL_OFFSET: usize = Self::PIXELS_OFFSET + BYTES_PER_SHARD;
+    /// Size of the tail: creator, bump, layers, seq, content_hash, index, hook_program
+    const TAIL_LEN: usize = 32 + 1 + 1 + 8 + 8 + 4 + 32;
+    /// Size of the tail of shards created before migrate_shard's fields: creator, bump
+    const LEGACY_TAIL_LEN: usize = 32 + 1;
+
+    /// Bump seq and apply `hash_delta` to the content hash after a write; returns the new seq
+    fn record_write(&mut self, hash_delta: u64) -> u64 {
+        self.seq += 1;
+        self.content_hash = self.content_hash.wrapping_add(hash_delta);
+        self.seq
+    }
+}
+
+/// Registry entry letting a partner program paint through CPI
+#[account]
+#[derive(InitSpace)]
+pub struct Partner {
+    /// Program allowed to paint; it signs with its [PARTNER_AUTHORITY_SEED] PDA
+    pub program_id: Pubkey,
+    /// Bump of that signer PDA under program_id
+    pub authority_bump: u8,
+    /// Pixels allowed per quota window
+    pub quota: u32,
+    /// Quota window length in seconds
+    pub quota_period: u64,
+    /// Pixels placed in the current window
+    pub used: u32,
+    pub window_start: u64,
+    /// Pixels placed over the partner's lifetime
+    pub total_pixels: u64,
+    pub enabled: bool,
+    pub bump: u8,
 }
 
 #[account]
@@ -632,16 +2118,25 @@ pub struct SessionAccount {
     pub bump: u8,
 }
 
-/// Pixel data for bulk placement
-/// Uses local coordinates within a shard (0-89)
+/// Pending change passed to a shard's paint hook
+/// Matches the arguments of an Anchor `on_paint(ctx, args: PaintHookArgs)` instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PaintHookArgs {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    /// Main wallet of the painter
+    pub main_wallet: Pubkey,
+    /// Signer of the placement (session key)
+    pub painter: Pubkey,
+    /// Pixels about to be written, in local coordinates
+    pub pixels: Vec<BulkPixel>,
+}
+
+/// Extra account a paint hook needs, as listed in its validation account
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct BulkPixel {
-    /// Local X coordinate within shard (0-89)
-    pub local_x: u8,
-    /// Local Y coordinate within shard (0-89)
-    pub local_y: u8,
-    /// Color index (1-255, 0 is reserved for transparent)
-    pub color: u8,
+pub struct HookAccount {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
 }
 
 // ========================================
@@ -664,10 +2159,108 @@ pub enum PixelError {
     Cooldown,
     #[msg("Bulk pixels array is empty")]
     EmptyBulkPixels,
-    #[msg("Bulk pixels exceeds maximum limit of 50")]
+    #[msg("Too many pixels for one call")]
     BulkTooLarge,
     #[msg("Bulk placement would exceed cooldown limit")]
     BulkExceedsCooldown,
+    #[msg("Invalid shard account")]
+    InvalidShardAccount,
+    #[msg("Multi-shard placement needs 1-4 shard accounts")]
+    InvalidShardCount,
+    #[msg("Shard account passed more than once")]
+    DuplicateShard,
+    #[msg("Packed pixel data length doesn't match pixel count")]
+    InvalidPackedData,
+    #[msg("Flood fill area exceeds maximum of 4096 pixels")]
+    FloodFillTooLarge,
+    #[msg("Only the shard owner can do this")]
+    NotShardOwner,
+    #[msg("Invalid transform: quarter turns must be 0-3")]
+    InvalidTransform,
+    #[msg("Snapshot limit reached for this shard")]
+    SnapshotLimit,
+    #[msg("Unsupported snapshot version")]
+    InvalidSnapshot,
+    #[msg("Shard has a painter layer: pass its ShardPainters account")]
+    PainterLayerRequired,
+    #[msg("Wallet has no attributed pixels on this shard")]
+    PainterNotFound,
+    #[msg("Shard has change history enabled: pass its ShardHistory account")]
+    HistoryLayerRequired,
+    #[msg("Checkpoint is already finalized")]
+    CheckpointAlreadyFinalized,
+    #[msg("Checkpoint is not finalized yet")]
+    CheckpointNotFinalized,
+    #[msg("Shards must be added to a checkpoint in creation order")]
+    CheckpointOutOfOrder,
+    #[msg("Checkpoint proof does not match the root")]
+    InvalidCheckpointProof,
+    #[msg("Region exceeds maximum of 1000 pixels")]
+    RegionTooLarge,
+    #[msg("Only the program upgrade authority can do this")]
+    NotAdmin,
+    #[msg("Partner quota and period must be greater than zero")]
+    InvalidPartnerQuota,
+    #[msg("Signer is not the partner program's authority PDA")]
+    InvalidPartnerAuthority,
+    #[msg("Partner is disabled")]
+    PartnerDisabled,
+    #[msg("Partner quota exceeded, wait for the next window")]
+    PartnerQuotaExceeded,
+    #[msg("Shard has a paint hook: only place_pixel and place_pixels_bulk may paint it")]
+    ShardHasPaintHook,
+    #[msg("Paint hook accounts missing or not matching the validation account")]
+    InvalidHookAccounts,
+    #[msg("Profile does not belong to the session's main wallet")]
+    InvalidProfile,
+    #[msg("No rewards to claim")]
+    NothingToClaim,
+    #[msg("Unknown badge")]
+    InvalidBadge,
+    #[msg("Not enough shards owned for this badge")]
+    BadgeThresholdNotMet,
+    #[msg("Invalid faction, or faction account not matching the profile")]
+    InvalidFaction,
+    #[msg("Faction switch cooldown active")]
+    FactionSwitchCooldown,
+    #[msg("Season is frozen: painting reopens with the next season")]
+    SeasonFrozen,
+    #[msg("Invalid season")]
+    InvalidSeason,
+    #[msg("Previous season not finalized")]
+    SeasonNotFinalized,
+    #[msg("No season running")]
+    SeasonNotRunning,
+    #[msg("Season has not ended yet")]
+    SeasonNotEnded,
+    #[msg("Score source is not the wallet's counter account for this metric")]
+    InvalidScoreSource,
+    #[msg("Invalid artwork: size must be 1-360 per side and the title at most 32 bytes")]
+    InvalidArtwork,
+    #[msg("Signer does not own this artwork card")]
+    NotCardOwner,
+    #[msg("Invalid template: size must be 1-90 per side, threshold 1-10000 bps and target within bounds")]
+    InvalidTemplate,
+    #[msg("Template is locked")]
+    TemplateLocked,
+    #[msg("Template must be locked first")]
+    TemplateNotLocked,
+    #[msg("Bounty already completed")]
+    BountyClosed,
+    #[msg("Bounty has not completed yet")]
+    BountyNotCompleted,
+    #[msg("Signer is not a contributor of this bounty")]
+    NotBountyContributor,
+    #[msg("The shard owner's reward accrual must be passed for pixels on someone else's shard")]
+    OwnerRewardsRequired,
+    #[msg("Invalid referrer or referral accounts")]
+    InvalidReferrer,
+    #[msg("Bounty deadline has passed")]
+    BountyExpired,
+    #[msg("Bounty still has claims or refunds outstanding")]
+    BountyOutstanding,
+    #[msg("Shard already uses the current layout")]
+    ShardAlreadyMigrated,
 }
 
 // ========================================
@@ -675,20 +2268,104 @@ pub enum PixelError {
 // ========================================
 
 #[event]
-pub struct PixelChanged {
-    pub px: u32,
-    pub py: u32,
-    pub color: u8,
-    pub painter: Pubkey,
+pub struct ShardInitialized {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    pub creator: Pubkey,
     pub main_wallet: Pubkey,
     pub timestamp: u64,
 }
 
 #[event]
-pub struct ShardInitialized {
+pub struct SessionCreated {
+    pub main_wallet: Pubkey,
+    pub session_key: Pubkey,
+    pub timestamp: u64,
+}
+
+#[event]
+pub struct SessionDelegated {
+    pub main_wallet: Pubkey,
+    pub session_key: Pubkey,
+    pub timestamp: u64,
+}
+
+#[event]
+pub struct ShardDelegated {
     pub shard_x: u16,
     pub shard_y: u16,
-    pub creator: Pubkey,
-    pub main_wallet: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: u64,
+}
+
+/// Emitted on the ER when a shard commit is scheduled; seq and content_hash are the
+/// state being committed
+#[event]
+pub struct ShardCommitted {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    pub seq: u64,
+    pub content_hash: u64,
+    pub timestamp: u64,
+}
+
+/// A legacy shard was upgraded to the current layout and given canvas index `index`
+#[event]
+pub struct ShardMigrated {
+    pub shard_x: u16,
+    pub shard_y: u16,
+    pub index: u32,
+    pub content_hash: u64,
     pub timestamp: u64,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ed25519 verify instruction data with one signature, everything stored inline
+    fn verify_ix_data(pubkey: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let (pubkey_offset, signature_offset, message_offset) = (16u16, 48u16, 112u16);
+        let mut data = vec![1, 0];
+        for field in [
+            signature_offset,
+            u16::MAX,
+            pubkey_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn ed25519_signed_reads_the_inline_pubkey_and_message() {
+        let pubkey = Pubkey::new_unique();
+        let data = verify_ix_data(&pubkey, b"hello");
+        let (signer, message) = ed25519_signed(&data).unwrap();
+        assert_eq!(signer, pubkey);
+        assert_eq!(message, b"hello");
+    }
+
+    #[test]
+    fn ed25519_signed_rejects_malformed_headers() {
+        let data = verify_ix_data(&Pubkey::new_unique(), b"hello");
+        assert!(ed25519_signed(&data[..15]).is_err());
+        assert!(ed25519_signed(&data[..data.len() - 1]).is_err());
+
+        let mut no_signatures = data.clone();
+        no_signatures[0] = 0;
+        assert!(ed25519_signed(&no_signatures).is_err());
+
+        // Pubkey read from another instruction
+        let mut elsewhere = data;
+        elsewhere[8..10].copy_from_slice(&1u16.to_le_bytes());
+        assert!(ed25519_signed(&elsewhere).is_err());
+    }
+}
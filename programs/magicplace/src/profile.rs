@@ -0,0 +1,266 @@
+//! Player profiles: XP tiers, daily streaks and achievements
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn initialize_profile(ctx: Context<InitializeProfile>, main_wallet: Pubkey) -> Result<()> {
+    let session_info = &ctx.accounts.session;
+    require!(
+        session_info.owner == &crate::ID || session_info.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::InvalidAuth
+    );
+    let session = SessionAccount::try_deserialize(&mut &session_info.data.borrow()[..])?;
+    require!(session.main_address == main_wallet, PixelError::InvalidAuth);
+
+    let profile = &mut ctx.accounts.profile;
+    profile.main_wallet = main_wallet;
+    profile.xp = 0;
+    profile.pixels_on_others = 0;
+    profile.pixels_on_own = 0;
+    profile.faction = 0;
+    profile.faction_joined_at = 0;
+    profile.bump = ctx.bumps.profile;
+
+    msg!("Profile initialized for main wallet: {}", main_wallet);
+    Ok(())
+}
+
+pub(crate) fn delegate_profile(ctx: Context<DelegateProfile>, main_wallet: Pubkey) -> Result<()> {
+    ctx.accounts.delegate_pda(
+        &ctx.accounts.authority,
+        &[PROFILE_SEED, main_wallet.as_ref()],
+        DelegateConfig {
+            validator: ctx.remaining_accounts.first().map(|acc| acc.key()),
+            ..Default::default()
+        },
+    )?;
+
+    msg!("Profile delegated to ER for wallet: {}", main_wallet);
+    Ok(())
+}
+
+// ========================================
+// Helpers
+// ========================================
+
+/// Burst limit and cooldown period (seconds) of an XP tier
+pub(crate) fn cooldown_for_tier(tier: u8) -> (u8, u64) {
+    (
+        COOLDOWN_LIMIT + tier * XP_TIER_BURST_BONUS,
+        COOLDOWN_PERIOD - tier as u64 * XP_TIER_PERIOD_CUT,
+    )
+}
+
+/// Set the `earned` achievement bits; returns the ones that were not set before
+pub(crate) fn unlock_achievements(achievements: &mut u64, earned: u64) -> u64 {
+    let unlocked = earned & !*achievements;
+    *achievements |= unlocked;
+    unlocked
+}
+
+/// Emit one AchievementUnlocked per bit set in `unlocked`
+pub(crate) fn emit_achievements(wallet: Pubkey, unlocked: u64, streak_days: u16, now: u64) {
+    for bit in (0..u64::BITS as u8).filter(|bit| unlocked & (1 << bit) != 0) {
+        emit!(AchievementUnlocked {
+            wallet,
+            achievement: bit,
+            streak_days,
+            timestamp: now,
+        });
+    }
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Create a main wallet's XP profile (base layer)
+#[derive(Accounts)]
+#[instruction(main_wallet: Pubkey)]
+pub struct InitializeProfile<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PlayerProfile::INIT_SPACE,
+        seeds = [PROFILE_SEED, main_wallet.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+
+    /// CHECK: The session account, could be delegated. Verified by seeds and custom owner check.
+    #[account(
+        seeds = [b"session", authority.key().as_ref()],
+        bump,
+    )]
+    pub session: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegate a main wallet's profile to Ephemeral Rollups
+#[delegate]
+#[derive(Accounts)]
+#[instruction(main_wallet: Pubkey)]
+pub struct DelegateProfile<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The profile PDA to delegate - validated by seeds constraint
+    #[account(mut, del, seeds = [PROFILE_SEED, main_wallet.as_ref()], bump)]
+    pub pda: AccountInfo<'info>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// Per main wallet XP and placement counters, shared by all of its sessions
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerProfile {
+    pub main_wallet: Pubkey,
+    pub xp: u64,
+    /// Pixels placed on shards the wallet does not own
+    pub pixels_on_others: u64,
+    /// Pixels placed on the wallet's own shards
+    pub pixels_on_own: u64,
+    /// Faction id, 0 when not in a faction
+    pub faction: u8,
+    /// When the wallet joined its current faction
+    pub faction_joined_at: u64,
+    /// Consecutive days (unix time / SECONDS_PER_DAY) with at least one placement
+    pub streak_days: u16,
+    pub best_streak: u16,
+    /// Last day with a placement, 0 before the first one
+    pub last_active_day: u32,
+    /// Unlocked ACHIEVEMENT_* bits (painting ones; shard ones live in WalletStats)
+    pub achievements: u64,
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    /// XP tier: the number of XP_TIER_THRESHOLDS reached
+    pub fn tier(&self) -> u8 {
+        XP_TIER_THRESHOLDS.iter().filter(|&&t| self.xp >= t).count() as u8
+    }
+
+    pub(crate) fn record_pixels(&mut self, on_own_shard: bool, count: u64) {
+        if on_own_shard {
+            self.pixels_on_own += count;
+        } else {
+            self.pixels_on_others += count;
+            self.xp += count * XP_PER_PIXEL;
+        }
+    }
+
+    /// Advance the daily streak for a placement at `now`; returns newly unlocked achievements
+    pub(crate) fn record_activity(&mut self, now: u64) -> u64 {
+        let day = (now / SECONDS_PER_DAY) as u32;
+        if day != self.last_active_day {
+            self.streak_days = if self.last_active_day != 0 && day == self.last_active_day + 1 {
+                self.streak_days.saturating_add(1)
+            } else {
+                1
+            };
+            self.best_streak = self.best_streak.max(self.streak_days);
+            self.last_active_day = day;
+        }
+
+        let mut earned = ACHIEVEMENT_FIRST_PIXEL;
+        if self.streak_days >= 7 {
+            earned |= ACHIEVEMENT_STREAK_7;
+        }
+        if self.streak_days >= 30 {
+            earned |= ACHIEVEMENT_STREAK_30;
+        }
+        if self.pixels_on_own + self.pixels_on_others >= 10_000 {
+            earned |= ACHIEVEMENT_PIXELS_10K;
+        }
+        unlock_achievements(&mut self.achievements, earned)
+    }
+}
+
+// ========================================
+// Events
+// ========================================
+
+/// `wallet` unlocked ACHIEVEMENT_* bit `achievement`; `streak_days` is the current
+/// streak for painting achievements and 0 for shard ones
+#[event]
+pub struct AchievementUnlocked {
+    pub wallet: Pubkey,
+    pub achievement: u8,
+    pub streak_days: u16,
+    pub timestamp: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> PlayerProfile {
+        PlayerProfile {
+            main_wallet: Pubkey::new_unique(),
+            xp: 0,
+            pixels_on_others: 0,
+            pixels_on_own: 0,
+            faction: 0,
+            faction_joined_at: 0,
+            streak_days: 0,
+            best_streak: 0,
+            last_active_day: 0,
+            achievements: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn higher_tiers_get_a_bigger_burst_and_a_shorter_period() {
+        assert_eq!(cooldown_for_tier(0), (COOLDOWN_LIMIT, COOLDOWN_PERIOD));
+        assert_eq!(cooldown_for_tier(1), (70, 13));
+        let top = XP_TIER_THRESHOLDS.len() as u8;
+        assert_eq!(cooldown_for_tier(top), (100, 7));
+    }
+
+    #[test]
+    fn streak_counts_calendar_days_not_24_hours() {
+        let day = 20_000 * SECONDS_PER_DAY;
+        let mut profile = profile();
+
+        assert_eq!(profile.record_activity(day + SECONDS_PER_DAY - 1), ACHIEVEMENT_FIRST_PIXEL);
+        assert_eq!(profile.streak_days, 1);
+
+        // A minute later is the next day, even though less than 24h passed
+        assert_eq!(profile.record_activity(day + SECONDS_PER_DAY + 59), 0);
+        assert_eq!(profile.streak_days, 2);
+
+        // Painting again the same day doesn't advance the streak
+        profile.record_activity(day + 2 * SECONDS_PER_DAY - 1);
+        assert_eq!(profile.streak_days, 2);
+
+        // Skipping a day restarts it, keeping the best
+        profile.record_activity(day + 3 * SECONDS_PER_DAY);
+        assert_eq!(profile.streak_days, 1);
+        assert_eq!(profile.best_streak, 2);
+    }
+
+    #[test]
+    fn streak_achievements_unlock_once_on_their_day() {
+        let day = 20_000 * SECONDS_PER_DAY;
+        let mut profile = profile();
+
+        for n in 0..6 {
+            profile.record_activity(day + n * SECONDS_PER_DAY);
+        }
+        assert_eq!(profile.record_activity(day + 6 * SECONDS_PER_DAY), ACHIEVEMENT_STREAK_7);
+        assert_eq!(profile.record_activity(day + 7 * SECONDS_PER_DAY), 0);
+        assert_eq!(profile.streak_days, 8);
+        assert_eq!(profile.achievements, ACHIEVEMENT_FIRST_PIXEL | ACHIEVEMENT_STREAK_7);
+    }
+}
@@ -0,0 +1,521 @@
+//! Token rewards for shard owners, referral shares and shard-count badges
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn initialize_reward_mint(_ctx: Context<InitializeRewardMint>) -> Result<()> {
+    msg!("Reward mint initialized");
+    Ok(())
+}
+
+pub(crate) fn initialize_rewards(ctx: Context<InitializeRewards>, owner: Pubkey) -> Result<()> {
+    let rewards = &mut ctx.accounts.rewards;
+    rewards.owner = owner;
+    rewards.accrued_pixels = 0;
+    rewards.bump = ctx.bumps.rewards;
+
+    msg!("Reward accrual initialized for {}", owner);
+    Ok(())
+}
+
+pub(crate) fn delegate_rewards(ctx: Context<DelegateRewards>, owner: Pubkey) -> Result<()> {
+    ctx.accounts.delegate_pda(
+        &ctx.accounts.authority,
+        &[REWARDS_SEED, owner.as_ref()],
+        DelegateConfig {
+            validator: ctx.remaining_accounts.first().map(|acc| acc.key()),
+            ..Default::default()
+        },
+    )?;
+
+    msg!("Reward accrual delegated to ER for {}", owner);
+    Ok(())
+}
+
+pub(crate) fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let rewards_info = &ctx.accounts.rewards;
+    require!(
+        rewards_info.owner == &crate::ID || rewards_info.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::InvalidAuth
+    );
+    let rewards = RewardAccrual::try_deserialize(&mut &rewards_info.data.borrow()[..])?;
+
+    let claim = &mut ctx.accounts.claim;
+    if claim.owner == Pubkey::default() {
+        claim.owner = rewards.owner;
+        claim.bump = ctx.bumps.claim;
+    }
+
+    let pixels = rewards.accrued_pixels.saturating_sub(claim.claimed_pixels);
+    require!(pixels > 0, PixelError::NothingToClaim);
+    let amount = pixels * REWARD_PER_PIXEL;
+
+    let bump = [ctx.bumps.mint_authority];
+    let signer_seeds: &[&[&[u8]]] = &[&[MINT_AUTHORITY_SEED, &bump]];
+
+    // Referral share, taken out of the owner's amount
+    let mut share = 0;
+    let owner_referral_info = ctx.accounts.owner_referral.to_account_info();
+    if !owner_referral_info.data_is_empty() {
+        require!(owner_referral_info.owner == &crate::ID, PixelError::InvalidReferrer);
+        let owner_referral = Referral::try_deserialize(&mut &owner_referral_info.data.borrow()[..])?;
+        if owner_referral.referrer != Pubkey::default() {
+            let referrer_referral = ctx
+                .accounts
+                .referrer_referral
+                .as_mut()
+                .ok_or(PixelError::InvalidReferrer)?;
+            require_keys_eq!(referrer_referral.wallet, owner_referral.referrer, PixelError::InvalidReferrer);
+            let referrer_token_account = ctx
+                .accounts
+                .referrer_token_account
+                .as_ref()
+                .ok_or(PixelError::InvalidReferrer)?;
+            require!(
+                referrer_token_account.mint == ctx.accounts.reward_mint.key()
+                    && referrer_token_account.owner == owner_referral.referrer,
+                PixelError::InvalidReferrer
+            );
+
+            share = amount * REFERRAL_SHARE_BPS / 10_000;
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.reward_mint.to_account_info(),
+                        to: referrer_token_account.to_account_info(),
+                        authority: ctx.accounts.mint_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                share,
+            )?;
+            referrer_referral.referral_rewards += share;
+
+            emit!(ReferralRewarded {
+                referrer: owner_referral.referrer,
+                wallet: claim.owner,
+                amount: share,
+                timestamp: Clock::get()?.unix_timestamp as u64,
+            });
+        }
+    }
+
+    let amount = amount - share;
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.reward_mint.to_account_info(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    claim.claimed_pixels = rewards.accrued_pixels;
+
+    msg!("Claimed {} reward units for {} pixels", amount, pixels);
+
+    emit!(RewardsClaimed {
+        owner: claim.owner,
+        pixels,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+    Ok(())
+}
+
+pub(crate) fn initialize_badge_mint(ctx: Context<InitializeBadgeMint>, badge: u8) -> Result<()> {
+    require!((badge as usize) < BADGE_THRESHOLDS.len(), PixelError::InvalidBadge);
+
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::NonTransferable,
+    ])?;
+    let badge_seed = badge.to_le_bytes();
+    let bump = [ctx.bumps.badge_mint];
+    let signer_seeds: &[&[&[u8]]] = &[&[BADGE_MINT_SEED, &badge_seed, &bump]];
+    create_pda_account(
+        &ctx.accounts.payer,
+        &ctx.accounts.badge_mint,
+        &ctx.accounts.system_program,
+        space,
+        &ctx.accounts.token_program.key(),
+        signer_seeds,
+    )?;
+
+    // The extension must be initialized before the mint itself
+    token_interface::non_transferable_mint_initialize(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        NonTransferableMintInitialize {
+            token_program_id: ctx.accounts.token_program.to_account_info(),
+            mint: ctx.accounts.badge_mint.to_account_info(),
+        },
+    ))?;
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::InitializeMint2 {
+                mint: ctx.accounts.badge_mint.to_account_info(),
+            },
+        ),
+        0,
+        &ctx.accounts.mint_authority.key(),
+        None,
+    )?;
+
+    msg!("Badge {} mint initialized", badge);
+    Ok(())
+}
+
+pub(crate) fn claim_badge(ctx: Context<ClaimBadge>, badge: u8) -> Result<()> {
+    let threshold = *BADGE_THRESHOLDS
+        .get(badge as usize)
+        .ok_or(PixelError::InvalidBadge)?;
+
+    let stats = &mut ctx.accounts.wallet_stats;
+    if stats.badges & (1 << badge) != 0 {
+        msg!("Badge {} already claimed", badge);
+        return Ok(());
+    }
+    require!(stats.shards_owned >= threshold, PixelError::BadgeThresholdNotMet);
+
+    let bump = [ctx.bumps.mint_authority];
+    let signer_seeds: &[&[&[u8]]] = &[&[MINT_AUTHORITY_SEED, &bump]];
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.badge_mint.to_account_info(),
+                to: ctx.accounts.owner_badge_account.to_account_info(),
+                authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+    stats.badges |= 1 << badge;
+
+    msg!("Badge {} claimed with {} shards", badge, stats.shards_owned);
+
+    emit!(BadgeClaimed {
+        owner: stats.owner,
+        badge,
+        shards_owned: stats.shards_owned,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+    Ok(())
+}
+
+pub(crate) fn commit_rewards(ctx: Context<CommitRewards>) -> Result<()> {
+    commit_accounts(
+        &ctx.accounts.payer,
+        vec![&ctx.accounts.rewards.to_account_info()],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+    )?;
+    msg!("Reward accrual committed to base layer");
+    Ok(())
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Create the reward token mint (base layer)
+#[derive(Accounts)]
+pub struct InitializeRewardMint<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [REWARD_MINT_SEED],
+        bump,
+        mint::decimals = REWARD_DECIMALS,
+        mint::authority = mint_authority,
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA used only as the mint authority
+    #[account(seeds = [MINT_AUTHORITY_SEED], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a shard owner's reward accrual (base layer)
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct InitializeRewards<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RewardAccrual::INIT_SPACE,
+        seeds = [REWARDS_SEED, owner.as_ref()],
+        bump
+    )]
+    pub rewards: Account<'info, RewardAccrual>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegate a shard owner's reward accrual to Ephemeral Rollups
+#[delegate]
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct DelegateRewards<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The reward accrual PDA to delegate - validated by seeds constraint
+    #[account(mut, del, seeds = [REWARDS_SEED, owner.as_ref()], bump)]
+    pub pda: AccountInfo<'info>,
+}
+
+/// Mint a shard owner's unclaimed rewards (base layer)
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    /// CHECK: The owner's reward accrual, could be delegated. Verified by seeds and custom owner check.
+    #[account(
+        seeds = [REWARDS_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub rewards: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + RewardClaim::INIT_SPACE,
+        seeds = [REWARD_CLAIM_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub claim: Account<'info, RewardClaim>,
+
+    #[account(mut, seeds = [REWARD_MINT_SEED], bump)]
+    pub reward_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA used only as the mint authority
+    #[account(seeds = [MINT_AUTHORITY_SEED], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    /// The shard owner's main wallet
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The owner's referral record (may not exist). When it names a referrer, the
+    /// referrer's record and reward token account are required and the referral share is paid out of the claim.
+    #[account(seeds = [REFERRAL_SEED, owner.key().as_ref()], bump)]
+    pub owner_referral: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub referrer_referral: Option<Account<'info, Referral>>,
+
+    #[account(mut)]
+    pub referrer_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a badge's non-transferable mint (base layer)
+#[derive(Accounts)]
+#[instruction(badge: u8)]
+pub struct InitializeBadgeMint<'info> {
+    /// CHECK: Created and initialized as a Token-2022 mint in the handler
+    #[account(mut, seeds = [BADGE_MINT_SEED, &badge.to_le_bytes()], bump)]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA used only as the mint authority
+    #[account(seeds = [MINT_AUTHORITY_SEED], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Mint a badge to a shard owner (base layer)
+#[derive(Accounts)]
+#[instruction(badge: u8)]
+pub struct ClaimBadge<'info> {
+    #[account(
+        mut,
+        seeds = [WALLET_STATS_SEED, owner.key().as_ref()],
+        bump = wallet_stats.bump
+    )]
+    pub wallet_stats: Account<'info, WalletStats>,
+
+    #[account(
+        mut,
+        seeds = [BADGE_MINT_SEED, &badge.to_le_bytes()],
+        bump,
+        mint::token_program = token_program,
+    )]
+    pub badge_mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    /// CHECK: PDA used only as the mint authority
+    #[account(seeds = [MINT_AUTHORITY_SEED], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = badge_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_badge_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    /// The shard owner's main wallet
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[commit]
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct CommitRewards<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, seeds = [REWARDS_SEED, owner.as_ref()], bump = rewards.bump)]
+    pub rewards: Account<'info, RewardAccrual>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// Token rewards a shard owner has earned from visitors' pixels (delegated to the ER)
+#[account]
+#[derive(InitSpace)]
+pub struct RewardAccrual {
+    /// Main wallet owning the shards
+    pub owner: Pubkey,
+    /// Pixels placed by others on the owner's shards, ever
+    pub accrued_pixels: u64,
+    pub bump: u8,
+}
+
+/// Who referred a main wallet and whom it referred (base layer only, never delegated)
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub wallet: Pubkey,
+    /// Main wallet that referred this one, default when none
+    pub referrer: Pubkey,
+    /// Main wallets this one referred
+    pub referrals: u32,
+    /// Reward base units earned as a referrer
+    pub referral_rewards: u64,
+    pub bump: u8,
+}
+
+/// How much of an owner's accrual has been minted (base layer only, never delegated)
+#[account]
+#[derive(InitSpace)]
+pub struct RewardClaim {
+    pub owner: Pubkey,
+    pub claimed_pixels: u64,
+    pub bump: u8,
+}
+
+/// Per main wallet ownership stats (base layer only, never delegated)
+#[account]
+#[derive(InitSpace)]
+pub struct WalletStats {
+    pub owner: Pubkey,
+    /// Shards created by the wallet
+    pub shards_owned: u32,
+    /// Claimed badges, bit i set for BADGE_THRESHOLDS[i]
+    pub badges: u8,
+    /// Bit i set once a shard was created inside CONTINENT_SHARD_BOUNDS[i]
+    pub continents: u8,
+    /// Unlocked ACHIEVEMENT_* bits (shard ones; painting ones live in PlayerProfile)
+    pub achievements: u64,
+    /// Faction whose territory counts this wallet's shards (0 = none), kept in line with
+    /// the profile by sync_territory
+    pub territory_faction: u8,
+    pub bump: u8,
+}
+
+impl WalletStats {
+    /// Count a shard created by the wallet; returns the achievement bits it newly unlocked
+    pub(crate) fn record_shard(&mut self, shard_x: u16, shard_y: u16) -> u64 {
+        self.shards_owned += 1;
+        for (i, &(min_x, min_y, max_x, max_y)) in CONTINENT_SHARD_BOUNDS.iter().enumerate() {
+            if (min_x..=max_x).contains(&shard_x) && (min_y..=max_y).contains(&shard_y) {
+                self.continents |= 1 << i;
+            }
+        }
+        let mut earned = ACHIEVEMENT_FIRST_SHARD;
+        if self.continents.count_ones() as usize == CONTINENT_SHARD_BOUNDS.len() {
+            earned |= ACHIEVEMENT_ALL_CONTINENTS;
+        }
+        unlock_achievements(&mut self.achievements, earned)
+    }
+}
+
+// ========================================
+// Events
+// ========================================
+
+/// A shard owner minted the rewards for `pixels` visitor pixels (`amount` in base units, after any referral share)
+#[event]
+pub struct RewardsClaimed {
+    pub owner: Pubkey,
+    pub pixels: u64,
+    pub amount: u64,
+    pub timestamp: u64,
+}
+
+/// A wallet minted badge `badge` (index into the badge ladder)
+#[event]
+pub struct BadgeClaimed {
+    pub owner: Pubkey,
+    pub badge: u8,
+    pub shards_owned: u32,
+    pub timestamp: u64,
+}
+
+/// `wallet` signed up referred by `referrer`, who now has `referrals` referrals
+#[event]
+pub struct ReferralRecorded {
+    pub wallet: Pubkey,
+    pub referrer: Pubkey,
+    pub referrals: u32,
+    pub timestamp: u64,
+}
+
+/// `referrer` received `amount` reward base units from a claim by `wallet`
+#[event]
+pub struct ReferralRewarded {
+    pub referrer: Pubkey,
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub timestamp: u64,
+}
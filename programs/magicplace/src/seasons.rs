@@ -0,0 +1,336 @@
+//! Seasons: opening, freezing, finalizing and archiving
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn initialize_season(ctx: Context<InitializeSeason>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    season.admin = ctx.accounts.admin.key();
+    season.id = 0;
+    season.finalized = true;
+    season.bump = ctx.bumps.season;
+    Ok(())
+}
+
+pub(crate) fn delegate_season(ctx: Context<DelegateSeason>) -> Result<()> {
+    ctx.accounts.delegate_pda(
+        &ctx.accounts.authority,
+        &[SEASON_SEED],
+        DelegateConfig {
+            validator: ctx.remaining_accounts.first().map(|acc| acc.key()),
+            ..Default::default()
+        },
+    )?;
+
+    msg!("Season delegated to ER");
+    Ok(())
+}
+
+pub(crate) fn open_season(
+    ctx: Context<OpenSeason>,
+    start: u64,
+    end: u64,
+    rules: SeasonRules,
+) -> Result<()> {
+    require!(end > start, PixelError::InvalidSeason);
+
+    let season = &mut ctx.accounts.season;
+    require!(season.finalized, PixelError::SeasonNotFinalized);
+
+    season.id += 1;
+    season.start = start;
+    season.end = end;
+    season.rules = rules;
+    season.faction_scores = [0; FACTION_COUNT as usize];
+    season.pixels_placed = 0;
+    season.winner_faction = 0;
+    season.finalized = false;
+
+    msg!("Season {} opened: {} - {}", season.id, start, end);
+
+    emit!(SeasonOpened {
+        season: season.id,
+        start,
+        end,
+        rules,
+    });
+    Ok(())
+}
+
+pub(crate) fn finalize_season<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeSeason<'info>>,
+) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    require!(!season.finalized, PixelError::SeasonNotRunning);
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(now >= season.end, PixelError::SeasonNotEnded);
+
+    require!(
+        ctx.remaining_accounts.len() == FACTION_COUNT as usize,
+        PixelError::InvalidFaction
+    );
+    let mut seen = [false; FACTION_COUNT as usize];
+    for info in ctx.remaining_accounts.iter() {
+        let faction = Account::<Faction>::try_from(info)?;
+        require!((1..=FACTION_COUNT).contains(&faction.id), PixelError::InvalidFaction);
+        let expected = Pubkey::create_program_address(
+            &[FACTION_SEED, &faction.id.to_le_bytes(), &[faction.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| PixelError::InvalidFaction)?;
+        require_keys_eq!(info.key(), expected, PixelError::InvalidFaction);
+
+        let slot = faction.id as usize - 1;
+        require!(!seen[slot], PixelError::InvalidFaction);
+        seen[slot] = true;
+        if faction.season == season.id {
+            season.faction_scores[slot] = faction.season_points;
+            season.pixels_placed += faction.season_pixels;
+        }
+    }
+
+    let mut winner = 0;
+    let mut best = 0;
+    for (i, &score) in season.faction_scores.iter().enumerate() {
+        if score > best {
+            best = score;
+            winner = i as u8 + 1;
+        }
+    }
+    season.winner_faction = winner;
+    season.finalized = true;
+
+    let season_info = season.to_account_info();
+    commit_accounts(
+        &ctx.accounts.payer,
+        vec![&season_info],
+        &ctx.accounts.magic_context,
+        &ctx.accounts.magic_program,
+    )?;
+
+    msg!("Season {} finalized, winner faction {}", season.id, winner);
+
+    emit!(SeasonFinalized {
+        season: season.id,
+        winner_faction: winner,
+        faction_scores: season.faction_scores,
+        pixels_placed: season.pixels_placed,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+pub(crate) fn archive_season(ctx: Context<ArchiveSeason>, season_id: u32) -> Result<()> {
+    let season_info = &ctx.accounts.season;
+    require!(
+        season_info.owner == &crate::ID || season_info.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::InvalidSeason
+    );
+    let season = Season::try_deserialize(&mut &season_info.data.borrow()[..])?;
+    require!(season.id == season_id && season.finalized, PixelError::SeasonNotFinalized);
+
+    let result = &mut ctx.accounts.result;
+    result.id = season.id;
+    result.start = season.start;
+    result.end = season.end;
+    result.rules = season.rules;
+    result.faction_scores = season.faction_scores;
+    result.pixels_placed = season.pixels_placed;
+    result.winner_faction = season.winner_faction;
+    result.bump = ctx.bumps.result;
+
+    msg!("Season {} archived", season_id);
+    Ok(())
+}
+
+// ========================================
+// Helpers
+// ========================================
+
+/// The season singleton, failing while it is frozen
+/// Read-only so paint transactions don't contend on it; None until initialize_season
+/// runs, in which case painting is open.
+pub(crate) fn load_open_season(season_info: &AccountInfo, now: u64) -> Result<Option<Season>> {
+    if season_info.data_is_empty() {
+        return Ok(None);
+    }
+    require!(
+        season_info.owner == &crate::ID || season_info.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::InvalidSeason
+    );
+    let season = Season::try_deserialize(&mut &season_info.data.borrow()[..])?;
+    season.require_open(now)?;
+    Ok(Some(season))
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Create the season singleton (base layer, admin only)
+#[derive(Accounts)]
+pub struct InitializeSeason<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Season::INIT_SPACE,
+        seeds = [SEASON_SEED],
+        bump
+    )]
+    pub season: Account<'info, Season>,
+
+    /// Program upgrade authority, recorded as the season admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PixelError::NotAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Delegate the season singleton to Ephemeral Rollups
+#[delegate]
+#[derive(Accounts)]
+pub struct DelegateSeason<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: The season PDA to delegate - validated by seeds constraint
+    #[account(mut, del, seeds = [SEASON_SEED], bump)]
+    pub pda: AccountInfo<'info>,
+}
+
+/// Open the next season (ER, admin only)
+#[derive(Accounts)]
+pub struct OpenSeason<'info> {
+    #[account(mut, seeds = [SEASON_SEED], bump = season.bump, has_one = admin @ PixelError::NotAdmin)]
+    pub season: Account<'info, Season>,
+
+    /// Season admin recorded by initialize_season
+    pub admin: Signer<'info>,
+}
+
+/// Snapshot a finalized season (base layer)
+#[derive(Accounts)]
+#[instruction(season_id: u32)]
+pub struct ArchiveSeason<'info> {
+    /// CHECK: The season singleton, could be delegated. Verified by seeds and custom owner check.
+    #[account(seeds = [SEASON_SEED], bump)]
+    pub season: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SeasonResult::INIT_SPACE,
+        seeds = [SEASON_SEED, &season_id.to_le_bytes()],
+        bump
+    )]
+    pub result: Account<'info, SeasonResult>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Finalize the running season and commit it (ER)
+#[commit]
+#[derive(Accounts)]
+pub struct FinalizeSeason<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut, seeds = [SEASON_SEED], bump = season.bump)]
+    pub season: Account<'info, Season>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// The current (or last) season, delegated to the ER
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    /// Season number, 0 before the first season
+    pub id: u32,
+    /// Painting is open in [start, end)
+    pub start: u64,
+    pub end: u64,
+    pub rules: SeasonRules,
+    /// Points per faction, indexed by faction id - 1, rolled up from the Faction accounts
+    /// by finalize_season
+    pub faction_scores: [u64; FACTION_COUNT as usize],
+    /// Pixels placed this season by faction members, rolled up by finalize_season
+    pub pixels_placed: u64,
+    /// Winning faction once finalized (0 = none)
+    pub winner_faction: u8,
+    pub finalized: bool,
+    pub bump: u8,
+    /// Wallet allowed to open seasons, the program upgrade authority at creation
+    pub admin: Pubkey,
+}
+
+impl Season {
+    /// Painting is open while a season runs, and before the first season is opened
+    fn require_open(&self, now: u64) -> Result<()> {
+        require!(
+            self.id == 0 || (self.start <= now && now < self.end),
+            PixelError::SeasonFrozen
+        );
+        Ok(())
+    }
+}
+
+/// Scores of a finished season (base layer only)
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonResult {
+    pub id: u32,
+    pub start: u64,
+    pub end: u64,
+    pub rules: SeasonRules,
+    pub faction_scores: [u64; FACTION_COUNT as usize],
+    pub pixels_placed: u64,
+    pub winner_faction: u8,
+    pub bump: u8,
+}
+
+/// How a season scores painting
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, InitSpace)]
+pub struct SeasonRules {
+    /// Points per pixel placed on the painter's own shards
+    pub points_per_own_pixel: u32,
+    /// Points per pixel placed on someone else's shards
+    pub points_per_enemy_pixel: u32,
+}
+
+// ========================================
+// Events
+// ========================================
+
+#[event]
+pub struct SeasonOpened {
+    pub season: u32,
+    pub start: u64,
+    pub end: u64,
+    pub rules: SeasonRules,
+}
+
+/// `faction_scores` is indexed by faction id - 1
+#[event]
+pub struct SeasonFinalized {
+    pub season: u32,
+    pub winner_faction: u8,
+    pub faction_scores: [u64; FACTION_COUNT as usize],
+    pub pixels_placed: u64,
+    pub timestamp: u64,
+}
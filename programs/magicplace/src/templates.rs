@@ -0,0 +1,608 @@
+//! Painting templates and their bounties
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn create_template<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateTemplate<'info>>,
+    template_id: u32,
+    x: u32,
+    y: u32,
+    w: u16,
+    h: u16,
+    threshold_bps: u16,
+) -> Result<()> {
+    require!(
+        w > 0 && h > 0 && w <= MAX_TEMPLATE_DIMENSION && h <= MAX_TEMPLATE_DIMENSION,
+        PixelError::InvalidTemplate
+    );
+    require!(
+        x as u64 + w as u64 <= CANVAS_RES as u64 && y as u64 + h as u64 <= CANVAS_RES as u64,
+        PixelError::InvalidPixelCoord
+    );
+    require!(
+        threshold_bps > 0 && threshold_bps as u32 <= TEMPLATE_BPS,
+        PixelError::InvalidTemplate
+    );
+
+    // Progress is only credited through the painter layer, so every covering shard needs one
+    let shards = ctx
+        .remaining_accounts
+        .iter()
+        .map(ShardView::load)
+        .collect::<Result<Vec<_>>>()?;
+    for shard in shards.iter() {
+        require!(shard.layers & LAYER_PAINTERS != 0, PixelError::PainterLayerRequired);
+    }
+    require_covering_shards(&shards, x, y, w, h)?;
+
+    let template = &mut ctx.accounts.template;
+    template.creator = ctx.accounts.creator.key();
+    template.id = template_id;
+    template.x = x;
+    template.y = y;
+    template.w = w;
+    template.h = h;
+    template.threshold_bps = threshold_bps;
+    template.bump = ctx.bumps.template;
+    template.target = vec![0; w as usize * h as usize];
+
+    msg!("Template {} created at ({}, {}) {}x{}", template_id, x, y, w, h);
+    Ok(())
+}
+
+pub(crate) fn write_template_chunk(
+    ctx: Context<WriteTemplateChunk>,
+    offset: u16,
+    data: Vec<u8>,
+) -> Result<()> {
+    let template = &mut ctx.accounts.template;
+    require!(!template.locked, PixelError::TemplateLocked);
+    require!(
+        !data.is_empty() && data.len() <= MAX_TEMPLATE_CHUNK,
+        PixelError::InvalidTemplate
+    );
+    let start = offset as usize;
+    require!(start + data.len() <= template.target.len(), PixelError::InvalidTemplate);
+
+    template.target[start..start + data.len()].copy_from_slice(&data);
+    Ok(())
+}
+
+pub(crate) fn lock_template(ctx: Context<LockTemplate>, deadline: u64) -> Result<()> {
+    require!(
+        deadline > Clock::get()?.unix_timestamp as u64,
+        PixelError::InvalidTemplate
+    );
+    let template = &mut ctx.accounts.template;
+    require!(!template.locked, PixelError::TemplateLocked);
+
+    let target_pixels = template.target.iter().filter(|&&color| color != 0).count() as u32;
+    require!(target_pixels > 0, PixelError::InvalidTemplate);
+    template.target_pixels = target_pixels;
+    template.locked = true;
+
+    let bounty = &mut ctx.accounts.bounty;
+    bounty.template = template.key();
+    bounty.deadline = deadline;
+    bounty.bump = ctx.bumps.bounty;
+
+    msg!("Template {} locked with {} target pixels", template.id, target_pixels);
+    Ok(())
+}
+
+pub(crate) fn fund_bounty(ctx: Context<FundBounty>, amount: u64) -> Result<()> {
+    require!(amount > 0, PixelError::InvalidTemplate);
+    require!(ctx.accounts.bounty.completed_at == 0, PixelError::BountyClosed);
+    require!(
+        (Clock::get()?.unix_timestamp as u64) < ctx.accounts.bounty.deadline,
+        PixelError::BountyExpired
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.funder.to_account_info(),
+                to: ctx.accounts.bounty.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let funding = &mut ctx.accounts.funding;
+    if funding.funder == Pubkey::default() {
+        funding.bounty = ctx.accounts.bounty.key();
+        funding.funder = ctx.accounts.funder.key();
+        funding.bump = ctx.bumps.funding;
+    }
+    funding.amount += amount;
+
+    let bounty = &mut ctx.accounts.bounty;
+    bounty.funded += amount;
+
+    emit!(BountyFunded {
+        template: bounty.template,
+        funder: ctx.accounts.funder.key(),
+        amount,
+        funded: bounty.funded,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+    Ok(())
+}
+
+pub(crate) fn check_template_progress<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CheckTemplateProgress<'info>>,
+) -> Result<()> {
+    let template = &ctx.accounts.template;
+    require!(template.locked, PixelError::TemplateNotLocked);
+
+    let mut shards: Vec<ShardView> = Vec::with_capacity(4);
+    let mut painters = Vec::with_capacity(4);
+    let mut accounts = ctx.remaining_accounts.iter();
+    while let Some(info) = accounts.next() {
+        let shard = ShardView::load(info)?;
+        require!(
+            !shards.iter().any(|s| s.key() == shard.key()),
+            PixelError::DuplicateShard
+        );
+
+        // Delegated layers are read at their last committed state, like the shards.
+        // The layer may have been turned off since create_template; nobody can be credited then.
+        require!(shard.layers & LAYER_PAINTERS != 0, PixelError::PainterLayerRequired);
+        let info = accounts.next().ok_or(PixelError::PainterLayerRequired)?;
+        painters.push(load_painter_layer(info, &shard)?);
+        shards.push(shard);
+    }
+
+    // Every covering shard must be present so no contributor can be left out
+    require_covering_shards(&shards, template.x, template.y, template.w, template.h)?;
+
+    let pixels = shards.iter().map(|s| s.pixels()).collect::<Result<Vec<_>>>()?;
+
+    // Matched pixels per (shard, painter slot)
+    let mut slot_pixels = vec![[0u32; MAX_PAINTER_SLOTS + 1]; shards.len()];
+    let mut matched_pixels = 0u32;
+    for (i, &color) in template.target.iter().enumerate() {
+        if color == 0 {
+            continue;
+        }
+        let x = template.x + (i % template.w as usize) as u32;
+        let y = template.y + (i / template.w as usize) as u32;
+        let shard_index = shards
+            .iter()
+            .position(|s| s.shard_x as u32 == x / SHARD_DIMENSION && s.shard_y as u32 == y / SHARD_DIMENSION)
+            .ok_or(PixelError::ShardMismatch)?;
+        let local_pixel_id = local_pixel_index(x % SHARD_DIMENSION, y % SHARD_DIMENSION);
+        if pixels[shard_index][local_pixel_id] != color {
+            continue;
+        }
+        matched_pixels += 1;
+        slot_pixels[shard_index][painters[shard_index].slots[local_pixel_id] as usize] += 1;
+    }
+
+    // Fold slots into main wallets (a wallet can hold a slot on several shards).
+    // Partner programs paint under their off-curve registry PDA and are never paid.
+    let mut contributors: Vec<BountyContributor> = Vec::new();
+    for (layer, counts) in painters.iter().zip(slot_pixels.iter()) {
+        for (slot, &count) in counts.iter().enumerate().skip(1) {
+            let wallet = layer.painters[slot - 1];
+            if count == 0 || !wallet.is_on_curve() {
+                continue;
+            }
+            match contributors.iter_mut().find(|c| c.wallet == wallet) {
+                Some(contributor) => contributor.pixels += count,
+                None => contributors.push(BountyContributor { wallet, pixels: count, claimed: false }),
+            }
+        }
+    }
+    drop(painters);
+    drop(pixels);
+
+    let timestamp = Clock::get()?.unix_timestamp as u64;
+    let template = &mut ctx.accounts.template;
+    template.matched_pixels = matched_pixels;
+    template.last_checked_at = timestamp;
+    let progress_bps = (matched_pixels as u64 * TEMPLATE_BPS as u64 / template.target_pixels as u64) as u16;
+
+    emit!(TemplateProgressChecked {
+        template: template.key(),
+        matched_pixels,
+        target_pixels: template.target_pixels,
+        progress_bps,
+        timestamp,
+    });
+
+    let bounty = &mut ctx.accounts.bounty;
+    if bounty.completed_at == 0
+        && timestamp < bounty.deadline
+        && progress_bps >= template.threshold_bps
+        && !contributors.is_empty()
+    {
+        contributors.sort_by(|a, b| b.pixels.cmp(&a.pixels).then(a.wallet.cmp(&b.wallet)));
+        contributors.truncate(MAX_BOUNTY_CONTRIBUTORS);
+        bounty.contributor_pixels = contributors.iter().map(|c| c.pixels).sum();
+        bounty.contributors = contributors;
+        bounty.completed_at = timestamp;
+
+        msg!("Template {} completed at {} bps", template.id, progress_bps);
+
+        emit!(BountyCompleted {
+            template: template.key(),
+            funded: bounty.funded,
+            contributors: bounty.contributors.len() as u8,
+            timestamp,
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn claim_bounty(ctx: Context<ClaimBounty>) -> Result<()> {
+    let contributor_key = ctx.accounts.contributor.key();
+    let bounty = &mut ctx.accounts.bounty;
+    require!(bounty.completed_at != 0, PixelError::BountyNotCompleted);
+
+    let contributor_pixels = bounty.contributor_pixels;
+    let funded = bounty.funded;
+    let contributor = bounty
+        .contributors
+        .iter_mut()
+        .find(|c| c.wallet == contributor_key)
+        .ok_or(PixelError::NotBountyContributor)?;
+    require!(!contributor.claimed, PixelError::NothingToClaim);
+    contributor.claimed = true;
+
+    let amount = (funded as u128 * contributor.pixels as u128 / contributor_pixels as u128) as u64;
+    let template = bounty.template;
+    // The escrow is program-owned, so lamports move without a system transfer
+    let bounty_info = bounty.to_account_info();
+    **bounty_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.contributor.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    emit!(BountyPaid {
+        template,
+        contributor: contributor_key,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+    Ok(())
+}
+
+pub(crate) fn refund_bounty(ctx: Context<RefundBounty>) -> Result<()> {
+    let bounty = &mut ctx.accounts.bounty;
+    require!(bounty.completed_at == 0, PixelError::BountyClosed);
+    let timestamp = Clock::get()?.unix_timestamp as u64;
+    require!(timestamp >= bounty.deadline, PixelError::BountyOutstanding);
+
+    let amount = ctx.accounts.funding.amount;
+    bounty.funded -= amount;
+    let bounty_info = bounty.to_account_info();
+    **bounty_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.funder.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    emit!(BountyRefunded {
+        template: bounty.template,
+        funder: ctx.accounts.funder.key(),
+        amount,
+        timestamp,
+    });
+    Ok(())
+}
+
+pub(crate) fn sweep_bounty(ctx: Context<SweepBounty>) -> Result<()> {
+    let bounty = &ctx.accounts.bounty;
+    let timestamp = Clock::get()?.unix_timestamp as u64;
+    let settled = if bounty.completed_at != 0 {
+        bounty.contributors.iter().all(|c| c.claimed)
+            || timestamp >= bounty.completed_at + BOUNTY_CLAIM_WINDOW
+    } else {
+        timestamp >= bounty.deadline && bounty.funded == 0
+    };
+    require!(settled, PixelError::BountyOutstanding);
+
+    // Anchor closes the account into the creator after this returns
+    emit!(BountySwept {
+        template: bounty.template,
+        creator: ctx.accounts.creator.key(),
+        amount: bounty.to_account_info().lamports(),
+        timestamp,
+    });
+    Ok(())
+}
+
+// ========================================
+// Helpers
+// ========================================
+
+/// Fail unless `shards` includes every shard the w×h rectangle at global (x, y) covers
+fn require_covering_shards(shards: &[ShardView], x: u32, y: u32, w: u16, h: u16) -> Result<()> {
+    let (x_end, y_end) = (x + w as u32, y + h as u32);
+    for shard_y in y / SHARD_DIMENSION..=(y_end - 1) / SHARD_DIMENSION {
+        for shard_x in x / SHARD_DIMENSION..=(x_end - 1) / SHARD_DIMENSION {
+            require!(
+                shards.iter().any(|s| s.shard_x as u32 == shard_x && s.shard_y as u32 == shard_y),
+                PixelError::ShardMismatch
+            );
+        }
+    }
+    Ok(())
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Create a painting template (base layer); covering shards are remaining accounts
+#[derive(Accounts)]
+#[instruction(template_id: u32, x: u32, y: u32, w: u16, h: u16)]
+pub struct CreateTemplate<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Template::INIT_SPACE + w as usize * h as usize,
+        seeds = [TEMPLATE_SEED, creator.key().as_ref(), &template_id.to_le_bytes()],
+        bump
+    )]
+    pub template: Account<'info, Template>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upload part of a template's target (base layer)
+#[derive(Accounts)]
+pub struct WriteTemplateChunk<'info> {
+    #[account(mut, has_one = creator @ PixelError::InvalidTemplate)]
+    pub template: Account<'info, Template>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Lock a template and open its bounty escrow (base layer)
+#[derive(Accounts)]
+pub struct LockTemplate<'info> {
+    #[account(mut, has_one = creator @ PixelError::InvalidTemplate)]
+    pub template: Account<'info, Template>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Bounty::INIT_SPACE,
+        seeds = [BOUNTY_SEED, template.key().as_ref()],
+        bump
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Fund a template's bounty (base layer)
+#[derive(Accounts)]
+pub struct FundBounty<'info> {
+    #[account(
+        mut,
+        seeds = [BOUNTY_SEED, bounty.template.as_ref()],
+        bump = bounty.bump,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + BountyFunding::INIT_SPACE,
+        seeds = [BOUNTY_FUNDING_SEED, bounty.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub funding: Account<'info, BountyFunding>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Measure a template's completion (base layer); covering shards are remaining accounts
+#[derive(Accounts)]
+pub struct CheckTemplateProgress<'info> {
+    #[account(mut)]
+    pub template: Account<'info, Template>,
+
+    #[account(
+        mut,
+        seeds = [BOUNTY_SEED, template.key().as_ref()],
+        bump = bounty.bump,
+    )]
+    pub bounty: Account<'info, Bounty>,
+}
+
+/// Claim a share of a completed bounty (base layer)
+#[derive(Accounts)]
+pub struct ClaimBounty<'info> {
+    #[account(
+        mut,
+        seeds = [BOUNTY_SEED, bounty.template.as_ref()],
+        bump = bounty.bump,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    /// Contributor's main wallet, as recorded by the painter layer
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+}
+
+/// Refund a funder of an expired bounty (base layer)
+#[derive(Accounts)]
+pub struct RefundBounty<'info> {
+    #[account(
+        mut,
+        seeds = [BOUNTY_SEED, bounty.template.as_ref()],
+        bump = bounty.bump,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        close = funder,
+        seeds = [BOUNTY_FUNDING_SEED, bounty.key().as_ref(), funder.key().as_ref()],
+        bump = funding.bump,
+    )]
+    pub funding: Account<'info, BountyFunding>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+}
+
+/// Close a settled bounty into its template's creator (base layer)
+#[derive(Accounts)]
+pub struct SweepBounty<'info> {
+    #[account(has_one = creator @ PixelError::InvalidTemplate)]
+    pub template: Account<'info, Template>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [BOUNTY_SEED, template.key().as_ref()],
+        bump = bounty.bump,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// Target image of a painting template (base layer only)
+/// Only the fixed fields count towards INIT_SPACE; the account is created with room
+/// for w×h target bytes.
+#[account]
+#[derive(InitSpace)]
+pub struct Template {
+    pub creator: Pubkey,
+    pub id: u32,
+    /// Global top-left corner
+    pub x: u32,
+    pub y: u32,
+    pub w: u16,
+    pub h: u16,
+    /// Completion (basis points of target pixels) that completes the bounty
+    pub threshold_bps: u16,
+    /// Non-zero target pixels, counted by lock_template
+    pub target_pixels: u32,
+    /// Locked templates can no longer change and have a bounty escrow
+    pub locked: bool,
+    /// Target pixels matching the canvas at the last check_template_progress
+    pub matched_pixels: u32,
+    pub last_checked_at: u64,
+    pub bump: u8,
+    /// Palette index of each pixel, row-major (0 = don't care)
+    #[max_len(0)]
+    pub target: Vec<u8>,
+}
+
+/// Lamport escrow of a template; the lamports are held by this account (base layer only)
+#[account]
+#[derive(InitSpace)]
+pub struct Bounty {
+    pub template: Pubkey,
+    /// Lamports deposited by funders, less refunds
+    pub funded: u64,
+    /// Last moment (unix time) the bounty can complete
+    pub deadline: u64,
+    /// When the template crossed its threshold (0 = still open)
+    pub completed_at: u64,
+    /// Sum of the contributors' pixels, the denominator of each share
+    pub contributor_pixels: u32,
+    /// Top painters of matching pixels at completion, most pixels first
+    #[max_len(MAX_BOUNTY_CONTRIBUTORS)]
+    pub contributors: Vec<BountyContributor>,
+    pub bump: u8,
+}
+
+/// One funder's total deposit into a bounty, refundable if the bounty expires
+#[account]
+#[derive(InitSpace)]
+pub struct BountyFunding {
+    pub bounty: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, InitSpace)]
+pub struct BountyContributor {
+    /// Main wallet
+    pub wallet: Pubkey,
+    /// Matching template pixels it painted last
+    pub pixels: u32,
+    pub claimed: bool,
+}
+
+// ========================================
+// Events
+// ========================================
+
+#[event]
+pub struct BountyFunded {
+    pub template: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    /// Total lamports funded so far
+    pub funded: u64,
+    pub timestamp: u64,
+}
+
+/// Result of a check_template_progress call
+#[event]
+pub struct TemplateProgressChecked {
+    pub template: Pubkey,
+    pub matched_pixels: u32,
+    pub target_pixels: u32,
+    pub progress_bps: u16,
+    pub timestamp: u64,
+}
+
+/// A template crossed its threshold; `contributors` can now claim their shares
+#[event]
+pub struct BountyCompleted {
+    pub template: Pubkey,
+    pub funded: u64,
+    pub contributors: u8,
+    pub timestamp: u64,
+}
+
+#[event]
+pub struct BountyPaid {
+    pub template: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub timestamp: u64,
+}
+
+/// `funder` took back `amount` lamports from a bounty that missed its deadline
+#[event]
+pub struct BountyRefunded {
+    pub template: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub timestamp: u64,
+}
+
+/// A settled bounty was closed, returning `amount` lamports (leftovers and rent) to `creator`
+#[event]
+pub struct BountySwept {
+    pub template: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: u64,
+}
@@ -0,0 +1,179 @@
+//! Read-only views returned through return data
+
+use super::*;
+
+// ========================================
+// Instructions
+// ========================================
+
+pub(crate) fn get_pixel(
+    ctx: Context<GetPixel>,
+    shard_x: u16,
+    shard_y: u16,
+    px: u32,
+    py: u32,
+) -> Result<u8> {
+    require!(px < CANVAS_RES && py < CANVAS_RES, PixelError::InvalidPixelCoord);
+    require!(
+        px / SHARD_DIMENSION == shard_x as u32 && py / SHARD_DIMENSION == shard_y as u32,
+        PixelError::ShardMismatch
+    );
+
+    let shard = ShardView::load(&ctx.accounts.shard)?;
+    let pixels = shard.pixels()?;
+    Ok(pixels[local_pixel_index(px % SHARD_DIMENSION, py % SHARD_DIMENSION)])
+}
+
+pub(crate) fn get_region(ctx: Context<GetPixel>, x0: u8, y0: u8, w: u8, h: u8) -> Result<Vec<u8>> {
+    require!(w > 0 && h > 0, PixelError::InvalidPixelCoord);
+    require!(
+        valid_local(x0, y0) && x0 as u32 + w as u32 <= SHARD_DIMENSION && y0 as u32 + h as u32 <= SHARD_DIMENSION,
+        PixelError::InvalidPixelCoord
+    );
+    require!(w as usize * h as usize <= MAX_VIEW_REGION_PIXELS, PixelError::RegionTooLarge);
+
+    let shard = ShardView::load(&ctx.accounts.shard)?;
+    let pixels = shard.pixels()?;
+    let mut region = Vec::with_capacity(w as usize * h as usize);
+    for y in y0 as u32..y0 as u32 + h as u32 {
+        let row = local_pixel_index(x0 as u32, y);
+        region.extend_from_slice(&pixels[row..row + w as usize]);
+    }
+    Ok(region)
+}
+
+pub(crate) fn get_artwork_pixels<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetArtworkPixels<'info>>,
+    row: u16,
+    rows: u16,
+) -> Result<Vec<u8>> {
+    let artwork = &ctx.accounts.artwork;
+    require!(
+        rows > 0 && row as u32 + rows as u32 <= artwork.h as u32,
+        PixelError::InvalidPixelCoord
+    );
+    require!(
+        rows as usize * artwork.w as usize <= MAX_VIEW_REGION_PIXELS,
+        PixelError::RegionTooLarge
+    );
+
+    let shards = ctx
+        .remaining_accounts
+        .iter()
+        .map(ShardView::load)
+        .collect::<Result<Vec<_>>>()?;
+
+    let x_end = artwork.x + artwork.w as u32;
+    let mut region = Vec::with_capacity(rows as usize * artwork.w as usize);
+    for y in artwork.y + row as u32..artwork.y + row as u32 + rows as u32 {
+        let mut x = artwork.x;
+        // One slice per shard the row crosses
+        while x < x_end {
+            let (shard_x, shard_y) = ((x / SHARD_DIMENSION) as u16, (y / SHARD_DIMENSION) as u16);
+            let segment_end = x_end.min((shard_x as u32 + 1) * SHARD_DIMENSION);
+            let len = (segment_end - x) as usize;
+            match shards.iter().find(|s| s.shard_x == shard_x && s.shard_y == shard_y) {
+                Some(shard) => {
+                    let start = local_pixel_index(x % SHARD_DIMENSION, y % SHARD_DIMENSION);
+                    region.extend_from_slice(&shard.pixels()?[start..start + len]);
+                }
+                None => region.resize(region.len() + len, 0),
+            }
+            x = segment_end;
+        }
+    }
+    Ok(region)
+}
+
+pub(crate) fn get_cooldown_status(ctx: Context<GetCooldownStatus>) -> Result<CooldownStatus> {
+    let session_info = &ctx.accounts.session;
+    require!(
+        session_info.owner == &crate::ID || session_info.owner == &DELEGATION_PROGRAM_ID,
+        PixelError::InvalidAuth
+    );
+    let session = SessionAccount::try_deserialize(&mut &session_info.data.borrow()[..])?;
+
+    let tier = match &ctx.accounts.profile {
+        Some(profile_info) => {
+            require!(
+                profile_info.owner == &crate::ID || profile_info.owner == &DELEGATION_PROGRAM_ID,
+                PixelError::InvalidProfile
+            );
+            let profile = PlayerProfile::try_deserialize(&mut &profile_info.data.borrow()[..])?;
+            require!(profile.main_wallet == session.main_address, PixelError::InvalidProfile);
+            profile.tier()
+        }
+        None => 0,
+    };
+    let (limit, period) = cooldown_for_tier(tier);
+
+    let elapsed = (Clock::get()?.unix_timestamp as u64).saturating_sub(session.last_place_timestamp);
+    let status = if session.cooldown_counter < limit {
+        CooldownStatus {
+            pixels_remaining: limit - session.cooldown_counter,
+            remaining_seconds: 0,
+            tier,
+        }
+    } else if elapsed >= period {
+        // The next placement resets the burst
+        CooldownStatus {
+            pixels_remaining: limit,
+            remaining_seconds: 0,
+            tier,
+        }
+    } else {
+        CooldownStatus {
+            pixels_remaining: 0,
+            remaining_seconds: period - elapsed,
+            tier,
+        }
+    };
+    Ok(status)
+}
+
+// ========================================
+// Account Structs
+// ========================================
+
+/// Read one shard for a view instruction (either layer)
+#[derive(Accounts)]
+#[instruction(shard_x: u16, shard_y: u16)]
+pub struct GetPixel<'info> {
+    /// CHECK: The shard, could be delegated. Verified by seeds and loaded via ShardView.
+    #[account(
+        seeds = [SHARD_SEED, &shard_x.to_le_bytes(), &shard_y.to_le_bytes()],
+        bump
+    )]
+    pub shard: UncheckedAccount<'info>,
+}
+
+/// Read an artwork's live pixels (either layer); covering shards are remaining accounts
+#[derive(Accounts)]
+pub struct GetArtworkPixels<'info> {
+    pub artwork: Account<'info, Artwork>,
+}
+
+/// Read a session's cooldown state (either layer)
+#[derive(Accounts)]
+pub struct GetCooldownStatus<'info> {
+    /// CHECK: The session account, could be delegated. Verified by custom owner check and discriminator.
+    pub session: UncheckedAccount<'info>,
+
+    /// CHECK: The wallet's profile, could be delegated. Checked against the session's main wallet.
+    pub profile: Option<UncheckedAccount<'info>>,
+}
+
+// ========================================
+// Account Data
+// ========================================
+
+/// Returned by get_cooldown_status
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CooldownStatus {
+    /// Pixels the session can still place on others' shards right now
+    pub pixels_remaining: u8,
+    /// Seconds until the burst resets (0 when not cooling down)
+    pub remaining_seconds: u64,
+    /// XP tier the burst and period were computed for
+    pub tier: u8,
+}
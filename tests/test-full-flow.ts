@@ -54,9 +54,13 @@ async function testFullFlow() {
   console.log("\n--- STEP 1: Initialize Shard ---");
   try {
     const initTx = await program.methods
-      .initializeShard(shardX, shardY)
+      .initializeShard(shardX, shardY, provider.wallet.publicKey)
       .accounts({
         authority: provider.wallet.publicKey,
+        walletStats: PublicKey.findProgramAddressSync(
+          [Buffer.from("wallet_stats"), provider.wallet.publicKey.toBuffer()],
+          program.programId
+        )[0],
       })
       .rpc();
     console.log(`Initialize TX: ${initTx}`);